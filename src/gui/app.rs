@@ -10,11 +10,19 @@ use crate::{
         components::{AutoUpdate, NavBar, Tab, TabBar, Table},
         format_code,
         mass_effect_1::{Me1General, Me1Plot, Me1RawData, Me1RawPlot},
-        mass_effect_1_le::{Me1LeGeneral, Me1LeInventory},
-        mass_effect_2::{Me2General, Me2Plot, Me2RawPlot, Me2Type},
-        mass_effect_3::{Me3General, Me3Plot, Me3RawPlot},
+        mass_effect_1_le::{Me1LeGalaxy, Me1LeGeneral, Me1LeInventory, Me1LeSquad, Me1LeWorld},
+        mass_effect_2::{
+            Me2General, Me2Plot, Me2RawPlot, Me2Research, Me2Squad, Me2SuicideMission, Me2Type,
+        },
+        mass_effect_3::{
+            Me3General, Me3Plot, Me3Powers, Me3PowersType, Me3RawPlot, Me3Squad, Me3WarAssets,
+            Me3Weapons,
+        },
         raw_ui::RawUi,
-        shared::HeadMorph,
+        shared::{
+            Crew, CrewType, GalaxyMap, GalaxyMapType, HeadMorph, Journal, JournalType,
+            LevelRecords, LevelRecordsType, RecordLists, Romance, RomanceType,
+        },
         shared::{FloatPlotType, IntPlotType},
     },
     save_data::{
@@ -201,6 +209,15 @@ fn mass_effect_1(save_game: Ref<'_, Me1SaveGame>) -> Html {
                         integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
                     />
                 </Tab>
+                <Tab title="Romance">
+                    <Romance romance={RomanceType::Me1(
+                        RcRef::clone(&plot.booleans),
+                        IntPlotType::Vec(RcRef::clone(&plot.integers)),
+                    )} />
+                </Tab>
+                <Tab title="Crew">
+                    <Crew crew={CrewType::Me1(RcRef::clone(&plot.booleans))} />
+                </Tab>
                 <Tab title="Raw Data">
                     <Me1RawData player={RcRef::clone(&save_game.player)} />
                 </Tab>
@@ -233,12 +250,36 @@ fn mass_effect_1_le(save_game: RcRef<Me1LeSaveData>) -> Html {
                         integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
                     />
                 </Tab>
+                <Tab title="Squad">
+                    <Me1LeSquad squad={RcRef::clone(&me1.squad)} />
+                </Tab>
                 <Tab title="Inventory">
                     <Me1LeInventory
                         player={RcRef::clone(&me1.player)}
                         squad={RcRef::clone(&me1.squad)}
                     />
                 </Tab>
+                <Tab title="World">
+                    <Me1LeWorld save_game={RcRef::clone(&save_game)} />
+                </Tab>
+                <Tab title="Galaxy">
+                    <Me1LeGalaxy save_game={RcRef::clone(&save_game)} />
+                </Tab>
+                <Tab title="Journal">
+                    <Journal journal={JournalType::Me1Le(
+                        RcRef::clone(&me1.journal),
+                        RcRef::clone(&me1.codex),
+                    )} />
+                </Tab>
+                <Tab title="Romance">
+                    <Romance romance={RomanceType::Me1(
+                        RcRef::clone(&plot.booleans),
+                        IntPlotType::Vec(RcRef::clone(&plot.integers)),
+                    )} />
+                </Tab>
+                <Tab title="Crew">
+                    <Crew crew={CrewType::Me1(RcRef::clone(&plot.booleans))} />
+                </Tab>
                 <Tab title="Head Morph">
                     <HeadMorph {head_morph} />
                 </Tab>
@@ -258,21 +299,56 @@ fn mass_effect_1_le(save_game: RcRef<Me1LeSaveData>) -> Html {
 }
 
 fn mass_effect_2(save_game: Me2Type) -> Html {
-    let (raw_data, plot, me1_plot, head_morph) = match save_game {
-        Me2Type::Vanilla(ref me2) => (
-            me2.view_opened("Mass Effect 2", true),
-            RcRef::clone(&me2.borrow().plot),
-            RcRef::clone(&me2.borrow().me1_plot),
-            RcRef::clone(&me2.borrow().player().appearance().head_morph),
-        ),
-        Me2Type::Legendary(ref me2) => (
-            me2.view_opened("Mass Effect 2", true),
-            RcRef::clone(&me2.borrow().plot),
-            RcRef::clone(&me2.borrow().me1_plot),
-            RcRef::clone(&me2.borrow().player().appearance().head_morph),
-        ),
-    };
+    let (raw_data, plot, me1_plot, player, squad, galaxy_map, head_morph, journal, records) =
+        match save_game {
+            Me2Type::Vanilla(ref me2) => (
+                me2.view_opened("Mass Effect 2", true),
+                RcRef::clone(&me2.borrow().plot),
+                RcRef::clone(&me2.borrow().me1_plot),
+                RcRef::clone(&me2.borrow().player),
+                RcRef::clone(&me2.borrow().squad),
+                RcRef::clone(&me2.borrow().galaxy_map),
+                RcRef::clone(&me2.borrow().player().appearance().head_morph),
+                JournalType::Me2(
+                    RcRef::clone(&me2.borrow().journal),
+                    RcRef::clone(&me2.borrow().codex),
+                ),
+                LevelRecordsType::Me2(RecordLists {
+                    kismet_records: RcRef::clone(&me2.borrow().kismet_records),
+                    doors: RcRef::clone(&me2.borrow().doors),
+                    placeables: None,
+                    pawns: RcRef::clone(&me2.borrow().pawns),
+                }),
+            ),
+            Me2Type::Legendary(ref me2) => (
+                me2.view_opened("Mass Effect 2", true),
+                RcRef::clone(&me2.borrow().plot),
+                RcRef::clone(&me2.borrow().me1_plot),
+                RcRef::clone(&me2.borrow().player),
+                RcRef::clone(&me2.borrow().squad),
+                RcRef::clone(&me2.borrow().galaxy_map),
+                RcRef::clone(&me2.borrow().player().appearance().head_morph),
+                JournalType::Me2(
+                    RcRef::clone(&me2.borrow().journal),
+                    RcRef::clone(&me2.borrow().codex),
+                ),
+                LevelRecordsType::Me2(RecordLists {
+                    kismet_records: RcRef::clone(&me2.borrow().kismet_records),
+                    doors: RcRef::clone(&me2.borrow().doors),
+                    placeables: None,
+                    pawns: RcRef::clone(&me2.borrow().pawns),
+                }),
+            ),
+        };
+    let research = html! { <Me2Research plot={RcRef::clone(&plot)} {player} /> };
+    let suicide_mission = html! { <Me2SuicideMission plot={RcRef::clone(&plot)} /> };
+    let squad = html! { <Me2Squad {squad} plot={RcRef::clone(&plot)} /> };
     let (plot, me1_plot) = (plot.borrow(), me1_plot.borrow());
+    let romance = RomanceType::Me2(
+        RcRef::clone(&plot.booleans),
+        IntPlotType::Vec(RcRef::clone(&plot.integers)),
+    );
+    let crew = CrewType::Me2(RcRef::clone(&plot.booleans), RcRef::clone(&me1_plot.booleans));
 
     html! {
         <section class="flex-auto flex p-1">
@@ -280,6 +356,9 @@ fn mass_effect_2(save_game: Me2Type) -> Html {
                 <Tab title="General">
                     <Me2General save_game={Me2Type::clone(&save_game)} />
                 </Tab>
+                <Tab title="Squad">
+                    { squad }
+                </Tab>
                 <Tab title="Plot">
                     <Me2Plot
                         booleans={RcRef::clone(&plot.booleans)}
@@ -288,6 +367,27 @@ fn mass_effect_2(save_game: Me2Type) -> Html {
                         me1_integers={IntPlotType::Vec(RcRef::clone(&me1_plot.integers))}
                     />
                 </Tab>
+                <Tab title="Research">
+                    { research }
+                </Tab>
+                <Tab title="Suicide Mission">
+                    { suicide_mission }
+                </Tab>
+                <Tab title="Galaxy Map">
+                    <GalaxyMap galaxy_map={GalaxyMapType::Me2(galaxy_map)} />
+                </Tab>
+                <Tab title="Journal">
+                    <Journal {journal} />
+                </Tab>
+                <Tab title="Romance">
+                    <Romance {romance} />
+                </Tab>
+                <Tab title="Crew">
+                    <Crew {crew} />
+                </Tab>
+                <Tab title="World">
+                    <LevelRecords {records} />
+                </Tab>
                 <Tab title="Head Morph">
                     <HeadMorph {head_morph} />
                 </Tab>
@@ -309,6 +409,19 @@ fn mass_effect_2(save_game: Me2Type) -> Html {
 fn mass_effect_3(save_game: RcRef<Me3SaveGame>) -> Html {
     let me3 = save_game.borrow();
     let plot = me3.plot();
+    let galaxy_map = RcRef::clone(&me3.galaxy_map);
+    let journal = JournalType::Me3(RcRef::clone(&me3.journal), RcRef::clone(&me3.codex));
+    let romance = RomanceType::Me3(
+        RcRef::clone(&plot.booleans),
+        IntPlotType::IndexMap(RcRef::clone(&plot.integers)),
+    );
+    let crew = CrewType::Me3(RcRef::clone(&plot.booleans));
+    let records = LevelRecordsType::Me3(RecordLists {
+        kismet_records: RcRef::clone(&me3.kismet_records),
+        doors: RcRef::clone(&me3.doors),
+        placeables: Some(RcRef::clone(&me3.placeables)),
+        pawns: RcRef::clone(&me3.pawns),
+    });
     let head_morph = RcRef::clone(&me3.player().appearance().head_morph);
 
     html! {
@@ -317,6 +430,22 @@ fn mass_effect_3(save_game: RcRef<Me3SaveGame>) -> Html {
                 <Tab title="General">
                     <Me3General save_game={RcRef::clone(&save_game)} />
                 </Tab>
+                <Tab title="Powers">
+                    <Me3Powers character={Me3PowersType::Player(RcRef::clone(&me3.player))} />
+                </Tab>
+                <Tab title="Weapons">
+                    <Me3Weapons
+                        player={RcRef::clone(&me3.player)}
+                        plot={RcRef::clone(&me3.plot)}
+                        variables={RcRef::clone(&me3.player_variables)}
+                    />
+                </Tab>
+                <Tab title="Squad">
+                    <Me3Squad
+                        squad={RcRef::clone(&me3.squad)}
+                        player={RcRef::clone(&me3.player)}
+                    />
+                </Tab>
                 <Tab title="Plot">
                     <Me3Plot
                         booleans={RcRef::clone(&plot.booleans)}
@@ -324,6 +453,27 @@ fn mass_effect_3(save_game: RcRef<Me3SaveGame>) -> Html {
                         variables={RcRef::clone(&me3.player_variables)}
                     />
                 </Tab>
+                <Tab title="War Assets">
+                    <Me3WarAssets
+                        player={RcRef::clone(&me3.player)}
+                        plot={RcRef::clone(&me3.plot)}
+                    />
+                </Tab>
+                <Tab title="Galaxy Map">
+                    <GalaxyMap galaxy_map={GalaxyMapType::Me3(galaxy_map)} />
+                </Tab>
+                <Tab title="Journal">
+                    <Journal {journal} />
+                </Tab>
+                <Tab title="Romance">
+                    <Romance {romance} />
+                </Tab>
+                <Tab title="Crew">
+                    <Crew {crew} />
+                </Tab>
+                <Tab title="World">
+                    <LevelRecords {records} />
+                </Tab>
                 <Tab title="Head Morph">
                     <HeadMorph {head_morph} />
                 </Tab>
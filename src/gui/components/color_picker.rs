@@ -1,6 +1,6 @@
 use std::cell::{Ref, RefMut};
 
-use anyhow::ensure;
+use gloo::storage::{LocalStorage, Storage};
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
@@ -9,12 +9,23 @@ use crate::{
     save_data::{shared::appearance::LinearColor, RcRef},
 };
 
+const PALETTE_KEY: &str = "color_palette";
+const MAX_INTENSITY: f32 = 20.0;
+
 pub enum Msg {
     R(CallbackType),
     G(CallbackType),
     B(CallbackType),
     A(CallbackType),
+    SrgbR(CallbackType),
+    SrgbG(CallbackType),
+    SrgbB(CallbackType),
     Change(Event),
+    Intensity(InputEvent),
+    ToggleTools,
+    SaveToPalette,
+    ApplyPalette(usize),
+    ClearPalette,
 }
 
 #[derive(Properties, PartialEq)]
@@ -33,14 +44,16 @@ impl Props {
     }
 }
 
-pub struct ColorPicker;
+pub struct ColorPicker {
+    tools_opened: bool,
+}
 
 impl Component for ColorPicker {
     type Message = Msg;
     type Properties = Props;
 
     fn create(_ctx: &Context<Self>) -> Self {
-        ColorPicker
+        ColorPicker { tools_opened: false }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -61,28 +74,57 @@ impl Component for ColorPicker {
                 ctx.props().color_mut().a = value;
                 true
             }
-            Msg::Change(event) => {
-                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
-                    let color = input.value();
-
-                    let hex_to_rgb = |hex: String| {
-                        ensure!(hex.len() == 7, "invalid color");
-
-                        let r = u8::from_str_radix(&hex[1..3], 16)?;
-                        let g = u8::from_str_radix(&hex[3..5], 16)?;
-                        let b = u8::from_str_radix(&hex[5..7], 16)?;
-                        Ok((r, g, b))
-                    };
-
-                    if let Ok((r, g, b)) = hex_to_rgb(color) {
-                        let mut color = ctx.props().color_mut();
-                        color.r = r as f32 / 255.0;
-                        color.g = g as f32 / 255.0;
-                        color.b = b as f32 / 255.0;
-                        return true;
-                    }
+            Msg::SrgbR(CallbackType::Byte(value)) => {
+                let (_, g, b) = ctx.props().color().to_srgb();
+                ctx.props().color_mut().set_srgb(value, g, b);
+                true
+            }
+            Msg::SrgbG(CallbackType::Byte(value)) => {
+                let (r, _, b) = ctx.props().color().to_srgb();
+                ctx.props().color_mut().set_srgb(r, value, b);
+                true
+            }
+            Msg::SrgbB(CallbackType::Byte(value)) => {
+                let (r, g, _) = ctx.props().color().to_srgb();
+                ctx.props().color_mut().set_srgb(r, g, value);
+                true
+            }
+            Msg::Change(event) => match event.target_dyn_into::<HtmlInputElement>() {
+                Some(input) => {
+                    // Invalid values are discarded by the re-render
+                    let _ = ctx.props().color_mut().set_hex(&input.value());
+                    true
+                }
+                None => false,
+            },
+            Msg::Intensity(event) => match event.target_dyn_into::<HtmlInputElement>() {
+                Some(input) => {
+                    let intensity = input.value_as_number() as f32;
+                    ctx.props().color_mut().set_intensity(intensity);
+                    true
                 }
-                false
+                None => false,
+            },
+            Msg::ToggleTools => {
+                self.tools_opened = !self.tools_opened;
+                true
+            }
+            Msg::SaveToPalette => {
+                let mut palette = Self::palette();
+                palette.push(ctx.props().color().clone());
+                let _ = LocalStorage::set(PALETTE_KEY, palette);
+                true
+            }
+            Msg::ApplyPalette(idx) => match Self::palette().get(idx) {
+                Some(color) => {
+                    *ctx.props().color_mut() = color.clone();
+                    true
+                }
+                None => false,
+            },
+            Msg::ClearPalette => {
+                LocalStorage::delete(PALETTE_KEY);
+                true
             }
             _ => unreachable!(),
         }
@@ -93,31 +135,86 @@ impl Component for ColorPicker {
             let colors = ctx.props().color();
             (colors.r, colors.g, colors.b, colors.a)
         };
-        let hex_color = {
-            let max_color = r.max(g.max(b));
-            let ratio = max_color.max(1.0);
-            let r = (r * 255.0 / ratio) as u8;
-            let g = (g * 255.0 / ratio) as u8;
-            let b = (b * 255.0 / ratio) as u8;
-            format!("#{:02X}{:02X}{:02X}", r, g, b)
-        };
+        let hex_color = ctx.props().color().to_hex();
+        let tools = self.tools_opened.then(|| self.tools(ctx));
+
+        html! {
+            <div class="flex flex-col gap-1">
+                <div class="flex gap-2">
+                    <InputNumber label="R" value={NumberType::Float(r.into())} onchange={ctx.link().callback(Msg::R)} />
+                    <InputNumber label="G" value={NumberType::Float(g.into())} onchange={ctx.link().callback(Msg::G)} />
+                    <InputNumber label="B" value={NumberType::Float(b.into())} onchange={ctx.link().callback(Msg::B)} />
+                    <InputNumber label="A" value={NumberType::Float(a.into())} onchange={ctx.link().callback(Msg::A)} />
+                    <label class="flex-auto flex items-center gap-1">
+                        <span class="border border-default-border w-5 h-5" style={format!("background-color: {}", hex_color)}>
+                            <input type="color"
+                                class="opacity-0"
+                                value={hex_color}
+                                onchange={ctx.link().callback(Msg::Change)}
+                            />
+                        </span>
+                        { &ctx.props().label }
+                    </label>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::ToggleTools)}>
+                        { if self.tools_opened { "Close" } else { "Tools" } }
+                    </button>
+                </div>
+                { for tools }
+            </div>
+        }
+    }
+}
+
+impl ColorPicker {
+    fn palette() -> Vec<LinearColor> {
+        LocalStorage::get(PALETTE_KEY).unwrap_or_default()
+    }
+
+    fn tools(&self, ctx: &Context<Self>) -> Html {
+        let color = ctx.props().color();
+        let (r, g, b) = color.to_srgb();
+        let hex_color = color.to_hex();
+        let intensity = color.intensity();
+
+        let palette = Self::palette().into_iter().enumerate().map(|(idx, color)| {
+            html! {
+                <button class="border border-default-border w-5 h-5"
+                    style={format!("background-color: {}", color.to_hex())}
+                    title={format!("{} x{:.1}", color.to_hex(), color.intensity())}
+                    onclick={ctx.link().callback(move |_| Msg::ApplyPalette(idx))}
+                />
+            }
+        });
 
         html! {
-            <div class="flex gap-2">
-                <InputNumber label="R" value={NumberType::Float(r.into())} onchange={ctx.link().callback(Msg::R)} />
-                <InputNumber label="G" value={NumberType::Float(g.into())} onchange={ctx.link().callback(Msg::G)} />
-                <InputNumber label="B" value={NumberType::Float(b.into())} onchange={ctx.link().callback(Msg::B)} />
-                <InputNumber label="A" value={NumberType::Float(a.into())} onchange={ctx.link().callback(Msg::A)} />
-                <label class="flex-auto flex items-center gap-1">
-                    <span class="border border-default-border w-5 h-5" style={format!("background-color: {}", hex_color)}>
-                        <input type="color"
-                            class="opacity-0"
-                            value={hex_color}
+            <div class="flex flex-col gap-1 pl-4">
+                <div class="flex items-center gap-2">
+                    <label class="flex items-center gap-1">
+                        <input type="text" class="input w-[110px]" value={hex_color}
                             onchange={ctx.link().callback(Msg::Change)}
                         />
-                    </span>
-                    { &ctx.props().label }
+                        {"Hex"}
+                    </label>
+                    <InputNumber label="sR" value={NumberType::Byte(r.into())} onchange={ctx.link().callback(Msg::SrgbR)} />
+                    <InputNumber label="sG" value={NumberType::Byte(g.into())} onchange={ctx.link().callback(Msg::SrgbG)} />
+                    <InputNumber label="sB" value={NumberType::Byte(b.into())} onchange={ctx.link().callback(Msg::SrgbB)} />
+                </div>
+                <label class="flex items-center gap-1">
+                    <input type="range" class="w-[220px]" min="1" max={MAX_INTENSITY.to_string()} step="0.1"
+                        value={intensity.to_string()}
+                        oninput={ctx.link().callback(Msg::Intensity)}
+                    />
+                    { format!("HDR intensity x{:.1}", intensity) }
                 </label>
+                <div class="flex flex-wrap items-center gap-1">
+                    { for palette }
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::SaveToPalette)}>
+                        {"Save to palette"}
+                    </button>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::ClearPalette)}>
+                        {"Clear palette"}
+                    </button>
+                </div>
             </div>
         }
     }
@@ -1,6 +1,43 @@
+use std::rc::Rc;
+
 use gloo::utils;
 use web_sys::HtmlElement;
-use yew::prelude::*;
+use yew::{html::IntoPropValue, prelude::*};
+
+/// Options of a `Select`, static ones for enums and owned ones for database entries.
+#[derive(Clone, PartialEq)]
+pub enum SelectOptions {
+    Static(&'static [&'static str]),
+    Owned(Rc<[String]>),
+}
+
+impl SelectOptions {
+    fn get(&self, idx: usize) -> Option<&str> {
+        match self {
+            SelectOptions::Static(options) => options.get(idx).copied(),
+            SelectOptions::Owned(options) => options.get(idx).map(String::as_str),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            SelectOptions::Static(options) => options.len(),
+            SelectOptions::Owned(options) => options.len(),
+        }
+    }
+}
+
+impl IntoPropValue<SelectOptions> for &'static [&'static str] {
+    fn into_prop_value(self) -> SelectOptions {
+        SelectOptions::Static(self)
+    }
+}
+
+impl IntoPropValue<SelectOptions> for Vec<String> {
+    fn into_prop_value(self) -> SelectOptions {
+        SelectOptions::Owned(self.into())
+    }
+}
 
 pub enum Msg {
     Open,
@@ -11,7 +48,7 @@ pub enum Msg {
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
-    pub options: &'static [&'static str],
+    pub options: SelectOptions,
     pub current_idx: usize,
     pub onselect: Callback<usize>,
     #[prop_or(true)]
@@ -95,7 +132,8 @@ impl Component for Select {
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let drop_down = self.opened.then(|| {
-            let options = ctx.props().options.iter().enumerate().map(|(idx, option)| {
+            let options = (0..ctx.props().options.len()).map(|idx| {
+                let option = ctx.props().options.get(idx).unwrap_or_default();
                 let selected = idx == self.current_idx;
                 html! {
                     <a
@@ -147,7 +185,7 @@ impl Component for Select {
                     ]}
                     {onclick}
                 >
-                    { ctx.props().options[self.current_idx] }
+                    { ctx.props().options.get(self.current_idx).unwrap_or_default() }
                 </a>
                 <div
                     class={classes![
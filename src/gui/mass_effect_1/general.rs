@@ -14,7 +14,7 @@ use crate::{
     gui::{
         components::{Select, Table},
         mass_effect_1::property::Property,
-        raw_ui::RawUi,
+        shared::{IntPlotType, Morality, MoralityType},
     },
     save_data::{mass_effect_1::data::StructType, RcRef},
 };
@@ -183,10 +183,7 @@ impl Me1General {
     fn morality(ctx: &Context<Self>) -> Html {
         let plot = ctx.props().plot();
         html! {
-            <Table title="Morality">
-                { for plot.integers().get(47).map(|paragon| paragon.view("Paragon")) }
-                { for plot.integers().get(46).map(|renegade| renegade.view("Renegade")) }
-            </Table>
+            <Morality morality={MoralityType::Me1(IntPlotType::Vec(RcRef::clone(&plot.integers)), None)} />
         }
     }
 
@@ -0,0 +1,279 @@
+use std::rc::Rc;
+
+use gloo::storage::{LocalStorage, Storage};
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{CheckBox, Table},
+    save_data::{
+        mass_effect_1_le::{
+            galaxy_db::{Me1LeGalaxyDb, Me1LeSafeSpot, Me1LeSystem},
+            legacy::World,
+            Me1LeSaveData,
+        },
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    MarkAllScanned(RcRef<World>),
+    PlanetScanned(RcRef<World>, i32, bool),
+    ResetCinematics(RcRef<World>),
+    Relocate(usize),
+    SaveLocation,
+    RelocateSaved(usize),
+    ClearSavedLocations,
+}
+
+const SAVED_LOCATIONS_KEY: &str = "me1_le_saved_locations";
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub save_game: RcRef<Me1LeSaveData>,
+}
+
+pub struct Me1LeGalaxy {
+    _db_handle: ContextHandle<Databases>,
+    galaxy_db: Option<Rc<Me1LeGalaxyDb>>,
+}
+
+impl Component for Me1LeGalaxy {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me1LeGalaxy { _db_handle, galaxy_db: databases.get_me1_le_galaxy() }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.galaxy_db = dbs.get_me1_le_galaxy();
+                true
+            }
+            Msg::MarkAllScanned(world) => {
+                if let Some(ref galaxy_db) = self.galaxy_db {
+                    world.borrow_mut().mark_all_scanned(galaxy_db);
+                }
+                true
+            }
+            Msg::PlanetScanned(world, planet_id, scanned) => {
+                world.borrow_mut().set_planet_scanned(planet_id, scanned);
+                true
+            }
+            Msg::ResetCinematics(world) => {
+                world.borrow_mut().reset_cinematics();
+                true
+            }
+            Msg::Relocate(idx) => {
+                if let Some(spot) = self.galaxy_db.as_ref().and_then(|db| db.safe_spots().get(idx))
+                {
+                    ctx.props().save_game.borrow_mut().relocate(spot);
+                }
+                true
+            }
+            Msg::SaveLocation => {
+                let mut saved_locations = Self::saved_locations();
+                saved_locations.push(ctx.props().save_game.borrow().current_spot());
+                let _ = LocalStorage::set(SAVED_LOCATIONS_KEY, saved_locations);
+                true
+            }
+            Msg::RelocateSaved(idx) => {
+                if let Some(spot) = Self::saved_locations().get(idx) {
+                    ctx.props().save_game.borrow_mut().relocate(spot);
+                }
+                true
+            }
+            Msg::ClearSavedLocations => {
+                LocalStorage::delete(SAVED_LOCATIONS_KEY);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let galaxy_db = match self.galaxy_db {
+            Some(ref galaxy_db) => galaxy_db,
+            None => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        let world = match ctx.props().save_game.borrow().world() {
+            Some(world) => world,
+            None => {
+                return html! {
+                    <p>{ "The galaxy isn't saved in character exports or unreadable saves." }</p>
+                }
+            }
+        };
+
+        html! {
+            <div class="flex flex-col gap-1">
+                { self.location(ctx, galaxy_db) }
+                { self.cinematics(ctx, &world) }
+                { self.galaxy_map(ctx, galaxy_db, &world) }
+            </div>
+        }
+    }
+}
+
+impl Me1LeGalaxy {
+    fn location(&self, ctx: &Context<Self>, galaxy_db: &Me1LeGalaxyDb) -> Html {
+        let save_game = ctx.props().save_game.borrow();
+        let (x, y, z) = save_game.location().xyz();
+
+        let spots = galaxy_db.safe_spots().iter().enumerate().map(|(idx, spot)| {
+            let onclick = ctx.link().callback(move |_| Msg::Relocate(idx));
+            html! {
+                <button class="button" {onclick}>
+                    { format!("Move to {}", spot.name) }
+                </button>
+            }
+        });
+
+        let saved_locations = Self::saved_locations();
+        let saved_spots = saved_locations.iter().enumerate().map(|(idx, spot)| {
+            let onclick = ctx.link().callback(move |_| Msg::RelocateSaved(idx));
+            html! {
+                <button class="button" {onclick}>
+                    { format!("Move to {}", spot.name) }
+                </button>
+            }
+        });
+        let clear_saved = (!saved_locations.is_empty()).then(|| {
+            html! {
+                <button class="button" onclick={ctx.link().callback(|_| Msg::ClearSavedLocations)}>
+                    { "Forget remembered locations" }
+                </button>
+            }
+        });
+
+        html! {
+            <Table title="Location" helper=
+                "Moving rewrites the level, map, position and world destination together.\n\
+                Remember the location of a save made elsewhere (the Citadel, ...) to move \
+                other saves there."
+            >
+                <div class="flex items-center gap-1 cursor-default">
+                    { format!("{} / {} at ({:.0}, {:.0}, {:.0})", save_game.base_level_name(), save_game.map_name(), x, y, z) }
+                </div>
+                <div class="flex flex-wrap items-center gap-1">
+                    { for spots }
+                    { for saved_spots }
+                </div>
+                <div class="flex flex-wrap items-center gap-1">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::SaveLocation)}>
+                        { "Remember this location" }
+                    </button>
+                    { for clear_saved }
+                </div>
+            </Table>
+        }
+    }
+
+    fn saved_locations() -> Vec<Me1LeSafeSpot> {
+        LocalStorage::get(SAVED_LOCATIONS_KEY).unwrap_or_default()
+    }
+
+    fn cinematics(&self, ctx: &Context<Self>, world_ref: &RcRef<World>) -> Html {
+        let world = world_ref.borrow();
+        let reset = {
+            let world = RcRef::clone(world_ref);
+            ctx.link().callback(move |_| Msg::ResetCinematics(RcRef::clone(&world)))
+        };
+
+        html! {
+            <Table title="Cinematics">
+                <div class="flex items-center gap-1">
+                    { format!("{} cinematics seen", world.cinematics_seen().len()) }
+                    <button class="button" onclick={reset}>{ "Reset seen cinematics" }</button>
+                </div>
+            </Table>
+        }
+    }
+
+    fn galaxy_map(
+        &self, ctx: &Context<Self>, galaxy_db: &Me1LeGalaxyDb, world_ref: &RcRef<World>,
+    ) -> Html {
+        let mark_all_scanned = {
+            let world = RcRef::clone(world_ref);
+            ctx.link().callback(move |_| Msg::MarkAllScanned(RcRef::clone(&world)))
+        };
+
+        let clusters = galaxy_db.clusters().iter().map(|cluster| {
+            let systems = cluster.systems.iter().map(|system| self.system(ctx, system, world_ref));
+            html! {
+                <Table title={cluster.name.clone()} opened=false>
+                    { for systems }
+                </Table>
+            }
+        });
+
+        // Ids not in the database can still be unscanned
+        let unknown_planets: Vec<_> = world_ref
+            .borrow()
+            .scanned_planets()
+            .iter()
+            .map(RcCell::get)
+            .filter(|&id| galaxy_db.planets().all(|planet| planet.id != id))
+            .collect();
+        let unknown_planets = (!unknown_planets.is_empty()).then(|| {
+            let planets = unknown_planets
+                .into_iter()
+                .map(|id| self.planet(ctx, world_ref, id, format!("Unknown planet ({})", id)));
+            html! {
+                <Table title="Unknown planets" opened=false>
+                    { for planets }
+                </Table>
+            }
+        });
+
+        html! {
+            <Table title="Galaxy map">
+                <button class="button" onclick={mark_all_scanned}>
+                    { "Mark everything scanned" }
+                </button>
+                { for clusters }
+                { for unknown_planets }
+            </Table>
+        }
+    }
+
+    fn system(&self, ctx: &Context<Self>, system: &Me1LeSystem, world_ref: &RcRef<World>) -> Html {
+        let planets = system
+            .planets
+            .iter()
+            .map(|planet| self.planet(ctx, world_ref, planet.id, planet.name.clone()));
+        html! {
+            <Table title={system.name.clone()}>
+                { for planets }
+            </Table>
+        }
+    }
+
+    fn planet(
+        &self, ctx: &Context<Self>, world_ref: &RcRef<World>, planet_id: i32, label: String,
+    ) -> Html {
+        let value = RcCell::new(world_ref.borrow().is_planet_scanned(planet_id));
+        let onchange = {
+            let world = RcRef::clone(world_ref);
+            ctx.link().callback(move |scanned| {
+                Msg::PlanetScanned(RcRef::clone(&world), planet_id, scanned)
+            })
+        };
+
+        html! {
+            <CheckBox {label} {value} {onchange} />
+        }
+    }
+}
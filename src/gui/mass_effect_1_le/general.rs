@@ -11,12 +11,13 @@ use crate::{
         components::{CallbackType, Helper, InputNumber, InputText, NumberType, Select, Table},
         mass_effect_1_le::bonus_talents::BonusTalents,
         raw_ui::RawUi,
+        shared::{IntPlotType, Morality, MoralityType},
     },
     save_data::{
         mass_effect_1_le::{
             player::{Item, Me1LeClass, Player},
             player_class_db::{Me1LePlayerClass, Me1LePlayerClassDb},
-            squad::Henchman,
+            progression_db::Me1LeProgressionDb,
             Me1LeSaveData,
         },
         shared::{
@@ -107,13 +108,15 @@ impl VanguardSpec {
 }
 
 pub enum Msg {
-    DatabaseLoaded(Databases),
+    DatabaseLoaded(Box<Databases>),
     Gender(usize),
     Origin(usize),
     Notoriety(usize),
     Difficulty(usize),
+    Level(CallbackType),
+    CurrentXp(CallbackType),
     TalentPoints(CallbackType),
-    ResetTalents(Option<&'static str>),
+    ResetTalents,
     PlayerClass(usize),
     PlayerSpecialization(usize),
     BonusTalent(Option<i32>),
@@ -137,6 +140,7 @@ impl Props {
 pub struct Me1LeGeneral {
     _db_handle: ContextHandle<Databases>,
     player_class_db: Option<Rc<Me1LePlayerClassDb>>,
+    progression_db: Option<Rc<Me1LeProgressionDb>>,
 }
 
 impl Component for Me1LeGeneral {
@@ -146,18 +150,23 @@ impl Component for Me1LeGeneral {
     fn create(ctx: &Context<Self>) -> Self {
         let (databases, _db_handle) = ctx
             .link()
-            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
             .expect("no database provider");
 
-        Me1LeGeneral { _db_handle, player_class_db: databases.get_me1_le_player_classes() }
+        Me1LeGeneral {
+            _db_handle,
+            player_class_db: databases.clone().get_me1_le_player_classes(),
+            progression_db: databases.get_me1_le_progression(),
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        let Me1LeSaveData { player, squad, plot, .. } = &mut *ctx.props().save_game_mut();
+        let Me1LeSaveData { player, plot, .. } = &mut *ctx.props().save_game_mut();
         let (mut player, mut plot) = (player.borrow_mut(), plot.borrow_mut());
         match msg {
             Msg::DatabaseLoaded(dbs) => {
-                self.player_class_db = dbs.get_me1_le_player_classes();
+                self.player_class_db = dbs.clone().get_me1_le_player_classes();
+                self.progression_db = dbs.get_me1_le_progression();
                 true
             }
             Msg::Gender(gender) => {
@@ -198,30 +207,20 @@ impl Component for Me1LeGeneral {
                 }
                 false
             }
-            Msg::ResetTalents(tag) => {
-                let (talent_points, complex_talents) = if let Some(tag) = tag {
-                    // Squad mate
-                    let squad = squad.borrow();
-                    let character = squad
-                        .iter()
-                        .find(|character| *character.borrow().tag() == tag)
-                        .unwrap()
-                        .borrow();
-                    (
-                        RcCell::clone(&character.talent_points),
-                        RcRef::clone(&character.complex_talents),
-                    )
-                } else {
-                    // Player
-                    (RcCell::clone(&player.talent_points), RcRef::clone(&player.complex_talents))
-                };
-
-                for talent in complex_talents.borrow_mut().iter_mut() {
-                    talent_points.update(|tp| tp + talent.borrow().current_rank());
-                    talent.borrow_mut().set_current_rank(0);
+            Msg::Level(CallbackType::Int(level)) => {
+                if let Some(ref progression_db) = self.progression_db {
+                    progression_db.set_player_level(&mut player, level);
                 }
                 true
             }
+            Msg::CurrentXp(CallbackType::Float(current_xp)) => {
+                player.set_current_xp(current_xp);
+                true
+            }
+            Msg::ResetTalents => {
+                player.respec();
+                true
+            }
             Msg::TalentPoints(CallbackType::Int(talent_points)) => {
                 player.set_talent_points(talent_points);
                 true
@@ -410,21 +409,22 @@ impl Component for Me1LeGeneral {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        if let Some(ref player_class_db) = self.player_class_db {
+        if let (Some(player_class_db), Some(progression_db)) =
+            (&self.player_class_db, &self.progression_db)
+        {
             let save_game = ctx.props().save_game();
 
             html! {
                 <div class="flex divide-solid divide-x divide-default-border">
                     <div class="flex-1 pr-1 flex flex-col gap-1">
                         { Self::role_play(ctx, save_game.player()) }
-                        { Self::gameplay(ctx, save_game.player()) }
+                        { Self::gameplay(ctx, progression_db, save_game.player()) }
                         { Self::bonus_talents(ctx, player_class_db, save_game.player()) }
                     </div>
                     <div class="flex-1 pl-1 flex flex-col gap-1">
                         { Self::general(ctx, save_game.player().game_options()) }
-                        { Self::morality(save_game.plot()) }
+                        { Self::morality(save_game.plot(), save_game.player()) }
                         { Self::resources(save_game.player()) }
-                        { Self::squad(ctx, save_game.squad()) }
                     </div>
                 </div>
             }
@@ -481,7 +481,9 @@ impl Me1LeGeneral {
         }
     }
 
-    fn gameplay(ctx: &Context<Self>, player: Ref<'_, Player>) -> Html {
+    fn gameplay(
+        ctx: &Context<Self>, progression_db: &Me1LeProgressionDb, player: Ref<'_, Player>,
+    ) -> Html {
         let Player { level, current_xp, player_class, specialization_bonus_id, .. } = &*player;
 
         let player_class = player_class.borrow();
@@ -531,16 +533,25 @@ impl Me1LeGeneral {
                 </div>
                 <InputNumber
                     label="Level"
-                    value={NumberType::Int(RcCell::clone(level))}
-                    helper="Classic mode (1 - 60)"
+                    value={NumberType::Int(level.get().into())}
+                    onchange={ctx.link().callback(Msg::Level)}
+                    helper="Classic mode (1 - 60)\n\
+                        The XP and the unspent talent points are updated to match the new level"
                 />
-                { current_xp.view("Current XP") }
+                <InputNumber
+                    label="Current XP"
+                    value={NumberType::Float(current_xp.get().into())}
+                    onchange={ctx.link().callback(Msg::CurrentXp)}
+                />
+                <div class="flex items-center gap-1 cursor-default">
+                    { format!("Next level at {} XP", progression_db.xp_for_level(level.get() + 1)) }
+                </div>
                 <InputNumber
                     label="Talent Points"
                     value={NumberType::Int(player.talent_points().into())}
                     onchange={ctx.link().callback(Msg::TalentPoints)}
                 />
-                <button class="button" onclick={ctx.link().callback(|_| Msg::ResetTalents(None))}>
+                <button class="button" onclick={ctx.link().callback(|_| Msg::ResetTalents)}>
                     { "Reset player's talents" }
                 </button>
             </Table>
@@ -591,12 +602,10 @@ impl Me1LeGeneral {
         }
     }
 
-    fn morality(plot: Ref<'_, PlotTable>) -> Html {
+    fn morality(plot: Ref<'_, PlotTable>, player: Ref<'_, Player>) -> Html {
+        let talents = player.persuasion_talents();
         html! {
-            <Table title="Morality">
-                { for plot.integers().get(47).map(|paragon| paragon.view("Paragon")) }
-                { for plot.integers().get(46).map(|renegade| renegade.view("Renegade")) }
-            </Table>
+            <Morality morality={MoralityType::Me1(IntPlotType::Vec(RcRef::clone(&plot.integers)), talents)} />
         }
     }
 
@@ -611,33 +620,4 @@ impl Me1LeGeneral {
             </Table>
         }
     }
-
-    fn squad(ctx: &Context<Self>, squad: Ref<'_, Vec<RcRef<Henchman>>>) -> Html {
-        let characters = [
-            ("hench_humanfemale", "Ashley"),
-            ("hench_turian", "Garrus"),
-            ("hench_humanmale", "Kaidan"),
-            ("hench_asari", "Liara"),
-            ("hench_quarian", "Tali"),
-            ("hench_krogan", "Wrex"),
-        ];
-
-        let characters = squad.iter().filter_map(|character| {
-            characters.iter().find_map(|&(tag, name)| {
-                (*character.borrow().tag() == tag).then(|| {
-                    html! {
-                        <button class="button" onclick={ctx.link().callback(move |_| Msg::ResetTalents(Some(tag)))}>
-                            { format!("Reset {}'s talents", name) }
-                        </button>
-                    }
-                })
-            })
-        });
-
-        html! {
-            <Table title="Squad">
-                { for characters }
-            </Table>
-        }
-    }
 }
@@ -131,7 +131,7 @@ impl Component for ItemSelect {
                             .item_db
                             .iter()
                             .filter_map(|(k, v)| {
-                                (v.to_lowercase().contains(&filter)).then(|| (*k, v.clone()))
+                                (v.name.to_lowercase().contains(&filter)).then(|| (*k, v.clone()))
                             })
                             .collect::<IndexMap<_, _>>();
                         self.filtered_list = Some(filtered_list.into());
@@ -208,7 +208,7 @@ impl Component for ItemSelect {
             .props()
             .item_db
             .get(&self.current_item)
-            .map(|item| item.as_str())
+            .map(|item| item.name.as_str())
             .unwrap_or_else(|| "Unknown item");
 
         let item_db = self.filtered_list.as_ref().unwrap_or(&ctx.props().item_db);
@@ -228,7 +228,7 @@ impl Component for ItemSelect {
                         ]}
                         onclick={ctx.link().callback(move |_| Msg::Select(key))}
                     >
-                        { option }
+                        { &option.name }
                     </a>
                 }
             });
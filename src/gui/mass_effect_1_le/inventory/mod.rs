@@ -6,7 +6,7 @@ use crate::{
     gui::components::{Select, Table},
     save_data::{
         mass_effect_1_le::{
-            item_db::{DbItem, Me1ItemDb},
+            item_db::{DbItem, ItemRace, Me1ItemDb},
             player::{Inventory, Item, ItemLevel, Player},
             squad::Henchman,
         },
@@ -19,11 +19,17 @@ mod item_select;
 pub use self::item_select::*;
 
 pub enum Msg {
-    DatabaseLoaded(Databases),
+    DatabaseLoaded(Box<Databases>),
     ChangeItem(RcRef<Item>, DbItem),
     ChangeItemLevel(RcRef<Item>, usize),
     RemoveItem(RcRef<Vec<RcRef<Item>>>, usize),
     AddItem(RcRef<Vec<RcRef<Item>>>),
+    UpgradeLevel(usize),
+    UpgradeAll,
+    RemoveJunk,
+    FillItem(DbItem),
+    FillLevel(usize),
+    FillInventory,
 }
 
 #[derive(Properties, PartialEq)]
@@ -45,6 +51,9 @@ impl Props {
 pub struct Me1LeInventory {
     _db_handle: ContextHandle<Databases>,
     item_db: Option<Rc<Me1ItemDb>>,
+    upgrade_level: ItemLevel,
+    fill_item: DbItem,
+    fill_level: ItemLevel,
 }
 
 impl Component for Me1LeInventory {
@@ -54,13 +63,19 @@ impl Component for Me1LeInventory {
     fn create(ctx: &Context<Self>) -> Self {
         let (databases, _db_handle) = ctx
             .link()
-            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
             .expect("no database provider");
 
-        Me1LeInventory { _db_handle, item_db: databases.get_me1_item_db() }
+        Me1LeInventory {
+            _db_handle,
+            item_db: databases.get_me1_item_db(),
+            upgrade_level: ItemLevel::X,
+            fill_item: DbItem { item_id: 0, manufacturer_id: 0 },
+            fill_level: ItemLevel::X,
+        }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::DatabaseLoaded(dbs) => {
                 self.item_db = dbs.get_me1_item_db();
@@ -85,6 +100,64 @@ impl Component for Me1LeInventory {
                 item_list.borrow_mut().push(Default::default());
                 true
             }
+            Msg::UpgradeLevel(level) => {
+                self.upgrade_level = ItemLevel::from(level);
+                false
+            }
+            Msg::UpgradeAll => {
+                if let Some(ref item_db) = self.item_db {
+                    let player = ctx.props().player();
+                    let inventory = player.inventory();
+                    let squad = ctx.props().squad();
+
+                    let item_lists = [
+                        RcRef::clone(&inventory.equipment),
+                        RcRef::clone(&inventory.quick_slots),
+                        RcRef::clone(&inventory.inventory),
+                    ]
+                    .into_iter()
+                    .chain(squad.iter().flat_map(|henchman| {
+                        let henchman = henchman.borrow();
+                        [RcRef::clone(&henchman.equipment), RcRef::clone(&henchman.quick_slots)]
+                    }));
+
+                    for item_list in item_lists {
+                        for item in item_list.borrow().iter() {
+                            item_db.upgrade(&mut item.borrow_mut(), self.upgrade_level);
+                        }
+                    }
+                }
+                true
+            }
+            Msg::RemoveJunk => {
+                ctx.props().player().inventory.borrow_mut().remove_junk();
+                true
+            }
+            Msg::FillItem(item) => {
+                self.fill_item = item;
+                true
+            }
+            Msg::FillLevel(level) => {
+                self.fill_level = ItemLevel::from(level);
+                false
+            }
+            // Filling with the "None" item would only add empty slots
+            Msg::FillInventory if self.fill_item.item_id == 0 => false,
+            Msg::FillInventory => {
+                let player = ctx.props().player();
+                let inventory = player.inventory();
+                let mut inventory = inventory.inventory.borrow_mut();
+
+                while inventory.len() < Inventory::MAX_ITEMS {
+                    let mut item = Item::default();
+                    item.set_item_id(self.fill_item.item_id);
+                    item.set_manufacturer_id(self.fill_item.manufacturer_id);
+                    *item.item_level_mut() = self.fill_level;
+                    item.set_new_item(true);
+                    inventory.push(item.into());
+                }
+                true
+            }
         }
     }
 
@@ -98,6 +171,8 @@ impl Component for Me1LeInventory {
                         { self.squad(ctx, ctx.props().squad()) }
                     </div>
                     <div class="flex-1 flex flex-col gap-1 pl-1 min-w-0">
+                        { self.bulk_actions(ctx, player.inventory()) }
+                        { for self.validation(ctx.props().player(), ctx.props().squad()) }
                         { self.inventory(ctx, player.inventory()) }
                     </div>
                 </div>
@@ -114,6 +189,79 @@ impl Component for Me1LeInventory {
 }
 
 impl Me1LeInventory {
+    fn bulk_actions(&self, ctx: &Context<Self>, inventory: Ref<'_, Inventory>) -> Html {
+        let link = ctx.link();
+        let inventory_len = inventory.inventory().len();
+        html! {
+            <Table title="Bulk actions">
+                <div class="flex items-center gap-1">
+                    <button class="button" onclick={link.callback(|_| Msg::UpgradeAll)}>
+                        {"Upgrade all gear to level"}
+                    </button>
+                    <Select
+                        options={ItemLevel::variants()}
+                        current_idx={self.upgrade_level as usize}
+                        onselect={link.callback(Msg::UpgradeLevel)}
+                        sized=false
+                    />
+                </div>
+                <button class="button" onclick={link.callback(|_| Msg::RemoveJunk)}>
+                    {"Remove junk from inventory"}
+                </button>
+                <div class="flex items-center gap-1 min-w-0">
+                    <button class="button"
+                        disabled={self.fill_item.item_id == 0}
+                        onclick={link.callback(|_| Msg::FillInventory)}
+                    >
+                        { format!("Fill inventory ({}/{}) with", inventory_len, Inventory::MAX_ITEMS) }
+                    </button>
+                    <ItemSelect
+                        item_db={Rc::clone(self.item_db.as_ref().unwrap())}
+                        current_item={self.fill_item}
+                        onselect={link.callback(Msg::FillItem)}
+                    />
+                    <Select
+                        options={ItemLevel::variants()}
+                        current_idx={self.fill_level as usize}
+                        onselect={link.callback(Msg::FillLevel)}
+                        sized=false
+                    />
+                </div>
+            </Table>
+        }
+    }
+
+    fn validation(
+        &self, player: Ref<'_, Player>, squad: Ref<'_, Vec<RcRef<Henchman>>>,
+    ) -> Option<Html> {
+        let item_db = self.item_db.as_ref()?;
+        let inventory = player.inventory();
+
+        let validate = |items: Ref<'_, Vec<RcRef<Item>>>, race| {
+            items.iter().flat_map(|item| item_db.validate(&item.borrow(), race)).collect::<Vec<_>>()
+        };
+
+        let mut issues = vec![];
+        issues.extend(validate(inventory.equipment(), Some(ItemRace::Human)));
+        issues.extend(validate(inventory.quick_slots(), None));
+        issues.extend(validate(inventory.inventory(), None));
+        for henchman in squad.iter() {
+            let henchman = henchman.borrow();
+            let race = ItemRace::from_tag(&henchman.tag());
+            issues.extend(validate(henchman.equipment(), race));
+            issues.extend(validate(henchman.quick_slots(), None));
+        }
+
+        (!issues.is_empty()).then(|| {
+            let issues = issues.into_iter().map(|issue| html! { <p>{ issue }</p> });
+            html! {
+                <Table title="Validation">
+                    { for issues }
+                </Table>
+            }
+        })
+    }
+
     fn item_view(&self, ctx: &Context<Self>, item: &RcRef<Item>) -> Html {
         html! {
             <div class="flex items-center gap-1 min-w-0">
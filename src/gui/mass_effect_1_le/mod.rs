@@ -15,20 +15,26 @@ use crate::{
 };
 
 mod bonus_talents;
+mod galaxy;
 mod general;
 mod inventory;
+mod squad;
+mod world;
 
-pub use self::{general::*, inventory::*};
+pub use self::{galaxy::*, general::*, inventory::*, squad::*, world::*};
 
 impl RawUi for RcRef<Me1LeSaveData> {
     fn view(&self, _: &str) -> yew::Html {
-        let no_export = self
-            .borrow()
-            .no_export()
-            .as_ref()
-            .map(|no_export_data| no_export_data.children())
-            .unwrap_or_else(|| vec![html! { "Export Save" }])
-            .into_iter();
+        let save_game = self.borrow();
+        let no_export = save_game.no_export();
+        let no_export = match (no_export.as_ref(), no_export.error()) {
+            (Some(no_export_data), _) => no_export_data.children(),
+            (None, Some(error)) => vec![html! {
+                format!("World data kept as is, the end of a modded save object couldn't be found: {}", error)
+            }],
+            (None, None) => vec![html! { "Export Save" }],
+        }
+        .into_iter();
 
         let children = self.children();
         let len = children.len();
@@ -66,6 +72,12 @@ impl RawUi for RcRef<BaseObject> {
             Object::VehicleBehavior(vehicle_behavior) => vehicle_behavior.children(),
             Object::Vehicle(vehicle) => vehicle.children(),
             Object::World(world) => world.children(),
+            Object::Opaque(raw) => vec![html! {
+                <div class="flex-auto flex items-center gap-1">
+                    <span class="w-2/3">{ format!("{} bytes", raw.len()) }</span>
+                    { "Unknown object, kept as is" }
+                </div>
+            }],
             Object::Default => unreachable!(),
         };
 
@@ -0,0 +1,240 @@
+use std::{cell::Ref, rc::Rc};
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{CallbackType, InputNumber, NumberType, Table},
+    save_data::{
+        mass_effect_1_le::{
+            henchman_db::{Me1LeHenchmanClass, Me1LeHenchmanDb},
+            player::ComplexTalent,
+            progression_db::Me1LeProgressionDb,
+            squad::Henchman,
+        },
+        RcRef,
+    },
+    services::database::Databases,
+};
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    Level(RcRef<Henchman>, CallbackType),
+    TalentRank(RcRef<Henchman>, RcRef<ComplexTalent>, CallbackType),
+    Respec(RcRef<Henchman>),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub squad: RcRef<Vec<RcRef<Henchman>>>,
+}
+
+pub struct Me1LeSquad {
+    _db_handle: ContextHandle<Databases>,
+    henchman_db: Option<Rc<Me1LeHenchmanDb>>,
+    progression_db: Option<Rc<Me1LeProgressionDb>>,
+}
+
+impl Component for Me1LeSquad {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me1LeSquad {
+            _db_handle,
+            henchman_db: databases.clone().get_me1_le_henchmen(),
+            progression_db: databases.get_me1_le_progression(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.henchman_db = dbs.clone().get_me1_le_henchmen();
+                self.progression_db = dbs.get_me1_le_progression();
+                true
+            }
+            Msg::Level(henchman, CallbackType::Int(level)) => {
+                if let Some(ref progression_db) = self.progression_db {
+                    progression_db.set_henchman_level(&mut henchman.borrow_mut(), level);
+                }
+                true
+            }
+            Msg::TalentRank(henchman, talent, CallbackType::Int(rank)) => {
+                let (current_rank, max_rank, prerequisites) = {
+                    let talent = talent.borrow();
+                    (talent.current_rank(), talent.max_rank(), talent.prerequisites())
+                };
+                let mut new_rank = rank.clamp(0, max_rank);
+
+                if new_rank > current_rank {
+                    // The game doesn't let you rank up a talent before its prerequisites
+                    let henchman = henchman.borrow();
+                    let complex_talents = henchman.complex_talents();
+                    let prerequisites_met =
+                        prerequisites.into_iter().all(|(prereq_id, prereq_rank)| {
+                            complex_talents.iter().any(|other| {
+                                let other = other.borrow();
+                                other.talent_id() == prereq_id
+                                    && other.current_rank() >= prereq_rank
+                            })
+                        });
+
+                    if !prerequisites_met {
+                        return true;
+                    }
+
+                    new_rank = new_rank.min(current_rank + henchman.talent_points());
+                }
+
+                henchman.borrow_mut().talent_points.update(|tp| tp + current_rank - new_rank);
+                talent.borrow_mut().set_current_rank(new_rank);
+                true
+            }
+            Msg::Respec(henchman) => {
+                henchman.borrow_mut().respec();
+                true
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let (Some(henchman_db), Some(progression_db)) = (&self.henchman_db, &self.progression_db)
+        {
+            let squad = ctx.props().squad.borrow();
+            let henchmen = henchman_db.henchmen().iter().filter_map(|class| {
+                squad.iter().find(|henchman| *henchman.borrow().tag() == class.tag).map(
+                    |henchman| Self::henchman(ctx, henchman_db, progression_db, class, henchman),
+                )
+            });
+
+            html! {
+                <div class="grid grid-cols-2 gap-1">
+                    { for henchmen }
+                </div>
+            }
+        } else {
+            html! {
+                <p>{ "Loading database..." }</p>
+            }
+        }
+    }
+}
+
+impl Me1LeSquad {
+    fn henchman(
+        ctx: &Context<Self>, henchman_db: &Me1LeHenchmanDb, progression_db: &Me1LeProgressionDb,
+        class: &Me1LeHenchmanClass, henchman_ref: &RcRef<Henchman>,
+    ) -> Html {
+        let henchman = henchman_ref.borrow();
+        let total_talent_points = progression_db.henchman_talent_points(henchman.level());
+
+        let level = {
+            let henchman = RcRef::clone(henchman_ref);
+            ctx.link().callback(move |level| Msg::Level(RcRef::clone(&henchman), level))
+        };
+        let respec = {
+            let henchman = RcRef::clone(henchman_ref);
+            ctx.link().callback(move |_| Msg::Respec(RcRef::clone(&henchman)))
+        };
+
+        let complex_talents = henchman.complex_talents();
+        let mut talents: Vec<_> = complex_talents.iter().collect();
+        talents.sort_by_key(|talent| talent.borrow().visual_order());
+
+        let talents = talents.into_iter().map(|talent_ref| {
+            Self::talent(ctx, henchman_db, class, henchman_ref, &complex_talents, talent_ref)
+        });
+
+        let warnings = henchman_db.unmet_prerequisites(&henchman);
+        let warnings = (!warnings.is_empty()).then(|| {
+            let warnings = warnings.into_iter().map(|warning| {
+                html! { <p class="text-red-500">{ warning }</p> }
+            });
+            html! { for warnings }
+        });
+
+        html! {
+            <Table title={format!("{} - {}", class.name, class.class_name)}>
+                <InputNumber
+                    label="Level"
+                    value={NumberType::Int(henchman.level().into())}
+                    onchange={level}
+                    helper="The unspent talent points are updated to match the new level"
+                />
+                <div class="flex items-center gap-1 cursor-default">
+                    { format!("{} / {} talent points available", henchman.talent_points(), total_talent_points) }
+                </div>
+                <button class="button" onclick={respec}>
+                    { format!("Reset {}'s talents", class.name) }
+                </button>
+                { for talents }
+                { for warnings }
+            </Table>
+        }
+    }
+
+    fn talent(
+        ctx: &Context<Self>, henchman_db: &Me1LeHenchmanDb, class: &Me1LeHenchmanClass,
+        henchman_ref: &RcRef<Henchman>, complex_talents: &Ref<'_, Vec<RcRef<ComplexTalent>>>,
+        talent_ref: &RcRef<ComplexTalent>,
+    ) -> Html {
+        let talent = talent_ref.borrow();
+        let talent_id = talent.talent_id();
+
+        let mut label = henchman_db.talent_name(talent_id);
+        if talent_id == class.class_talent {
+            label = format!("{} (class)", label);
+        }
+
+        let requires = talent.prerequisites().into_iter().map(|(prereq_id, prereq_rank)| {
+            format!("Requires {} rank {}", henchman_db.talent_name(prereq_id), prereq_rank)
+        });
+
+        let unlocks =
+            complex_talents.iter().filter(|other| *other != talent_ref).flat_map(|other| {
+                let other = other.borrow();
+                other
+                    .prerequisites()
+                    .into_iter()
+                    .filter(|&(prereq_id, _)| prereq_id == talent_id)
+                    .map(|(_, prereq_rank)| {
+                        format!(
+                            "Unlocks {} at rank {}",
+                            henchman_db.talent_name(other.talent_id()),
+                            prereq_rank
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+        let infos = requires.chain(unlocks).map(|info| {
+            html! { <span class="text-xs opacity-70">{ info }</span> }
+        });
+
+        let onchange = {
+            let henchman = RcRef::clone(henchman_ref);
+            let talent = RcRef::clone(talent_ref);
+            ctx.link().callback(move |rank| {
+                Msg::TalentRank(RcRef::clone(&henchman), RcRef::clone(&talent), rank)
+            })
+        };
+
+        html! {
+            <div class="flex items-center gap-2">
+                <InputNumber
+                    {label}
+                    value={NumberType::Int(talent.current_rank().into())}
+                    {onchange}
+                />
+                { format!("/ {}", talent.max_rank()) }
+                { for infos }
+            </div>
+        }
+    }
+}
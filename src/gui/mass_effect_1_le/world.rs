@@ -0,0 +1,461 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use super::ItemSelect;
+use crate::{
+    gui::{
+        components::{Select, Table},
+        raw_ui::RawUi,
+    },
+    save_data::{
+        mass_effect_1_le::{
+            item_db::{DbItem, Me1ItemDb},
+            legacy::{
+                art_placeable::ArtPlaceableBehavior,
+                inventory::{Inventory, Shop},
+                BaseObject, Level, Map, Object, OptionObjectProxy,
+            },
+            player::ItemLevel,
+            Me1LeSaveData,
+        },
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    RevivePawns(RcRef<Map>),
+    ResetPlaceables(RcRef<Map>),
+    ResetPlaceable(RcRef<ArtPlaceableBehavior>),
+    Restock(RcRef<Shop>),
+    ChangeItem(RcRef<BaseObject>, DbItem),
+    ChangeItemLevel(RcRef<BaseObject>, usize),
+    DuplicateItem(RcRef<Inventory>, usize),
+    RemoveItem(RcRef<Inventory>, usize),
+    RemoveMod(RcRef<OptionObjectProxy>),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub save_game: RcRef<Me1LeSaveData>,
+}
+
+pub struct Me1LeWorld {
+    _db_handle: ContextHandle<Databases>,
+    item_db: Option<Rc<Me1ItemDb>>,
+    error: Option<String>,
+}
+
+impl Component for Me1LeWorld {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me1LeWorld { _db_handle, item_db: databases.get_me1_item_db(), error: None }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.item_db = dbs.get_me1_item_db();
+                true
+            }
+            Msg::RevivePawns(map) => {
+                map.borrow().revive_pawns();
+                true
+            }
+            Msg::ResetPlaceables(map) => {
+                map.borrow().reset_placeables();
+                true
+            }
+            Msg::ResetPlaceable(placeable) => {
+                placeable.borrow_mut().reset();
+                true
+            }
+            Msg::Restock(shop) => {
+                shop.borrow_mut().restock();
+                true
+            }
+            Msg::ChangeItem(object, new_item) => {
+                if let (Some(item_db), Some((item_id, manufacturer_id, _))) =
+                    (&self.item_db, item_fields(&object.borrow()))
+                {
+                    let current_item =
+                        DbItem { item_id: item_id.get(), manufacturer_id: manufacturer_id.get() };
+
+                    // The save object has to stay the same kind of item (weapon, armor, etc.)
+                    let same_category = match (item_db.get(&current_item), item_db.get(&new_item)) {
+                        (Some(current), Some(new)) => current.category == new.category,
+                        _ => true,
+                    };
+
+                    if same_category {
+                        item_id.set(new_item.item_id);
+                        manufacturer_id.set(new_item.manufacturer_id);
+                    }
+                }
+                true
+            }
+            Msg::ChangeItemLevel(object, item_level) => {
+                if let Some((_, _, level)) = item_fields(&object.borrow()) {
+                    *level.borrow_mut() = ItemLevel::from(item_level);
+                }
+                false
+            }
+            Msg::DuplicateItem(inventory, idx) => {
+                self.error = inventory
+                    .borrow_mut()
+                    .duplicate_item(idx)
+                    .err()
+                    .map(|err| format!("Failed to duplicate the item: {}", err));
+                true
+            }
+            Msg::RemoveItem(inventory, idx) => {
+                inventory.borrow_mut().items_mut().remove(idx);
+                true
+            }
+            Msg::RemoveMod(slot) => {
+                *slot.borrow_mut().proxy_mut() = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.item_db.is_none() {
+            return html! {
+                <p>{ "Loading database..." }</p>
+            };
+        }
+
+        let save_game = ctx.props().save_game.borrow();
+        let no_export = save_game.no_export();
+        let no_export = match no_export.as_ref() {
+            Some(no_export) => no_export.borrow(),
+            None => {
+                return match no_export.error() {
+                    Some(error) => html! {
+                        <div class="flex flex-col gap-1 cursor-default">
+                            <p>{ format!("The world couldn't be read, it will be saved unchanged: {}", error) }</p>
+                            <p>
+                                { "Save objects added by a mod are kept as is, but the end of one of \
+                                them couldn't be found." }
+                            </p>
+                        </div>
+                    },
+                    None => html! {
+                        <p>{ "The world isn't saved in character exports." }</p>
+                    },
+                };
+            }
+        };
+
+        let legacy_maps = no_export.legacy_maps();
+        let maps = legacy_maps.iter().map(|(name, map)| self.map(ctx, name, map));
+        let error = self.error.as_ref().map(|error| {
+            html! { <p class="text-red-500">{ error }</p> }
+        });
+
+        html! {
+            <div class="flex flex-col gap-1">
+                { for error }
+                { for maps }
+            </div>
+        }
+    }
+}
+
+impl Me1LeWorld {
+    fn map(&self, ctx: &Context<Self>, name: &str, map_ref: &RcRef<Map>) -> Html {
+        let link = ctx.link();
+        let map = map_ref.borrow();
+        let levels = map.levels();
+
+        let killed_pawns: usize = levels
+            .values()
+            .map(|level| destroyed_actors(&level.borrow(), ".BioPawnBehavior"))
+            .sum();
+
+        let revive_pawns = {
+            let map = RcRef::clone(map_ref);
+            link.callback(move |_| Msg::RevivePawns(RcRef::clone(&map)))
+        };
+        let reset_placeables = {
+            let map = RcRef::clone(map_ref);
+            link.callback(move |_| Msg::ResetPlaceables(RcRef::clone(&map)))
+        };
+
+        let levels = levels.iter().filter_map(|(name, level)| self.level(ctx, name, level));
+
+        html! {
+            <Table title={name.to_owned()} opened=false>
+                <div class="flex items-center gap-1">
+                    <button class="button" onclick={revive_pawns}>
+                        { format!("Revive pawns ({} killed)", killed_pawns) }
+                    </button>
+                    <button class="button" onclick={reset_placeables}>
+                        { "Reset containers and placeables" }
+                    </button>
+                </div>
+                { for levels }
+            </Table>
+        }
+    }
+
+    fn level(&self, ctx: &Context<Self>, name: &str, level: &RcRef<Level>) -> Option<Html> {
+        let level = level.borrow();
+        let objects = level.objects();
+
+        let shops = objects.iter().filter_map(|object| {
+            let object = object.borrow();
+            match object._object {
+                Object::Shop(ref shop) => Some(self.shop(ctx, &object.owner_name(), shop)),
+                _ => None,
+            }
+        });
+
+        let containers = objects.iter().filter_map(|object| {
+            let object = object.borrow();
+            match object._object {
+                Object::ArtPlaceableBehavior(ref placeable) => {
+                    self.container(ctx, &object.owner_name(), placeable)
+                }
+                _ => None,
+            }
+        });
+
+        let mut content = shops.chain(containers).peekable();
+        let killed_pawns = destroyed_actors(&level, ".BioPawnBehavior");
+        if content.peek().is_none() && killed_pawns == 0 {
+            return None;
+        }
+
+        let name = name.trim_start_matches("Level ").trim_end_matches(".TheWorld:PersistentLevel");
+        let killed_pawns = (killed_pawns > 0).then(|| {
+            html! { <p>{ format!("{} killed pawns", killed_pawns) }</p> }
+        });
+
+        Some(html! {
+            <Table title={name.to_owned()} opened=false>
+                { for killed_pawns }
+                { for content }
+            </Table>
+        })
+    }
+
+    fn shop(&self, ctx: &Context<Self>, name: &str, shop_ref: &RcRef<Shop>) -> Html {
+        let shop = shop_ref.borrow();
+        let restock = {
+            let shop = RcRef::clone(shop_ref);
+            ctx.link().callback(move |_| Msg::Restock(RcRef::clone(&shop)))
+        };
+
+        let stock = shop.inventory();
+        let stock = stock.iter().filter_map(|proxy| self.proxy_inventory(ctx, proxy));
+
+        let status = if shop.is_initialized() {
+            format!("Stocked at player level {}", shop.last_player_level())
+        } else {
+            "Not stocked yet".to_owned()
+        };
+
+        html! {
+            <Table title={format!("Shop {}", name)}>
+                <div class="flex items-center gap-1">
+                    { status }
+                    <button class="button" onclick={restock}>{ "Restock" }</button>
+                </div>
+                { for stock }
+            </Table>
+        }
+    }
+
+    fn container(
+        &self, ctx: &Context<Self>, name: &str, placeable_ref: &RcRef<ArtPlaceableBehavior>,
+    ) -> Option<Html> {
+        let placeable = placeable_ref.borrow();
+        let inventory = placeable.inventory();
+        if !placeable.generated_treasure() && inventory.is_none() {
+            return None;
+        }
+
+        let reset = {
+            let placeable = RcRef::clone(placeable_ref);
+            ctx.link().callback(move |_| Msg::ResetPlaceable(RcRef::clone(&placeable)))
+        };
+
+        let content = inventory.as_ref().and_then(|inventory| self.inventory(ctx, inventory));
+        let status = if inventory.is_some() { "Not looted" } else { "Looted" };
+
+        Some(html! {
+            <Table title={format!("Container {}", name)}>
+                <div class="flex items-center gap-1">
+                    { format!("{} ({})", status, placeable.current_fsm_state_name()) }
+                    <button class="button" onclick={reset}>{ "Reset" }</button>
+                </div>
+                { for content }
+            </Table>
+        })
+    }
+
+    fn proxy_inventory(
+        &self, ctx: &Context<Self>, proxy: &RcRef<OptionObjectProxy>,
+    ) -> Option<Html> {
+        let proxy = proxy.borrow();
+        let inventory = proxy.proxy();
+        inventory.as_ref().and_then(|inventory| self.inventory(ctx, inventory))
+    }
+
+    fn inventory(&self, ctx: &Context<Self>, object: &RcRef<BaseObject>) -> Option<Html> {
+        let object = object.borrow();
+        let inventory_ref = match object._object {
+            Object::Inventory(ref inventory) => inventory,
+            _ => return None,
+        };
+        let inventory = inventory_ref.borrow();
+        let link = ctx.link();
+
+        let items = inventory.items();
+        let items = items.iter().enumerate().map(|(idx, item)| {
+            let remove = {
+                let inventory = RcRef::clone(inventory_ref);
+                link.callback(move |_| Msg::RemoveItem(RcRef::clone(&inventory), idx))
+            };
+            let duplicate = {
+                let inventory = RcRef::clone(inventory_ref);
+                link.callback(move |_| Msg::DuplicateItem(RcRef::clone(&inventory), idx))
+            };
+
+            html! {
+                <>
+                    <div class="flex items-center gap-1 min-w-0">
+                        <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                            onclick={remove}
+                        >
+                            {"remove"}
+                        </a>
+                        <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                            onclick={duplicate}
+                        >
+                            {"duplicate"}
+                        </a>
+                        { for self.item(ctx, item) }
+                    </div>
+                    { for self.item_mods(ctx, item) }
+                </>
+            }
+        });
+
+        let plot_items = inventory.plot_items();
+        let plot_items = (!plot_items.is_empty()).then(|| inventory.plot_items.view("Plot items"));
+
+        Some(html! {
+            <Table title={object.owner_name().clone()}>
+                { for items }
+                { for plot_items }
+            </Table>
+        })
+    }
+
+    fn item(&self, ctx: &Context<Self>, object: &RcRef<BaseObject>) -> Option<Html> {
+        let (item_id, manufacturer_id, item_level) = item_fields(&object.borrow())?;
+        let current_item =
+            DbItem { item_id: item_id.get(), manufacturer_id: manufacturer_id.get() };
+        let current_level = *item_level.borrow() as usize;
+
+        let onselect_item = {
+            let object = RcRef::clone(object);
+            ctx.link().callback(move |new_item| Msg::ChangeItem(RcRef::clone(&object), new_item))
+        };
+        let onselect_level = {
+            let object = RcRef::clone(object);
+            ctx.link().callback(move |idx| Msg::ChangeItemLevel(RcRef::clone(&object), idx))
+        };
+
+        Some(html! {
+            <>
+                <ItemSelect
+                    item_db={Rc::clone(self.item_db.as_ref().unwrap())}
+                    {current_item}
+                    onselect={onselect_item}
+                />
+                <Select
+                    options={ItemLevel::variants()}
+                    current_idx={current_level}
+                    onselect={onselect_level}
+                    sized=false
+                />
+            </>
+        })
+    }
+
+    fn item_mods(&self, ctx: &Context<Self>, object: &RcRef<BaseObject>) -> Option<Html> {
+        let object = object.borrow();
+        let item = match object._object {
+            Object::Item(ref item) => item.borrow(),
+            _ => return None,
+        };
+
+        let slot_specs = item.slot_specs();
+        let mods = slot_specs.iter().flat_map(|slot_spec| {
+            let slot_spec = slot_spec.borrow();
+            let mods = slot_spec.mods();
+            mods.iter()
+                .filter_map(|slot| {
+                    let item_mod = slot.borrow().proxy().clone()?;
+                    let remove = {
+                        let slot = RcRef::clone(slot);
+                        ctx.link().callback(move |_| Msg::RemoveMod(RcRef::clone(&slot)))
+                    };
+
+                    Some(html! {
+                        <div class="flex items-center gap-1 min-w-0 pl-6">
+                            <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                                onclick={remove}
+                            >
+                                {"remove mod"}
+                            </a>
+                            { for self.item(ctx, &item_mod) }
+                        </div>
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+
+        Some(html! { for mods })
+    }
+}
+
+/// Id, manufacturer and level of an item or a mod.
+fn item_fields(object: &BaseObject) -> Option<(RcCell<i32>, RcCell<i32>, RcRef<ItemLevel>)> {
+    match object._object {
+        Object::Item(ref item) => {
+            let item = item.borrow();
+            Some((
+                item.item_id.clone(),
+                item.manufacturer_id.clone(),
+                RcRef::clone(&item.item_level),
+            ))
+        }
+        Object::ItemMod(ref item_mod) => {
+            let item_mod = item_mod.borrow();
+            Some((
+                item_mod.item_id.clone(),
+                item_mod.manufacturer_id.clone(),
+                RcRef::clone(&item_mod.item_level),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn destroyed_actors(level: &Level, class_name: &str) -> usize {
+    level.actors().iter().filter(|actor| actor.borrow().contains(class_name)).count()
+}
@@ -1,26 +1,38 @@
-use std::cell::Ref;
+use std::{cell::Ref, rc::Rc};
 
-use yew::prelude::*;
+use gloo::storage::{LocalStorage, Storage};
+use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
     gui::{
-        components::{Helper, InputText, Select, Table},
+        components::{Helper, InputNumber, InputText, NumberType, Select, Table},
         raw_ui::RawUi,
-        shared::{BonusPowerType, BonusPowers},
+        shared::{BonusPowerType, BonusPowers, IntPlotType, Morality, MoralityType, WeaponSelect},
     },
     save_data::{
-        mass_effect_2::{player::Player, Difficulty},
+        mass_effect_2::{
+            import_bonus_db::Me2ImportBonusDb,
+            player::Player,
+            player_class_db::Me2PlayerClassDb,
+            weapon_db::{Me2WeaponDb, Me2WeaponType},
+            Difficulty, Me1ImportBonus,
+        },
         shared::{
+            location_db::{LocationDb, SafeLocation},
+            morality_db::MoralityDb,
             player::{Notoriety, Origin},
             plot::PlotTable,
             EndGameState,
         },
-        RcRef,
+        RcCell, RcRef,
     },
+    services::database::Databases,
 };
 
 use super::Me2Type;
 
+const SAVED_LOCATIONS_KEY: &str = "me2_saved_locations";
+
 #[derive(Clone, RawUi)]
 enum Me2Class {
     Soldier,
@@ -45,10 +57,18 @@ impl Me2Class {
 }
 
 pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
     Gender(usize),
     Origin(usize),
     Notoriety(usize),
     PlayerClass(usize),
+    Equip(String),
+    RecomputeMe1ImportBonus,
+    ApplyMe1ImportBonus,
+    Relocate(usize),
+    SaveLocation,
+    RelocateSaved(usize),
+    ClearSavedLocations,
 }
 
 #[derive(Properties, PartialEq)]
@@ -56,17 +76,116 @@ pub struct Props {
     pub save_game: Me2Type,
 }
 
-pub struct Me2General;
+pub struct Me2General {
+    _db_handle: ContextHandle<Databases>,
+    player_class_db: Option<Rc<Me2PlayerClassDb>>,
+    weapon_db: Option<Rc<Me2WeaponDb>>,
+    location_db: Option<Rc<LocationDb>>,
+    import_bonus_db: Option<Rc<Me2ImportBonusDb>>,
+    me1_morality_db: Option<Rc<MoralityDb>>,
+    morality_db: Option<Rc<MoralityDb>>,
+    class_error: Option<String>,
+    weapon_error: Option<String>,
+    me1_credits: RcCell<i32>,
+    me1_import_bonus_applied: bool,
+}
 
 impl Component for Me2General {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Me2General
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me2General {
+            _db_handle,
+            player_class_db: databases.clone().get_me2_player_classes(),
+            weapon_db: databases.clone().get_me2_weapon(),
+            location_db: databases.clone().get_me2_location(),
+            import_bonus_db: databases.clone().get_me2_import_bonus(),
+            me1_morality_db: databases.clone().get_me1_morality(),
+            morality_db: databases.get_me2_morality(),
+            class_error: None,
+            weapon_error: None,
+            me1_credits: Default::default(),
+            me1_import_bonus_applied: false,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.player_class_db = dbs.clone().get_me2_player_classes();
+                self.weapon_db = dbs.clone().get_me2_weapon();
+                self.location_db = dbs.clone().get_me2_location();
+                self.import_bonus_db = dbs.clone().get_me2_import_bonus();
+                self.me1_morality_db = dbs.clone().get_me1_morality();
+                self.morality_db = dbs.get_me2_morality();
+                return true;
+            }
+            Msg::RecomputeMe1ImportBonus => {
+                if let (Me2Type::Legendary(ref me2), Some(import_bonus_db), Some(me1_morality_db)) =
+                    (&ctx.props().save_game, &self.import_bonus_db, &self.me1_morality_db)
+                {
+                    let me1_credits = Some(self.me1_credits.get()).filter(|&credits| credits > 0);
+                    me2.borrow().recompute_me1_import_bonus(
+                        import_bonus_db,
+                        me1_morality_db,
+                        me1_credits,
+                    );
+                }
+                return true;
+            }
+            Msg::ApplyMe1ImportBonus => {
+                if let (Me2Type::Legendary(ref me2), Some(morality_db)) =
+                    (&ctx.props().save_game, &self.morality_db)
+                {
+                    // The bonus adds up, it's applied once per loaded save
+                    if !self.me1_import_bonus_applied {
+                        me2.borrow().apply_me1_import_bonus(morality_db);
+                        self.me1_import_bonus_applied = true;
+                    }
+                }
+                return true;
+            }
+            Msg::Relocate(idx) => {
+                let location_db = self.location_db.as_ref();
+                if let Some(spot) = location_db.and_then(|db| db.locations().get(idx)) {
+                    match ctx.props().save_game {
+                        Me2Type::Vanilla(ref me2) => me2.borrow_mut().relocate(spot),
+                        Me2Type::Legendary(ref me2) => me2.borrow_mut().relocate(spot),
+                    }
+                }
+                return true;
+            }
+            Msg::SaveLocation => {
+                let mut saved_locations = Self::saved_locations();
+                saved_locations.push(match ctx.props().save_game {
+                    Me2Type::Vanilla(ref me2) => me2.borrow().current_spot(),
+                    Me2Type::Legendary(ref me2) => me2.borrow().current_spot(),
+                });
+                let _ = LocalStorage::set(SAVED_LOCATIONS_KEY, saved_locations);
+                return true;
+            }
+            Msg::RelocateSaved(idx) => {
+                if let Some(spot) = Self::saved_locations().get(idx) {
+                    match ctx.props().save_game {
+                        Me2Type::Vanilla(ref me2) => me2.borrow_mut().relocate(spot),
+                        Me2Type::Legendary(ref me2) => me2.borrow_mut().relocate(spot),
+                    }
+                }
+                return true;
+            }
+            Msg::ClearSavedLocations => {
+                LocalStorage::delete(SAVED_LOCATIONS_KEY);
+                return true;
+            }
+            _ => {}
+        }
+
         let (player, me1_plot, plot) = match ctx.props().save_game {
             Me2Type::Vanilla(ref me2) => {
                 let me2 = me2.borrow();
@@ -198,44 +317,77 @@ impl Component for Me2General {
                 false
             }
             Msg::PlayerClass(class_idx) => {
-                *player.class_name_mut() = Me2Class::names()[class_idx].to_owned();
-                false
+                let class_name = Me2Class::names()[class_idx];
+                match self.player_class_db {
+                    Some(ref player_class_db) => {
+                        let result = player_class_db.change_class(&mut player, class_name);
+                        self.class_error = result.err().map(|err| err.to_string());
+                    }
+                    None => *player.class_name_mut() = class_name.to_owned(),
+                }
+                true
+            }
+            Msg::Equip(weapon) => {
+                if let Some(ref weapon_db) = self.weapon_db {
+                    let result = weapon_db.equip_player(&player, &weapon);
+                    self.weapon_error = result.err().map(|err| err.to_string());
+                }
+                true
             }
+            Msg::DatabaseLoaded(_)
+            | Msg::RecomputeMe1ImportBonus
+            | Msg::ApplyMe1ImportBonus
+            | Msg::Relocate(_)
+            | Msg::SaveLocation
+            | Msg::RelocateSaved(_)
+            | Msg::ClearSavedLocations => unreachable!(),
         }
     }
 
+    fn changed(&mut self, _ctx: &Context<Self>) -> bool {
+        // Another save
+        self.me1_import_bonus_applied = false;
+        true
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let (difficulty, end_game_state, player, plot) = match ctx.props().save_game {
-            Me2Type::Vanilla(ref me2) => {
-                let me2 = me2.borrow();
-                (
-                    RcRef::clone(&me2.difficulty),
-                    RcRef::clone(&me2.end_game_state),
-                    RcRef::clone(&me2.player),
-                    RcRef::clone(&me2.plot),
-                )
-            }
-            Me2Type::Legendary(ref me2) => {
-                let me2 = me2.borrow();
-                (
-                    RcRef::clone(&me2.difficulty),
-                    RcRef::clone(&me2.end_game_state),
-                    RcRef::clone(&me2.player),
-                    RcRef::clone(&me2.plot),
-                )
-            }
-        };
+        let (difficulty, end_game_state, player, plot, me1_import_bonus) =
+            match ctx.props().save_game {
+                Me2Type::Vanilla(ref me2) => {
+                    let me2 = me2.borrow();
+                    (
+                        RcRef::clone(&me2.difficulty),
+                        RcRef::clone(&me2.end_game_state),
+                        RcRef::clone(&me2.player),
+                        RcRef::clone(&me2.plot),
+                        None,
+                    )
+                }
+                Me2Type::Legendary(ref me2) => {
+                    let me2 = me2.borrow();
+                    (
+                        RcRef::clone(&me2.difficulty),
+                        RcRef::clone(&me2.end_game_state),
+                        RcRef::clone(&me2.player),
+                        RcRef::clone(&me2.plot),
+                        Some(RcRef::clone(&me2.me1_import_bonus)),
+                    )
+                }
+            };
 
         html! {
             <div class="flex divide-solid divide-x divide-default-border">
                 <div class="flex-1 pr-1 flex flex-col gap-1">
                     { Self::role_play(ctx, player.borrow()) }
                     { Self::morality(plot.borrow()) }
-                    { Self::gameplay(ctx, player.borrow()) }
+                    { self.gameplay(ctx, player.borrow()) }
+                    { self.weapon_loadout(ctx, player.borrow()) }
                     { Self::resources(player.borrow()) }
                 </div>
                 <div class="flex-1 pl-1 flex flex-col gap-1">
                     { Self::general(difficulty, end_game_state) }
+                    { self.location(ctx) }
+                    { for me1_import_bonus.map(|bonus| self.me1_import_bonus(ctx, bonus.borrow())) }
                     { Self::bonus_powers(player.borrow()) }
                 </div>
             </div>
@@ -290,14 +442,11 @@ impl Me2General {
 
     fn morality(plot: Ref<'_, PlotTable>) -> Html {
         html! {
-            <Table title="Morality">
-                { for plot.integers().get(2).map(|paragon| paragon.view("Paragon")) }
-                { for plot.integers().get(3).map(|renegade| renegade.view("Renegade")) }
-            </Table>
+            <Morality morality={MoralityType::Me2(IntPlotType::Vec(RcRef::clone(&plot.integers)))} />
         }
     }
 
-    fn gameplay(ctx: &Context<Self>, player: Ref<'_, Player>) -> Html {
+    fn gameplay(&self, ctx: &Context<Self>, player: Ref<'_, Player>) -> Html {
         let Player { level, current_xp, talent_points, credits, medigel, .. } = &*player;
 
         let class_idx = Me2Class::names()
@@ -305,6 +454,9 @@ impl Me2General {
             .enumerate()
             .find_map(|(i, name)| player.class_name().eq_ignore_ascii_case(name).then(|| i))
             .unwrap_or_default();
+        let class_error = self.class_error.as_ref().map(|error| {
+            html! { <p class="text-red-500">{ error }</p> }
+        });
 
         html! {
             <Table title="Gameplay">
@@ -315,7 +467,12 @@ impl Me2General {
                         onselect={ctx.link().callback(Msg::PlayerClass)}
                     />
                     {"Class"}
+                    <Helper text=
+                        "Changing your class replaces the powers of your old class by the new ones \
+                        and refunds their talent points. Bonus powers are kept."
+                    />
                 </div>
+                { for class_error }
                 { level.view("Level") }
                 { current_xp.view("Current XP") }
                 { talent_points.view("Talent Points") }
@@ -325,6 +482,64 @@ impl Me2General {
         }
     }
 
+    fn weapon_loadout(&self, ctx: &Context<Self>, player: Ref<'_, Player>) -> Html {
+        let weapon_db = match self.weapon_db {
+            Some(ref weapon_db) => weapon_db,
+            None => {
+                return html! {
+                    <Table title="Weapon Loadout">
+                        <p>{ "Loading database..." }</p>
+                    </Table>
+                }
+            }
+        };
+
+        let loadout = player.weapons_loadout();
+        let slot = |weapon_type: Me2WeaponType| {
+            let category =
+                weapon_db.types().iter().find(|category| category.weapon_type == weapon_type)?;
+            let weapons = category
+                .weapons
+                .iter()
+                .map(|weapon| (weapon.class_name.clone(), weapon.name.clone()))
+                .collect::<Vec<_>>();
+            let equipped = weapon_type.loadout_slot(&loadout).borrow().clone();
+            Some(html! {
+                <WeaponSelect
+                    label={category.name.clone()}
+                    {weapons}
+                    {equipped}
+                    onselect={ctx.link().batch_callback(|weapon: Option<String>| weapon.map(Msg::Equip))}
+                />
+            })
+        };
+
+        let class_name = player.class_name();
+        let class_slots = weapon_db.class_slots(&class_name).into_iter().filter_map(slot);
+        let bonus_slots = weapon_db.bonus_slots(&class_name).into_iter().filter_map(slot);
+
+        let issues = weapon_db.player_issues(&player).into_iter().map(|issue| {
+            html! { <p class="text-red-500">{ issue }</p> }
+        });
+        let weapon_error = self.weapon_error.as_ref().map(|error| {
+            html! { <p class="text-red-500">{ format!("Error: {}", error) }</p> }
+        });
+
+        html! {
+            <Table title="Weapon Loadout">
+                { for class_slots }
+                <Table title="Bonus weapon training" opened=false helper=
+                    "The weapon types learned on the Collector ship.\n\
+                    Only one of them can be trained in game, equip a weapon of the type you have chosen."
+                >
+                    { for bonus_slots }
+                </Table>
+                { for issues }
+                { for weapon_error }
+            </Table>
+        }
+    }
+
     fn resources(player: Ref<'_, Player>) -> Html {
         let Player { eezo, iridium, palladium, platinum, probes, current_fuel, .. } = &*player;
 
@@ -349,6 +564,129 @@ impl Me2General {
         }
     }
 
+    fn location(&self, ctx: &Context<Self>) -> Html {
+        let (base_level_name, location) = match ctx.props().save_game {
+            Me2Type::Vanilla(ref me2) => {
+                let me2 = me2.borrow();
+                (RcRef::clone(&me2.base_level_name), RcRef::clone(&me2.location))
+            }
+            Me2Type::Legendary(ref me2) => {
+                let me2 = me2.borrow();
+                (RcRef::clone(&me2.base_level_name), RcRef::clone(&me2.location))
+            }
+        };
+        let (x, y, z) = location.borrow().xyz();
+
+        let spots =
+            self.location_db.iter().flat_map(|db| db.locations()).enumerate().map(|(idx, spot)| {
+                let onclick = ctx.link().callback(move |_| Msg::Relocate(idx));
+                html! {
+                    <button class="button" {onclick}>
+                        { format!("Teleport to {}", spot.name) }
+                    </button>
+                }
+            });
+
+        let saved_locations = Self::saved_locations();
+        let saved_spots = saved_locations.iter().enumerate().map(|(idx, spot)| {
+            let onclick = ctx.link().callback(move |_| Msg::RelocateSaved(idx));
+            html! {
+                <button class="button" {onclick}>
+                    { format!("Teleport to {}", spot.name) }
+                </button>
+            }
+        });
+        let clear_saved = (!saved_locations.is_empty()).then(|| {
+            html! {
+                <button class="button" onclick={ctx.link().callback(|_| Msg::ClearSavedLocations)}>
+                    { "Forget remembered locations" }
+                </button>
+            }
+        });
+
+        html! {
+            <Table title="Location" helper=
+                "Teleporting rewrites the level, position, rotation and streamed levels together.\n\
+                Use it to get out of a soft-lock, the save loads at the chosen location.\n\
+                Remember the location of a save made elsewhere (the Citadel, Illium, Tuchanka, ...) \
+                to teleport other saves there."
+            >
+                <div class="flex items-center gap-1 cursor-default">
+                    { format!("{} at ({:.0}, {:.0}, {:.0})", base_level_name.borrow(), x, y, z) }
+                </div>
+                <div class="flex flex-wrap items-center gap-1">
+                    { for spots }
+                    { for saved_spots }
+                </div>
+                <div class="flex flex-wrap items-center gap-1">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::SaveLocation)}>
+                        { "Remember this location" }
+                    </button>
+                    { for clear_saved }
+                </div>
+            </Table>
+        }
+    }
+
+    fn saved_locations() -> Vec<SafeLocation> {
+        LocalStorage::get(SAVED_LOCATIONS_KEY).unwrap_or_default()
+    }
+
+    fn me1_import_bonus(&self, ctx: &Context<Self>, bonus: Ref<'_, Me1ImportBonus>) -> Html {
+        let Me1ImportBonus {
+            imported_me1_level,
+            starting_me2_level,
+            bonus_xp,
+            bonus_credits,
+            bonus_resources,
+            bonus_paragon,
+            bonus_renegade,
+        } = &*bonus;
+
+        html! {
+            <Table title="ME1 Import Bonus">
+                { imported_me1_level.view("Imported ME1 Level") }
+                { starting_me2_level.view("Starting ME2 Level") }
+                { bonus_xp.view("Bonus XP") }
+                { bonus_credits.view("Bonus Credits") }
+                { bonus_resources.view("Bonus Resources") }
+                { bonus_paragon.view("Bonus Paragon") }
+                { bonus_renegade.view("Bonus Renegade") }
+                <InputNumber
+                    label="ME1 Credits"
+                    value={NumberType::Int(RcCell::clone(&self.me1_credits))}
+                    helper="Credits of the imported ME1 character, leave at 0 to keep the bonus credits"
+                />
+                <div class="flex items-center gap-1 cursor-default">
+                    <button class="button"
+                        disabled={self.import_bonus_db.is_none() || self.me1_morality_db.is_none()}
+                        onclick={ctx.link().callback(|_| Msg::RecomputeMe1ImportBonus)}
+                    >
+                        { "Recompute from ME1" }
+                    </button>
+                    <Helper text=
+                        "Recomputes the starting level, XP and resources from the imported ME1 \
+                        level, the credits from the ME1 credits, and the paragon and renegade from \
+                        the imported ME1 plot.\n\
+                        Only the bonus tiers seen in real saves are known, the others are left as is."
+                    />
+                </div>
+                <div class="flex items-center gap-1 cursor-default">
+                    <button class="button"
+                        disabled={self.morality_db.is_none() || self.me1_import_bonus_applied}
+                        onclick={ctx.link().callback(|_| Msg::ApplyMe1ImportBonus)}
+                    >
+                        { if self.me1_import_bonus_applied { "Bonus applied" } else { "Apply bonus now" } }
+                    </button>
+                    <Helper text=
+                        "Adds the bonus to your credits, resources, paragon and renegade, once per \
+                        loaded save. Use it to fix an import that didn't give you its bonus."
+                    />
+                </div>
+            </Table>
+        }
+    }
+
     fn bonus_powers(player: Ref<'_, Player>) -> Html {
         let power_list: &'static [(&'static str, &'static str, &'static str)] = &[
             ("Slam", "SFXGameContent_Powers.SFXPower_Crush_Player", "Slam"),
@@ -1,8 +1,11 @@
 mod general;
 mod plot;
 mod raw_plot;
+mod research;
+mod squad;
+mod suicide_mission;
 
-pub use self::{general::*, plot::*, raw_plot::*};
+pub use self::{general::*, plot::*, raw_plot::*, research::*, squad::*, suicide_mission::*};
 
 use crate::save_data::{
     mass_effect_2::{Me2LeSaveGame, Me2SaveGame},
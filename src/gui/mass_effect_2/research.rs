@@ -0,0 +1,197 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{CheckBox, Table},
+    save_data::{
+        mass_effect_2::{
+            player::Player,
+            research_db::{Me2ResearchDb, Me2Resource},
+        },
+        shared::plot::PlotTable,
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    Unlock(usize),
+    UnlockAll,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub plot: RcRef<PlotTable>,
+    pub player: RcRef<Player>,
+}
+
+pub struct Me2Research {
+    _db_handle: ContextHandle<Databases>,
+    research_db: Option<Rc<Me2ResearchDb>>,
+    deduct: RcCell<bool>,
+    error: Option<String>,
+}
+
+impl Component for Me2Research {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me2Research {
+            _db_handle,
+            research_db: databases.get_me2_research(),
+            deduct: RcCell::new(false),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.research_db = dbs.get_me2_research();
+                true
+            }
+            Msg::Unlock(idx) => {
+                if let Some(ref research_db) = self.research_db {
+                    let Props { plot, player } = &ctx.props();
+                    let player = player.borrow();
+                    let player = self.deduct.get().then(|| &*player);
+                    let result = research_db.unlock(&mut plot.borrow_mut(), player, [idx]);
+                    self.error = result.err().map(|err| err.to_string());
+                }
+                true
+            }
+            Msg::UnlockAll => {
+                if let Some(ref research_db) = self.research_db {
+                    let Props { plot, player } = &ctx.props();
+                    let player = player.borrow();
+                    let player = self.deduct.get().then(|| &*player);
+                    let result = research_db.unlock_all(&mut plot.borrow_mut(), player);
+                    self.error = result.err().map(|err| err.to_string());
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let research_db = match self.research_db {
+            Some(ref research_db) => research_db,
+            None => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        html! {
+            <div class="flex divide-solid divide-x divide-default-border">
+                <div class="flex-1 pr-1 flex flex-col gap-1">
+                    { self.resources(ctx) }
+                    { self.consistency(ctx, research_db) }
+                </div>
+                <div class="flex-1 pl-1 flex flex-col gap-1">
+                    { self.upgrades(ctx, research_db) }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl Me2Research {
+    fn resources(&self, ctx: &Context<Self>) -> Html {
+        let player = ctx.props().player.borrow();
+        let resources = Me2Resource::ALL.into_iter().map(|resource| {
+            html! { <p>{ format!("{}: {}", resource, resource.amount(&player).get()) }</p> }
+        });
+        let error = self.error.as_ref().map(|error| {
+            html! { <p class="text-red-500">{ format!("Error: {}", error) }</p> }
+        });
+
+        html! {
+            <Table title="Resources" helper=
+                "Unlocking an upgrade also unlocks the ones it requires.\n\
+                The cost isn't known for every upgrade, those are unlocked for free."
+            >
+                { for resources }
+                <CheckBox label="Deduct resources" value={RcCell::clone(&self.deduct)} />
+                <button class="button" onclick={ctx.link().callback(|_| Msg::UnlockAll)}>
+                    { "Unlock all" }
+                </button>
+                { for error }
+            </Table>
+        }
+    }
+
+    fn consistency(&self, ctx: &Context<Self>, research_db: &Me2ResearchDb) -> Html {
+        let inconsistencies = research_db.inconsistencies(&ctx.props().plot.borrow());
+        let content = if inconsistencies.is_empty() {
+            html! { <p>{ "Every unlocked upgrade has its prerequisites" }</p> }
+        } else {
+            let inconsistencies = inconsistencies.into_iter().map(|inconsistency| {
+                html! { <p class="text-red-500">{ inconsistency }</p> }
+            });
+            html! { for inconsistencies }
+        };
+
+        html! {
+            <Table title="Consistency check">
+                { content }
+            </Table>
+        }
+    }
+
+    fn upgrades(&self, ctx: &Context<Self>, research_db: &Me2ResearchDb) -> Html {
+        let plot = ctx.props().plot.borrow();
+        let mut categories: Vec<(&str, Vec<Html>)> = Vec::new();
+        for (idx, upgrade) in research_db.upgrades().iter().enumerate() {
+            let state = if upgrade.is_unlocked(&plot) {
+                html! { <span class="px-1">{ "unlocked" }</span> }
+            } else {
+                html! {
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        onclick={ctx.link().callback(move |_| Msg::Unlock(idx))}
+                    >
+                        { "unlock" }
+                    </a>
+                }
+            };
+            // With the upgrades it requires
+            let cost: Vec<_> = research_db
+                .cost(&research_db.pending(&plot, idx))
+                .into_iter()
+                .map(|(resource, amount)| format!("{} {}", amount, resource))
+                .collect();
+            let cost =
+                if cost.is_empty() { String::new() } else { format!(" ({})", cost.join(", ")) };
+            let row = html! {
+                <div class="flex items-center gap-1">
+                    { state }
+                    { format!("{}{}", upgrade.name, cost) }
+                </div>
+            };
+
+            match categories.last_mut() {
+                Some((category, rows)) if *category == upgrade.category => rows.push(row),
+                _ => categories.push((&upgrade.category, vec![row])),
+            }
+        }
+
+        let categories = categories.into_iter().map(|(category, rows)| {
+            html! {
+                <Table title={category.to_owned()} opened=false>
+                    { for rows }
+                </Table>
+            }
+        });
+
+        html! { for categories }
+    }
+}
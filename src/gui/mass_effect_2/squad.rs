@@ -0,0 +1,342 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{CheckBox, InputNumber, NumberType, Table},
+        shared::WeaponSelect,
+    },
+    save_data::{
+        mass_effect_2::{
+            player::Power,
+            squad::Henchman,
+            squad_db::Me2SquadDb,
+            suicide_mission::Member,
+            weapon_db::{Me2WeaponCategory, Me2WeaponDb},
+        },
+        shared::plot::PlotTable,
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    Changed,
+    Flag(usize, bool),
+    Rank(RcRef<Henchman>, RcRef<Power>, i32),
+    Evolve(RcRef<Henchman>, RcRef<Power>, String),
+    Equip(RcRef<Henchman>, String),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub squad: RcRef<Vec<RcRef<Henchman>>>,
+    pub plot: RcRef<PlotTable>,
+}
+
+pub struct Me2Squad {
+    _db_handle: ContextHandle<Databases>,
+    squad_db: Option<Rc<Me2SquadDb>>,
+    weapon_db: Option<Rc<Me2WeaponDb>>,
+    error: Option<(RcRef<Henchman>, String)>,
+}
+
+impl Component for Me2Squad {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me2Squad {
+            _db_handle,
+            squad_db: databases.clone().get_me2_squad(),
+            weapon_db: databases.get_me2_weapon(),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.squad_db = dbs.clone().get_me2_squad();
+                self.weapon_db = dbs.get_me2_weapon();
+                true
+            }
+            Msg::Changed => true,
+            Msg::Flag(idx, value) => {
+                if let Some(mut boolean) = ctx.props().plot.borrow_mut().booleans_mut().get_mut(idx)
+                {
+                    *boolean = value;
+                }
+                true
+            }
+            Msg::Rank(henchman, power, rank) => {
+                if let Some(ref squad_db) = self.squad_db {
+                    squad_db.set_rank(&mut henchman.borrow_mut(), &power, rank);
+                }
+                true
+            }
+            Msg::Evolve(henchman, power, class_name) => {
+                if let Some(ref squad_db) = self.squad_db {
+                    let evolutions = squad_db.evolutions(&power.borrow().power_class_name());
+                    let evolution =
+                        evolutions.into_iter().find(|evolution| evolution.class_name == class_name);
+                    if let Some(evolution) = evolution {
+                        squad_db.evolve(&mut henchman.borrow_mut(), &power, evolution);
+                    }
+                }
+                true
+            }
+            Msg::Equip(henchman, weapon) => {
+                if let Some(ref weapon_db) = self.weapon_db {
+                    let result = weapon_db.equip_henchman(&henchman.borrow(), &weapon);
+                    self.error = result.err().map(|err| (henchman, err.to_string()));
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let (squad_db, weapon_db) = match (&self.squad_db, &self.weapon_db) {
+            (Some(squad_db), Some(weapon_db)) => (squad_db, weapon_db),
+            _ => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        // Known squadmates first, in the order of the database
+        let mut squad = ctx.props().squad.borrow().clone();
+        squad.sort_by_key(|henchman| {
+            let henchman = henchman.borrow();
+            squad_db
+                .henchmen()
+                .iter()
+                .position(|other| other.tag.eq_ignore_ascii_case(&henchman.tag()))
+                .unwrap_or(usize::MAX)
+        });
+        let henchmen =
+            squad.iter().map(|henchman| self.henchman(ctx, squad_db, weapon_db, henchman));
+
+        html! {
+            <div class="grid grid-cols-2 gap-1">
+                { for henchmen }
+            </div>
+        }
+    }
+}
+
+impl Me2Squad {
+    fn henchman(
+        &self, ctx: &Context<Self>, squad_db: &Me2SquadDb, weapon_db: &Me2WeaponDb,
+        henchman_ref: &RcRef<Henchman>,
+    ) -> Html {
+        let henchman = henchman_ref.borrow();
+        let flags = squad_db
+            .henchman(&henchman.tag())
+            .and_then(|henchman| henchman.member)
+            .map(|member| self.flags(ctx, member));
+
+        html! {
+            <Table title={squad_db.name(&henchman.tag())} opened=false>
+                { for flags }
+                <InputNumber
+                    label="Level"
+                    value={NumberType::Int(RcCell::clone(&henchman.character_level))}
+                    onchange={ctx.link().callback(|_| Msg::Changed)}
+                />
+                <InputNumber
+                    label="Talent points"
+                    value={NumberType::Int(RcCell::clone(&henchman.talent_points))}
+                    onchange={ctx.link().callback(|_| Msg::Changed)}
+                />
+                { self.powers(ctx, squad_db, henchman_ref) }
+                { self.loadout(ctx, weapon_db, henchman_ref) }
+            </Table>
+        }
+    }
+
+    fn flags(&self, ctx: &Context<Self>, member: Member) -> Html {
+        let plot = ctx.props().plot.borrow();
+        let booleans = plot.booleans();
+        let is_set = |idx: usize| booleans.get(idx).map(|b| *b).unwrap_or_default();
+
+        let (recruited, loyal, dead, _) = member.plots();
+        html! {
+            <div class="flex gap-2">
+                <CheckBox
+                    label="Recruited"
+                    value={RcCell::new(is_set(recruited))}
+                    onchange={ctx.link().callback(move |value| Msg::Flag(recruited, value))}
+                />
+                <CheckBox
+                    label="Loyal"
+                    value={RcCell::new(is_set(loyal))}
+                    onchange={ctx.link().callback(move |value| Msg::Flag(loyal, value))}
+                />
+                <CheckBox
+                    label="Alive"
+                    value={RcCell::new(!is_set(dead))}
+                    onchange={ctx.link().callback(move |value: bool| Msg::Flag(dead, !value))}
+                />
+            </div>
+        }
+    }
+
+    fn powers(
+        &self, ctx: &Context<Self>, squad_db: &Me2SquadDb, henchman_ref: &RcRef<Henchman>,
+    ) -> Html {
+        let henchman = henchman_ref.borrow();
+
+        let powers = squad_db.base_powers(&henchman).into_iter().map(|power_ref| {
+            let power = power_ref.borrow();
+            let evolved = squad_db.evolution(&henchman, &power);
+            let current_rank = power.rank() as i32;
+
+            let option = |label: String, selected: bool, onclick: Callback<MouseEvent>| {
+                html! {
+                    <button
+                        class={classes![
+                            "rounded-none",
+                            "hover:bg-theme-hover",
+                            "active:bg-theme-active",
+                            "px-1",
+                            "w-full",
+                            "text-left",
+                            selected.then(|| "bg-theme-bg"),
+                        ]}
+                        {onclick}
+                    >
+                        { label }
+                    </button>
+                }
+            };
+            let rank_callback = |rank: i32| {
+                let (henchman, power) = (RcRef::clone(henchman_ref), RcRef::clone(&power_ref));
+                ctx.link().callback(move |_| {
+                    Msg::Rank(RcRef::clone(&henchman), RcRef::clone(&power), rank)
+                })
+            };
+            let ranks = (1..=3).map(|rank| {
+                let selected = evolved.is_some() || rank <= current_rank;
+                html! {
+                    <div class="flex-1">
+                        { option(format!("Rank {}", rank), selected, rank_callback(rank)) }
+                    </div>
+                }
+            });
+
+            let evolved_class_name =
+                evolved.as_ref().map(|evolved| evolved.borrow().power_class_name().clone());
+            let evolutions = squad_db.evolutions(&power.power_class_name());
+            let mut options: Vec<_> = evolutions
+                .iter()
+                .map(|evolution| {
+                    let selected = evolved_class_name
+                        .as_ref()
+                        .map_or(false, |other| other.eq_ignore_ascii_case(&evolution.class_name));
+                    let (henchman, power) = (RcRef::clone(henchman_ref), RcRef::clone(&power_ref));
+                    let class_name = evolution.class_name.clone();
+                    let onclick = ctx.link().callback(move |_| {
+                        Msg::Evolve(RcRef::clone(&henchman), RcRef::clone(&power), class_name.clone())
+                    });
+                    option(evolution.label.clone(), selected, onclick)
+                })
+                .collect();
+            // Evolutions missing from the database can't be chosen but are shown
+            if let Some(ref evolved) = evolved {
+                let evolved = evolved.borrow();
+                let is_known = evolutions.iter().any(|evolution| {
+                    evolution.class_name.eq_ignore_ascii_case(&evolved.power_class_name())
+                });
+                if !is_known {
+                    options.push(html! {
+                        <div class="px-1 bg-theme-bg">{ evolved.name().clone() }</div>
+                    });
+                }
+            }
+
+            html! {
+                <div class="flex items-center gap-1">
+                    <div class="w-48">{ power.name().clone() }</div>
+                    { for ranks }
+                    <div class="flex-1 flex flex-col">
+                        { for options }
+                    </div>
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        onclick={rank_callback(0)}
+                    >
+                        { "reset" }
+                    </a>
+                </div>
+            }
+        });
+
+        html! {
+            <Table title="Powers" opened=false helper=
+                "The rank 4 column lists the evolutions known for each power.\n\
+                Evolving a power sets it back to rank 0 and adds the evolution, like the game does."
+            >
+                { for powers }
+            </Table>
+        }
+    }
+
+    fn loadout(
+        &self, ctx: &Context<Self>, weapon_db: &Me2WeaponDb, henchman_ref: &RcRef<Henchman>,
+    ) -> Html {
+        let henchman = henchman_ref.borrow();
+        let slots = weapon_db.henchman_slots(&henchman.tag());
+
+        let slots = weapon_db
+            .types()
+            .iter()
+            .filter(|category| slots.contains(&category.weapon_type))
+            .map(|category| self.slot(ctx, henchman_ref, category));
+
+        let issues = weapon_db.henchman_issues(&henchman).into_iter().map(|issue| {
+            html! { <p class="text-red-500">{ issue }</p> }
+        });
+        let error =
+            self.error.as_ref().filter(|(other, _)| other == henchman_ref).map(|(_, error)| {
+                html! { <p class="text-red-500">{ format!("Error: {}", error) }</p> }
+            });
+
+        html! {
+            <Table title="Loadout" opened=false helper="Only the weapon types the squadmate uses are shown.">
+                { for slots }
+                { for issues }
+                { for error }
+            </Table>
+        }
+    }
+
+    fn slot(
+        &self, ctx: &Context<Self>, henchman_ref: &RcRef<Henchman>, category: &Me2WeaponCategory,
+    ) -> Html {
+        let loadout = henchman_ref.borrow().weapon_loadout().clone();
+        let equipped = category.weapon_type.loadout_slot(&loadout).borrow().clone();
+        let weapons = category
+            .weapons
+            .iter()
+            .map(|weapon| (weapon.class_name.clone(), weapon.name.clone()))
+            .collect::<Vec<_>>();
+
+        let henchman = RcRef::clone(henchman_ref);
+        let onselect = ctx.link().batch_callback(move |weapon: Option<String>| {
+            weapon.map(|weapon| Msg::Equip(RcRef::clone(&henchman), weapon))
+        });
+
+        html! {
+            <WeaponSelect label={category.name.clone()} {weapons} {equipped} {onselect} />
+        }
+    }
+}
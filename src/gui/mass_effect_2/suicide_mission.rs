@@ -0,0 +1,240 @@
+use yew::prelude::*;
+
+use crate::{
+    gui::components::{CheckBox, Table},
+    save_data::{
+        mass_effect_2::suicide_mission::{
+            decision_paths, propose_edits, simulate, Candidate, Choices, Member, Outcome, PlotEdit,
+            Role, SquadState,
+        },
+        shared::plot::PlotTable,
+        RcCell, RcRef,
+    },
+};
+
+pub enum Msg {
+    Choose(Candidate),
+    ResetChoices,
+    Survivor(Member, bool),
+    ApplyEdits(Vec<PlotEdit>),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub plot: RcRef<PlotTable>,
+}
+
+pub struct Me2SuicideMission {
+    choices: Choices,
+    // Members who must survive, everyone recruited by default
+    survivors: Option<Vec<Member>>,
+}
+
+impl Component for Me2SuicideMission {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Me2SuicideMission { choices: Choices::default(), survivors: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Choose(candidate) => {
+                candidate.set(&mut self.choices);
+                true
+            }
+            Msg::ResetChoices => {
+                self.choices = Choices::default();
+                true
+            }
+            Msg::Survivor(member, survives) => {
+                let state = SquadState::from_plot(&ctx.props().plot.borrow());
+                let survivors = self.survivors.get_or_insert_with(|| recruited(&state));
+                survivors.retain(|&other| other != member);
+                if survives {
+                    survivors.push(member);
+                }
+                true
+            }
+            Msg::ApplyEdits(edits) => {
+                let mut plot = ctx.props().plot.borrow_mut();
+                for edit in edits {
+                    edit.apply(&mut plot);
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let state = SquadState::from_plot(&ctx.props().plot.borrow());
+        let outcome = simulate(&state, &self.choices);
+        let survivors = self.survivors.clone().unwrap_or_else(|| recruited(&state));
+
+        html! {
+            <div class="flex divide-solid divide-x divide-default-border">
+                <div class="flex-1 pr-1 flex flex-col gap-1">
+                    { self.outcome(ctx, &state, &outcome) }
+                    { self.edits(ctx, &state, &survivors) }
+                </div>
+                <div class="flex-1 pl-1 flex flex-col gap-1">
+                    { self.decisions(ctx, &state) }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl Me2SuicideMission {
+    fn outcome(&self, ctx: &Context<Self>, state: &SquadState, outcome: &Outcome) -> Html {
+        let upgrades = [
+            (state.heavy_ship_armor, "Heavy Ship Armor"),
+            (state.multicore_shielding, "Multicore Shielding"),
+            (state.thanix_cannon, "Thanix Cannon"),
+        ]
+        .into_iter()
+        .filter(|(applied, _)| !applied)
+        .map(|(_, name)| html! { <p class="text-red-500">{ format!("Missing {}", name) }</p> });
+
+        let picks = [
+            (Role::TechSpecialist, outcome.tech_specialist),
+            (Role::FirstLeader, outcome.first_leader),
+            (Role::BioticSpecialist, outcome.biotic_specialist),
+            (Role::SecondLeader, outcome.second_leader),
+            (Role::CrewEscort, outcome.crew_escort),
+        ]
+        .into_iter()
+        .map(|(role, member)| {
+            let member = member.map(|member| member.to_string()).unwrap_or_else(|| "Nobody".into());
+            html! { <p>{ format!("{}: {}", role, member) }</p> }
+        });
+        let long_walk_squad =
+            outcome.long_walk_squad.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let final_squad = outcome.final_squad.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        let members = state.members.iter().map(|member| {
+            let loyalty = if member.loyal { "loyal" } else { "not loyal" };
+            match outcome.deaths.iter().find(|death| death.member == member.member) {
+                Some(death) => html! {
+                    <p class="text-red-500">
+                        { format!("{} ({}) dies: {}", member.member, loyalty, death.reason) }
+                    </p>
+                },
+                None => html! { <p>{ format!("{} ({}) survives", member.member, loyalty) }</p> },
+            }
+        });
+
+        let shepard = if outcome.shepard_survives(state) {
+            html! { <p>{ "Shepard survives" }</p> }
+        } else {
+            html! { <p class="text-red-500">{ "Shepard dies, fewer than 2 squadmates are left" }</p> }
+        };
+
+        html! {
+            <Table title="Predicted outcome" helper=
+                "Decisions you didn't choose are the ones saving the most squadmates.\n\
+                Based on the suicide mission rules documented by the community."
+            >
+                { for upgrades }
+                { for picks }
+                <p>{ format!("{}: {}", Role::LongWalkSquad, long_walk_squad.join(" and ")) }</p>
+                <p>{ format!("{}: {}", Role::FinalSquad, final_squad.join(" and ")) }</p>
+                <button class="button" onclick={ctx.link().callback(|_| Msg::ResetChoices)}>
+                    { "Reset my decisions" }
+                </button>
+                { for members }
+                { shepard }
+            </Table>
+        }
+    }
+
+    fn edits(&self, ctx: &Context<Self>, state: &SquadState, survivors: &[Member]) -> Html {
+        let checkboxes = state.members.iter().map(|member| {
+            let member = member.member;
+            html! {
+                <CheckBox
+                    label={member.to_string()}
+                    value={RcCell::new(survivors.contains(&member))}
+                    onchange={ctx.link().callback(move |survives| Msg::Survivor(member, survives))}
+                />
+            }
+        });
+
+        let edits = propose_edits(state, &self.choices, survivors);
+        let outcome = {
+            let mut state = state.clone();
+            for edit in &edits {
+                edit.apply_to_state(&mut state);
+            }
+            simulate(&state, &self.choices)
+        };
+        let reached = survivors.iter().all(|&member| outcome.survives(member));
+
+        let content = if edits.is_empty() {
+            html! { <p>{ "Nothing to change" }</p> }
+        } else {
+            let descriptions = edits.iter().map(|edit| html! { <p>{ &edit.description }</p> });
+            let apply = {
+                let edits = edits.clone();
+                ctx.link().callback(move |_| Msg::ApplyEdits(edits.clone()))
+            };
+            html! {
+                <>
+                    { for descriptions }
+                    <button class="button" onclick={apply}>{ "Apply these edits" }</button>
+                </>
+            }
+        };
+        let warning = (!reached).then(|| {
+            html! { <p class="text-red-500">{ "Some of them can't be saved with plot edits alone" }</p> }
+        });
+
+        html! {
+            <Table title="Edits for the chosen survivors">
+                <div class="flex flex-wrap gap-x-2">
+                    { for checkboxes }
+                </div>
+                { content }
+                { for warning }
+            </Table>
+        }
+    }
+
+    fn decisions(&self, ctx: &Context<Self>, state: &SquadState) -> Html {
+        let roles = decision_paths(state, &self.choices).into_iter().map(|(role, paths)| {
+            let paths = paths.into_iter().map(|(candidate, outcome)| {
+                let deaths = if outcome.deaths.is_empty() {
+                    "everyone survives".to_owned()
+                } else {
+                    let deaths: Vec<_> =
+                        outcome.deaths.iter().map(|death| death.member.to_string()).collect();
+                    format!("{} die", deaths.join(", "))
+                };
+
+                html! {
+                    <div class="flex items-center gap-1">
+                        <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                            onclick={ctx.link().callback(move |_| Msg::Choose(candidate))}
+                        >
+                            {"choose"}
+                        </a>
+                        { format!("{}: {}", candidate, deaths) }
+                    </div>
+                }
+            });
+
+            html! {
+                <Table title={role.to_string()} opened=false>
+                    { for paths }
+                </Table>
+            }
+        });
+
+        html! { for roles }
+    }
+}
+
+fn recruited(state: &SquadState) -> Vec<Member> {
+    state.members.iter().map(|member| member.member).collect()
+}
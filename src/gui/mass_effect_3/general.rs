@@ -1,20 +1,32 @@
-use std::cell::{Ref, RefMut};
+use std::{
+    cell::{Ref, RefMut},
+    rc::Rc,
+};
 
-use yew::prelude::*;
+use gloo::storage::{LocalStorage, Storage};
+use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
     gui::{
         components::{Helper, InputText, Select, Table},
         raw_ui::RawUi,
-        shared::{BonusPowerType, BonusPowers},
+        shared::{BonusPowerType, BonusPowers, IntPlotType, Morality, MoralityType},
     },
     save_data::{
-        mass_effect_3::{player::Player, plot::PlotTable, Me3SaveGame},
-        shared::player::{Notoriety, Origin},
+        mass_effect_3::{
+            player::Player, player_class_db::Me3PlayerClassDb, plot::PlotTable, Me3SaveGame,
+        },
+        shared::{
+            location_db::{LocationDb, SafeLocation},
+            player::{Notoriety, Origin},
+        },
         RcRef,
     },
+    services::database::Databases,
 };
 
+const SAVED_LOCATIONS_KEY: &str = "me3_saved_locations";
+
 #[derive(Clone, RawUi)]
 enum Me3Class {
     Soldier,
@@ -51,10 +63,15 @@ impl Me3Class {
 }
 
 pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
     Gender(usize),
     Origin(usize),
     Notoriety(usize),
     PlayerClass(usize),
+    Relocate(usize),
+    SaveLocation,
+    RelocateSaved(usize),
+    ClearSavedLocations,
 }
 
 #[derive(Properties, PartialEq)]
@@ -72,17 +89,64 @@ impl Props {
     }
 }
 
-pub struct Me3General;
+pub struct Me3General {
+    _db_handle: ContextHandle<Databases>,
+    player_class_db: Option<Rc<Me3PlayerClassDb>>,
+    location_db: Option<Rc<LocationDb>>,
+    class_error: Option<String>,
+}
 
 impl Component for Me3General {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Me3General
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me3General {
+            _db_handle,
+            player_class_db: databases.clone().get_me3_player_classes(),
+            location_db: databases.get_me3_location(),
+            class_error: None,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.player_class_db = dbs.clone().get_me3_player_classes();
+                self.location_db = dbs.get_me3_location();
+                return true;
+            }
+            Msg::Relocate(idx) => {
+                let location_db = self.location_db.as_ref();
+                if let Some(spot) = location_db.and_then(|db| db.locations().get(idx)) {
+                    ctx.props().save_game_mut().relocate(spot);
+                }
+                return true;
+            }
+            Msg::SaveLocation => {
+                let mut saved_locations = Self::saved_locations();
+                saved_locations.push(ctx.props().save_game().current_spot());
+                let _ = LocalStorage::set(SAVED_LOCATIONS_KEY, saved_locations);
+                return true;
+            }
+            Msg::RelocateSaved(idx) => {
+                if let Some(spot) = Self::saved_locations().get(idx) {
+                    ctx.props().save_game_mut().relocate(spot);
+                }
+                return true;
+            }
+            Msg::ClearSavedLocations => {
+                LocalStorage::delete(SAVED_LOCATIONS_KEY);
+                return true;
+            }
+            _ => {}
+        }
+
         let Me3SaveGame { player, plot, .. } = &mut *ctx.props().save_game_mut();
         let (mut player, mut plot) = (player.borrow_mut(), plot.borrow_mut());
         match msg {
@@ -217,9 +281,21 @@ impl Component for Me3General {
                 false
             }
             Msg::PlayerClass(class_idx) => {
-                *player.class_name_mut() = Me3Class::names()[class_idx].to_owned();
-                false
+                let class_name = Me3Class::names()[class_idx];
+                match self.player_class_db {
+                    Some(ref player_class_db) => {
+                        let result = player_class_db.change_class(&mut player, class_name);
+                        self.class_error = result.err().map(|err| err.to_string());
+                    }
+                    None => *player.class_name_mut() = class_name.to_owned(),
+                }
+                true
             }
+            Msg::DatabaseLoaded(_)
+            | Msg::Relocate(_)
+            | Msg::SaveLocation
+            | Msg::RelocateSaved(_)
+            | Msg::ClearSavedLocations => unreachable!(),
         }
     }
 
@@ -231,10 +307,11 @@ impl Component for Me3General {
                 <div class="flex-1 pr-1 flex flex-col gap-1">
                     { Self::role_play(ctx, save_game.player()) }
                     { Self::morality(save_game.plot()) }
-                    { Self::gameplay(ctx, save_game.player()) }
+                    { self.gameplay(ctx, save_game.player()) }
                 </div>
                 <div class="flex-1 pl-1 flex flex-col gap-1">
                     { Self::general(&save_game) }
+                    { self.location(ctx, &save_game) }
                     { Self::bonus_powers(save_game.player()) }
                 </div>
             </div>
@@ -289,16 +366,11 @@ impl Me3General {
 
     fn morality(plot: Ref<'_, PlotTable>) -> Html {
         html! {
-            <Table title="Morality">
-                { for plot.integers().get(&10159).map(|paragon| paragon.view("Paragon")) }
-                { for plot.integers().get(&10160).map(|renegade| renegade.view("Renegade")) }
-                { for plot.integers().get(&10297).map(|renegade| renegade.view("Reputation")) }
-                { for plot.integers().get(&10380).map(|renegade| renegade.view("Reputation Points")) }
-            </Table>
+            <Morality morality={MoralityType::Me3(IntPlotType::IndexMap(RcRef::clone(&plot.integers)))} />
         }
     }
 
-    fn gameplay(ctx: &Context<Self>, player: Ref<'_, Player>) -> Html {
+    fn gameplay(&self, ctx: &Context<Self>, player: Ref<'_, Player>) -> Html {
         let Player {
             level,
             current_xp,
@@ -315,6 +387,9 @@ impl Me3General {
             .enumerate()
             .find_map(|(i, name)| player.class_name().eq_ignore_ascii_case(name).then(|| i))
             .unwrap_or_default();
+        let class_error = self.class_error.as_ref().map(|error| {
+            html! { <p class="text-red-500">{ error }</p> }
+        });
 
         html! {
             <Table title="Gameplay">
@@ -325,7 +400,12 @@ impl Me3General {
                         onselect={ctx.link().callback(Msg::PlayerClass)}
                     />
                     {"Class"}
+                    <Helper text=
+                        "Changing your class replaces the powers of your old class by the new ones \
+                        and refunds their talent points. Bonus powers are kept."
+                    />
                 </div>
+                { for class_error }
                 { level.view("Level") }
                 { current_xp.view("Current XP") }
                 { talent_points.view("Talent Points") }
@@ -348,6 +428,64 @@ impl Me3General {
         }
     }
 
+    fn location(&self, ctx: &Context<Self>, save_game: &Ref<Me3SaveGame>) -> Html {
+        let (x, y, z) = save_game.location().xyz();
+
+        let spots =
+            self.location_db.iter().flat_map(|db| db.locations()).enumerate().map(|(idx, spot)| {
+                let onclick = ctx.link().callback(move |_| Msg::Relocate(idx));
+                html! {
+                    <button class="button" {onclick}>
+                        { format!("Teleport to {}", spot.name) }
+                    </button>
+                }
+            });
+
+        let saved_locations = Self::saved_locations();
+        let saved_spots = saved_locations.iter().enumerate().map(|(idx, spot)| {
+            let onclick = ctx.link().callback(move |_| Msg::RelocateSaved(idx));
+            html! {
+                <button class="button" {onclick}>
+                    { format!("Teleport to {}", spot.name) }
+                </button>
+            }
+        });
+        let clear_saved = (!saved_locations.is_empty()).then(|| {
+            html! {
+                <button class="button" onclick={ctx.link().callback(|_| Msg::ClearSavedLocations)}>
+                    { "Forget remembered locations" }
+                </button>
+            }
+        });
+
+        html! {
+            <Table title="Location" helper=
+                "Teleporting rewrites the level, position, rotation and streamed levels together.\n\
+                Use it to get out of a soft-lock, the save loads at the chosen location.\n\
+                Remember the location of a save made elsewhere (the Citadel, ...) to teleport \
+                other saves there."
+            >
+                <div class="flex items-center gap-1 cursor-default">
+                    { format!("{} at ({:.0}, {:.0}, {:.0})", save_game.base_level_name(), x, y, z) }
+                </div>
+                <div class="flex flex-wrap items-center gap-1">
+                    { for spots }
+                    { for saved_spots }
+                </div>
+                <div class="flex flex-wrap items-center gap-1">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::SaveLocation)}>
+                        { "Remember this location" }
+                    </button>
+                    { for clear_saved }
+                </div>
+            </Table>
+        }
+    }
+
+    fn saved_locations() -> Vec<SafeLocation> {
+        LocalStorage::get(SAVED_LOCATIONS_KEY).unwrap_or_default()
+    }
+
     fn bonus_powers(player: Ref<'_, Player>) -> Html {
         let power_list: &'static [(&'static str, &'static str, &'static str)] = &[
             ("EnergyDrain", "SFXGameContent.SFXPowerCustomAction_EnergyDrain", "Energy Drain"),
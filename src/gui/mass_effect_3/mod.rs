@@ -1,9 +1,16 @@
 mod general;
 mod plot;
 mod plot_variable;
+mod powers;
 mod raw_plot;
+mod squad;
+mod war_assets;
+mod weapons;
 
-pub use self::{general::*, plot::*, plot_variable::*, raw_plot::*};
+pub use self::{
+    general::*, plot::*, plot_variable::*, powers::*, raw_plot::*, squad::*, war_assets::*,
+    weapons::*,
+};
 
 use yew::prelude::*;
 
@@ -0,0 +1,248 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{CheckBox, Table},
+    save_data::{
+        mass_effect_3::{player::Player, player::Power, power_db::Me3PowerDb, squad::Henchman},
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+#[derive(Clone)]
+pub enum Me3PowersType {
+    Player(RcRef<Player>),
+    Henchman(RcRef<Henchman>),
+}
+
+impl PartialEq for Me3PowersType {
+    fn eq(&self, other: &Me3PowersType) -> bool {
+        match (self, other) {
+            (Me3PowersType::Player(player), Me3PowersType::Player(other)) => player == other,
+            (Me3PowersType::Henchman(henchman), Me3PowersType::Henchman(other)) => {
+                henchman == other
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Me3PowersType {
+    fn title(&self) -> String {
+        match self {
+            Me3PowersType::Player(_) => "Player".to_owned(),
+            Me3PowersType::Henchman(_) => "Powers".to_owned(),
+        }
+    }
+
+    fn powers(&self) -> Vec<RcRef<Power>> {
+        match self {
+            Me3PowersType::Player(player) => player.borrow().powers().clone(),
+            Me3PowersType::Henchman(henchman) => henchman.borrow().powers().clone(),
+        }
+    }
+
+    fn talent_points(&self) -> RcCell<i32> {
+        match self {
+            Me3PowersType::Player(player) => RcCell::clone(&player.borrow().talent_points),
+            Me3PowersType::Henchman(henchman) => RcCell::clone(&henchman.borrow().talent_points),
+        }
+    }
+
+    fn spent_talent_points(&self) -> i32 {
+        match self {
+            Me3PowersType::Player(player) => player.borrow().spent_talent_points(),
+            Me3PowersType::Henchman(henchman) => henchman.borrow().spent_talent_points(),
+        }
+    }
+
+    fn check(&self, power_db: &Me3PowerDb) -> Option<String> {
+        match self {
+            Me3PowersType::Player(player) => power_db.check_player(&player.borrow()),
+            Me3PowersType::Henchman(henchman) => power_db.check_henchman(&henchman.borrow()),
+        }
+    }
+
+    fn respec(&self) {
+        match self {
+            Me3PowersType::Player(player) => player.borrow_mut().respec(),
+            Me3PowersType::Henchman(henchman) => henchman.borrow_mut().respec(),
+        }
+    }
+}
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    Rank(RcRef<Power>, i32, usize),
+    Respec,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub character: Me3PowersType,
+    #[prop_or(true)]
+    pub opened: bool,
+}
+
+pub struct Me3Powers {
+    _db_handle: ContextHandle<Databases>,
+    power_db: Option<Rc<Me3PowerDb>>,
+    pay: RcCell<bool>,
+    error: Option<String>,
+}
+
+impl Component for Me3Powers {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me3Powers {
+            _db_handle,
+            power_db: databases.get_me3_power(),
+            pay: RcCell::new(true),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.power_db = dbs.get_me3_power();
+                true
+            }
+            Msg::Rank(power, rank, evolution) => {
+                let power = power.borrow();
+                if self.pay.get() {
+                    let talent_points = ctx.props().character.talent_points();
+                    let result = power.rank_up(&talent_points, rank, evolution);
+                    self.error = result.err().map(|err| err.to_string());
+                } else {
+                    power.set_rank(rank, evolution);
+                    self.error = None;
+                }
+                true
+            }
+            Msg::Respec => {
+                ctx.props().character.respec();
+                self.error = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let power_db = match self.power_db {
+            Some(ref power_db) => power_db,
+            None => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        let Props { character, opened } = &ctx.props();
+        let powers = character.powers().into_iter().map(|power| self.power(ctx, power_db, power));
+        let warning = character.check(power_db).map(|warning| {
+            html! { <p class="text-red-500">{ warning }</p> }
+        });
+        let error = self.error.as_ref().map(|error| {
+            html! { <p class="text-red-500">{ format!("Error: {}", error) }</p> }
+        });
+
+        html! {
+            <Table title={character.title()} opened={*opened} helper=
+                "Each rank costs its number of talent points.\n\
+                Respec refunds every rank, including the ones the character started with."
+            >
+                <div class="flex items-center gap-1">
+                    { format!("{} talent points spent, {} available", character.spent_talent_points(), character.talent_points().get()) }
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::Respec)}>
+                        { "Respec" }
+                    </button>
+                </div>
+                <CheckBox label="Pay with the talent points" value={RcCell::clone(&self.pay)} />
+                { for warning }
+                { for error }
+                { for powers }
+            </Table>
+        }
+    }
+}
+
+impl Me3Powers {
+    fn power(&self, ctx: &Context<Self>, power_db: &Me3PowerDb, power_ref: RcRef<Power>) -> Html {
+        let power = power_ref.borrow();
+        let name = power.name().to_owned();
+        let current_rank = power.current_rank();
+
+        let option = |label: String, rank: i32, evolution: usize, selected: bool| {
+            let power_ref = RcRef::clone(&power_ref);
+            html! {
+                <button
+                    class={classes![
+                        "rounded-none",
+                        "hover:bg-theme-hover",
+                        "active:bg-theme-active",
+                        "px-1",
+                        "w-full",
+                        "text-left",
+                        selected.then(|| "bg-theme-bg"),
+                    ]}
+                    onclick={ctx.link().callback(move |_| Msg::Rank(RcRef::clone(&power_ref), rank, evolution))}
+                >
+                    { label }
+                </button>
+            }
+        };
+
+        let ranks = (1..=Power::MAX_RANK).map(|rank| {
+            let options = if rank < 4 {
+                vec![option(format!("Rank {}", rank), rank, 0, rank <= current_rank)]
+            } else {
+                let chosen = power.evolution(rank);
+                (0..2)
+                    .map(|evolution| {
+                        let label = power_db.evolution_name(&name, rank, evolution);
+                        option(label, rank, evolution, chosen == Some(evolution))
+                    })
+                    .collect()
+            };
+            html! {
+                <div class="flex-1 flex flex-col">
+                    { for options }
+                </div>
+            }
+        });
+        let invalid = (!power.has_valid_evolutions()).then(|| {
+            html! {
+                <p class="text-red-500">{ "The evolutions don't match the rank, set the rank again to fix them" }</p>
+            }
+        });
+        let reset_ref = RcRef::clone(&power_ref);
+        let reset = html! {
+            <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                onclick={ctx.link().callback(move |_| Msg::Rank(RcRef::clone(&reset_ref), 0, 0))}
+            >
+                { "reset" }
+            </a>
+        };
+
+        html! {
+            <div class="flex flex-col">
+                <div class="flex items-center gap-1">
+                    <div class="w-48">{ power_db.label(&name) }</div>
+                    { for ranks }
+                    { reset }
+                </div>
+                { for invalid }
+            </div>
+        }
+    }
+}
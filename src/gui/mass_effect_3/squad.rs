@@ -0,0 +1,259 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{InputNumber, NumberType, Table},
+        mass_effect_3::{Me3Powers, Me3PowersType},
+        shared::WeaponSelect,
+    },
+    save_data::{
+        mass_effect_3::{
+            player::Player,
+            squad::Henchman,
+            squad_db::Me3SquadDb,
+            weapon_db::{Me3WeaponCategory, Me3WeaponDb, EMPTY_SLOT},
+        },
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    Changed,
+    Equip(RcRef<Henchman>, String),
+    Attach(RcRef<Henchman>, String, String),
+    Detach(RcRef<Henchman>, String, String),
+    CopyLoadout(RcRef<Henchman>),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub squad: RcRef<Vec<RcRef<Henchman>>>,
+    pub player: RcRef<Player>,
+}
+
+pub struct Me3Squad {
+    _db_handle: ContextHandle<Databases>,
+    squad_db: Option<Rc<Me3SquadDb>>,
+    weapon_db: Option<Rc<Me3WeaponDb>>,
+    error: Option<(RcRef<Henchman>, String)>,
+}
+
+impl Component for Me3Squad {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me3Squad {
+            _db_handle,
+            squad_db: databases.clone().get_me3_squad(),
+            weapon_db: databases.get_me3_weapon(),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.squad_db = dbs.clone().get_me3_squad();
+                self.weapon_db = dbs.get_me3_weapon();
+                true
+            }
+            Msg::Changed => true,
+            Msg::Equip(henchman, weapon) => {
+                if let Some(ref weapon_db) = self.weapon_db {
+                    let result = weapon_db.equip_henchman(&henchman.borrow(), &weapon);
+                    self.error = result.err().map(|err| (henchman, err.to_string()));
+                }
+                true
+            }
+            Msg::Attach(henchman, weapon, weapon_mod) => {
+                if let Some(ref weapon_db) = self.weapon_db {
+                    let result =
+                        weapon_db.attach_mod(&henchman.borrow().weapon_mods, &weapon, &weapon_mod);
+                    self.error = result.err().map(|err| (henchman, err.to_string()));
+                }
+                true
+            }
+            Msg::Detach(henchman, weapon, weapon_mod) => {
+                Me3WeaponDb::detach_mod(&henchman.borrow().weapon_mods, &weapon, &weapon_mod);
+                self.error = None;
+                true
+            }
+            Msg::CopyLoadout(henchman) => {
+                henchman.borrow().copy_loadout(&ctx.props().player.borrow());
+                self.error = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let (squad_db, weapon_db) = match (&self.squad_db, &self.weapon_db) {
+            (Some(squad_db), Some(weapon_db)) => (squad_db, weapon_db),
+            _ => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        // Known squadmates first, in the order of the database
+        let mut squad = ctx.props().squad.borrow().clone();
+        squad.sort_by_key(|henchman| {
+            let henchman = henchman.borrow();
+            squad_db
+                .henchmen()
+                .iter()
+                .position(|other| other.tag.eq_ignore_ascii_case(&henchman.tag()))
+                .unwrap_or(usize::MAX)
+        });
+        let henchmen =
+            squad.iter().map(|henchman| self.henchman(ctx, squad_db, weapon_db, henchman));
+
+        html! {
+            <div class="grid grid-cols-2 gap-1">
+                { for henchmen }
+            </div>
+        }
+    }
+}
+
+impl Me3Squad {
+    fn henchman(
+        &self, ctx: &Context<Self>, squad_db: &Me3SquadDb, weapon_db: &Me3WeaponDb,
+        henchman_ref: &RcRef<Henchman>,
+    ) -> Html {
+        let henchman = henchman_ref.borrow();
+        let character = Me3PowersType::Henchman(RcRef::clone(henchman_ref));
+
+        html! {
+            <Table title={squad_db.name(&henchman.tag())} opened=false>
+                <InputNumber
+                    label="Level"
+                    value={NumberType::Int(RcCell::clone(&henchman.character_level))}
+                    onchange={ctx.link().callback(|_| Msg::Changed)}
+                />
+                <InputNumber
+                    label="Talent points"
+                    value={NumberType::Int(RcCell::clone(&henchman.talent_points))}
+                    onchange={ctx.link().callback(|_| Msg::Changed)}
+                />
+                <InputNumber
+                    label="Grenades"
+                    value={NumberType::Int(RcCell::clone(&henchman.grenades))}
+                    onchange={ctx.link().callback(|_| Msg::Changed)}
+                />
+                <Me3Powers {character} opened=false />
+                { self.loadout(ctx, weapon_db, henchman_ref) }
+            </Table>
+        }
+    }
+
+    fn loadout(
+        &self, ctx: &Context<Self>, weapon_db: &Me3WeaponDb, henchman_ref: &RcRef<Henchman>,
+    ) -> Html {
+        let henchman = henchman_ref.borrow();
+        let loadout = henchman.weapon_loadout();
+
+        let slots = weapon_db.henchman_slots(&henchman.tag());
+        let slots =
+            weapon_db.types().iter().filter(|category| slots.contains(&category.weapon_type)).map(
+                |category| {
+                    let equipped = category.weapon_type.loadout_slot(&loadout).borrow().clone();
+                    self.slot(ctx, henchman_ref, category, equipped)
+                },
+            );
+
+        let issues = weapon_db.henchman_issues(&henchman).into_iter().map(|issue| {
+            html! { <p class="text-red-500">{ issue }</p> }
+        });
+        let error =
+            self.error.as_ref().filter(|(other, _)| other == henchman_ref).map(|(_, error)| {
+                html! { <p class="text-red-500">{ format!("Error: {}", error) }</p> }
+            });
+
+        let copy_ref = RcRef::clone(henchman_ref);
+        let copy = ctx.link().callback(move |_| Msg::CopyLoadout(RcRef::clone(&copy_ref)));
+
+        html! {
+            <Table title="Loadout" helper=
+                "Only the weapon types the squadmate uses are shown.\n\
+                Copying the player's loadout fills these slots with the player's weapons and their mods."
+            >
+                <button class="button" onclick={copy}>
+                    { "Copy loadout from player" }
+                </button>
+                { for slots }
+                { for issues }
+                { for error }
+            </Table>
+        }
+    }
+
+    fn slot(
+        &self, ctx: &Context<Self>, henchman_ref: &RcRef<Henchman>, category: &Me3WeaponCategory,
+        equipped: String,
+    ) -> Html {
+        let attached = Me3WeaponDb::mods(&henchman_ref.borrow().weapon_mods, &equipped);
+
+        let mods = category.mods.iter().map(|weapon_mod| {
+            let is_attached =
+                attached.iter().any(|other| other.eq_ignore_ascii_case(&weapon_mod.class_name));
+            let henchman = RcRef::clone(henchman_ref);
+            let (weapon, mod_class_name) = (equipped.clone(), weapon_mod.class_name.clone());
+            let (label, onclick) = if is_attached {
+                ("detach", ctx.link().callback(move |_| {
+                    Msg::Detach(RcRef::clone(&henchman), weapon.clone(), mod_class_name.clone())
+                }))
+            } else {
+                ("attach", ctx.link().callback(move |_| {
+                    Msg::Attach(RcRef::clone(&henchman), weapon.clone(), mod_class_name.clone())
+                }))
+            };
+            html! {
+                <div class="flex items-center gap-1">
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        {onclick}
+                    >
+                        { label }
+                    </a>
+                    { &weapon_mod.name }
+                </div>
+            }
+        });
+
+        let weapons = category
+            .weapons
+            .iter()
+            .map(|weapon| (weapon.class_name.clone(), weapon.name.clone()))
+            .collect::<Vec<_>>();
+        let henchman = RcRef::clone(henchman_ref);
+        let onselect = ctx.link().batch_callback(move |weapon: Option<String>| {
+            weapon.map(|weapon| Msg::Equip(RcRef::clone(&henchman), weapon))
+        });
+        let is_empty = equipped.is_empty() || equipped == EMPTY_SLOT;
+        let mods = (!is_empty).then(|| {
+            html! {
+                <Table title="Mods (two per weapon)" opened=false>
+                    { for mods }
+                </Table>
+            }
+        });
+
+        html! {
+            <div>
+                <WeaponSelect label={category.name.clone()} {weapons} {equipped} {onselect} />
+                { for mods }
+            </div>
+        }
+    }
+}
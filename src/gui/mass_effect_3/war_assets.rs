@@ -0,0 +1,228 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{InputNumber, NumberType, Table},
+    save_data::{
+        mass_effect_3::{
+            player::Player,
+            plot::PlotTable,
+            war_assets_db::{effective_military_strength, is_multiplier, Me3WarAssetsDb},
+        },
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    Changed,
+    Add(i32),
+    AddNew,
+    Remove(i32),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub player: RcRef<Player>,
+    pub plot: RcRef<PlotTable>,
+}
+
+pub struct Me3WarAssets {
+    _db_handle: ContextHandle<Databases>,
+    war_assets_db: Option<Rc<Me3WarAssetsDb>>,
+    readiness: RcCell<i32>,
+    new_id: RcCell<i32>,
+    new_value: RcCell<i32>,
+}
+
+impl Component for Me3WarAssets {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me3WarAssets {
+            _db_handle,
+            war_assets_db: databases.get_me3_war_assets(),
+            readiness: RcCell::new(50),
+            new_id: RcCell::new(0),
+            new_value: RcCell::new(0),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.war_assets_db = dbs.get_me3_war_assets();
+                true
+            }
+            Msg::Changed => true,
+            Msg::Add(id) => {
+                let strength = self
+                    .war_assets_db
+                    .as_ref()
+                    .and_then(|db| db.asset(id))
+                    .map_or(0, |asset| asset.strength);
+                self.add(ctx, id, strength);
+                true
+            }
+            Msg::AddNew => {
+                self.add(ctx, self.new_id.get(), self.new_value.get());
+                true
+            }
+            Msg::Remove(id) => {
+                ctx.props().player.borrow_mut().remove_war_asset(id);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let war_assets_db = match self.war_assets_db {
+            Some(ref war_assets_db) => war_assets_db,
+            None => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        html! {
+            <div class="flex divide-solid divide-x divide-default-border">
+                <div class="flex-1 pr-1 flex flex-col gap-1">
+                    { self.strength(ctx, war_assets_db) }
+                    { self.add_assets(ctx, war_assets_db) }
+                </div>
+                <div class="flex-1 pl-1 flex flex-col gap-1">
+                    { self.assets(ctx, war_assets_db) }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl Me3WarAssets {
+    fn add(&self, ctx: &Context<Self>, id: i32, value: i32) {
+        if let Some(ref war_assets_db) = self.war_assets_db {
+            let Props { player, plot } = &ctx.props();
+            war_assets_db.add_asset(&mut player.borrow_mut(), &mut plot.borrow_mut(), id, value);
+        }
+    }
+
+    fn strength(&self, ctx: &Context<Self>, war_assets_db: &Me3WarAssetsDb) -> Html {
+        let Props { player, plot } = &ctx.props();
+        let tms = player.borrow().total_military_strength();
+        let ems = effective_military_strength(tms, self.readiness.get());
+
+        let plot = plot.borrow();
+        let endings = war_assets_db.endings().iter().map(|ending| {
+            let available = ending.is_available(ems, &plot);
+            html! {
+                <p class={classes![(!available).then(|| "text-red-500")]}>
+                    { format!("{} ({} EMS): {}", ending.name, ending.min_ems,
+                        if available { "available" } else { "unavailable" }) }
+                </p>
+            }
+        });
+
+        html! {
+            <>
+                <Table title="Military strength" helper=
+                    "The galactic readiness isn't stored in the save, it depends on the multiplayer and the companion apps.\n\
+                    The multipliers aren't part of the total military strength."
+                >
+                    <p>{ format!("Total military strength: {}", tms) }</p>
+                    <InputNumber
+                        label="Galactic readiness (%)"
+                        value={NumberType::Int(RcCell::clone(&self.readiness))}
+                        onchange={ctx.link().callback(|_| Msg::Changed)}
+                    />
+                    <p>{ format!("Effective military strength: {}", ems) }</p>
+                </Table>
+                <Table title="Endings">
+                    { for endings }
+                </Table>
+            </>
+        }
+    }
+
+    fn add_assets(&self, ctx: &Context<Self>, war_assets_db: &Me3WarAssetsDb) -> Html {
+        let player = ctx.props().player.borrow();
+        let war_assets = player.war_assets();
+        let missing = war_assets_db
+            .assets()
+            .iter()
+            .filter(|asset| !war_assets.contains_key(&asset.id))
+            .map(|asset| {
+                let id = asset.id;
+                html! {
+                    <div class="flex items-center gap-1">
+                        <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                            onclick={ctx.link().callback(move |_| Msg::Add(id))}
+                        >
+                            { "add" }
+                        </a>
+                        { asset.label() }
+                    </div>
+                }
+            });
+
+        html! {
+            <Table title="Add a war asset" helper=
+                "Adding a known asset gives it its usual strength and sets the plot flags granting it."
+            >
+                <InputNumber label="Id" value={NumberType::Int(RcCell::clone(&self.new_id))} />
+                <InputNumber label="Strength" value={NumberType::Int(RcCell::clone(&self.new_value))} />
+                <button class="button" onclick={ctx.link().callback(|_| Msg::AddNew)}>
+                    { "Add" }
+                </button>
+                { for missing }
+            </Table>
+        }
+    }
+
+    fn assets(&self, ctx: &Context<Self>, war_assets_db: &Me3WarAssetsDb) -> Html {
+        let player = ctx.props().player.borrow();
+        let war_assets = player.war_assets();
+        let (multipliers, assets): (Vec<_>, Vec<_>) =
+            war_assets.iter().partition(|(_, value)| is_multiplier(value.get()));
+
+        let row = |(&id, value): (&i32, &RcCell<i32>)| {
+            let label = match war_assets_db.asset(id) {
+                Some(asset) => asset.label(),
+                None => format!("Asset {}", id),
+            };
+            html! {
+                <div class="flex items-center gap-1">
+                    <InputNumber
+                        {label}
+                        value={NumberType::Int(RcCell::clone(value))}
+                        onchange={ctx.link().callback(|_| Msg::Changed)}
+                    />
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        onclick={ctx.link().callback(move |_| Msg::Remove(id))}
+                    >
+                        { "remove" }
+                    </a>
+                </div>
+            }
+        };
+
+        html! {
+            <>
+                <Table title="War assets">
+                    { for assets.into_iter().map(row) }
+                </Table>
+                <Table title="Multipliers" helper="10000 is a x1.0 multiplier, 15000 a x1.5 one">
+                    { for multipliers.into_iter().map(row) }
+                </Table>
+            </>
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{CallbackType, CheckBox, InputNumber, NumberType, Table},
+        shared::WeaponSelect,
+    },
+    save_data::{
+        mass_effect_3::{
+            player::Player,
+            plot::PlotTable,
+            weapon_db::{Me3WeaponCategory, Me3WeaponDb, Me3WeaponType, PlayerVariables},
+        },
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    Select(String),
+    Unlock(String, bool),
+    Level(String, CallbackType),
+    Equip(String),
+    Unequip(Me3WeaponType),
+    Attach(String, String),
+    Detach(String, String),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub player: RcRef<Player>,
+    pub plot: RcRef<PlotTable>,
+    pub variables: RcRef<PlayerVariables>,
+}
+
+pub struct Me3Weapons {
+    _db_handle: ContextHandle<Databases>,
+    weapon_db: Option<Rc<Me3WeaponDb>>,
+    selected: Option<String>,
+    error: Option<String>,
+}
+
+impl Component for Me3Weapons {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Me3Weapons {
+            _db_handle,
+            weapon_db: databases.get_me3_weapon(),
+            selected: None,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let Props { player, plot, variables } = &ctx.props();
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.weapon_db = dbs.get_me3_weapon();
+                true
+            }
+            Msg::Select(weapon) => {
+                self.selected = Some(weapon);
+                true
+            }
+            Msg::Unlock(weapon, unlocked) => {
+                if let Some(ref weapon_db) = self.weapon_db {
+                    let (mut plot, mut variables) = (plot.borrow_mut(), variables.borrow_mut());
+                    weapon_db.set_unlocked(&mut plot, &mut variables, &weapon, unlocked);
+                }
+                true
+            }
+            Msg::Level(class_name, CallbackType::Int(level)) => {
+                if let Some(ref weapon_db) = self.weapon_db {
+                    weapon_db.set_upgrade_level(&mut variables.borrow_mut(), &class_name, level);
+                }
+                true
+            }
+            Msg::Level(..) => unreachable!(),
+            Msg::Equip(weapon) => {
+                if let Some(ref weapon_db) = self.weapon_db {
+                    let result = weapon_db.equip(&player.borrow(), &weapon);
+                    self.error = result.err().map(|err| err.to_string());
+                }
+                true
+            }
+            Msg::Unequip(weapon_type) => {
+                Me3WeaponDb::unequip(&player.borrow().weapons_loadout(), weapon_type);
+                true
+            }
+            Msg::Attach(weapon, weapon_mod) => {
+                if let Some(ref weapon_db) = self.weapon_db {
+                    let result =
+                        weapon_db.attach_mod(&player.borrow().weapons_mods, &weapon, &weapon_mod);
+                    self.error = result.err().map(|err| err.to_string());
+                }
+                true
+            }
+            Msg::Detach(weapon, weapon_mod) => {
+                Me3WeaponDb::detach_mod(&player.borrow().weapons_mods, &weapon, &weapon_mod);
+                self.error = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let weapon_db = match self.weapon_db {
+            Some(ref weapon_db) => weapon_db,
+            None => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        let categories = weapon_db.types().iter().map(|category| self.category(ctx, category));
+
+        html! {
+            <div class="flex divide-solid divide-x divide-default-border">
+                <div class="flex-1 pr-1 flex flex-col gap-1">
+                    { self.loadout(ctx, weapon_db) }
+                    { self.selected(ctx, weapon_db) }
+                </div>
+                <div class="flex-1 pl-1 flex flex-col gap-1">
+                    { for categories }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl Me3Weapons {
+    fn loadout(&self, ctx: &Context<Self>, weapon_db: &Me3WeaponDb) -> Html {
+        let Props { player, plot, .. } = &ctx.props();
+        let player = player.borrow();
+        let loadout = player.weapons_loadout();
+
+        let slots = weapon_db.types().iter().map(|category| {
+            let weapon_type = category.weapon_type;
+            let weapons = category
+                .weapons
+                .iter()
+                .map(|weapon| (weapon.class_name.clone(), weapon.name.clone()))
+                .collect::<Vec<_>>();
+            let equipped = weapon_type.loadout_slot(&loadout).borrow().clone();
+            let onselect = ctx.link().callback(move |weapon: Option<String>| match weapon {
+                Some(weapon) => Msg::Equip(weapon),
+                None => Msg::Unequip(weapon_type),
+            });
+            html! {
+                <WeaponSelect label={category.name.clone()} {weapons} {equipped} unequip=true {onselect} />
+            }
+        });
+
+        let issues = weapon_db.player_issues(&player, &plot.borrow());
+        let issues = issues.into_iter().map(|issue| {
+            html! { <p class="text-red-500">{ issue }</p> }
+        });
+        let error = self.error.as_ref().map(|error| {
+            html! { <p class="text-red-500">{ format!("Error: {}", error) }</p> }
+        });
+
+        html! {
+            <Table title="Loadout" helper=
+                "Select a weapon to unlock, upgrade or equip it and to attach its mods.\n\
+                The primary and secondary weapons must be part of the loadout.\n\
+                Every class can carry every weapon type, `-` empties the slot."
+            >
+                { for slots }
+                <p>{ format!("Primary weapon: {}", weapon_db.name(&player.primary_weapon())) }</p>
+                <p>{ format!("Secondary weapon: {}", weapon_db.name(&player.secondary_weapon())) }</p>
+                { for issues }
+                { for error }
+            </Table>
+        }
+    }
+
+    fn selected(&self, ctx: &Context<Self>, weapon_db: &Me3WeaponDb) -> Html {
+        let (category, weapon) =
+            match self.selected.as_deref().and_then(|class_name| weapon_db.weapon(class_name)) {
+                Some(selected) => selected,
+                None => return Html::default(),
+            };
+        let Props { player, plot, variables } = &ctx.props();
+        let variables = variables.borrow();
+        let class_name = weapon.class_name.clone();
+        let weapon_class_name = weapon.class_name.clone();
+
+        let unlocked = weapon.unlock.map(|_| {
+            let class_name = class_name.clone();
+            html! {
+                <CheckBox
+                    label="Unlocked"
+                    value={RcCell::new(weapon.is_unlocked(&plot.borrow()))}
+                    onchange={ctx.link().callback(move |unlocked| Msg::Unlock(class_name.clone(), unlocked))}
+                />
+            }
+        });
+        let level = {
+            let level = Me3WeaponDb::upgrade_level(&variables, &class_name);
+            let class_name = class_name.clone();
+            html! {
+                <InputNumber
+                    label={format!("Upgrade level (max {})", weapon_db.max_weapon_level)}
+                    value={NumberType::Int(RcCell::new(level))}
+                    onchange={ctx.link().callback(move |level| Msg::Level(class_name.clone(), level))}
+                />
+            }
+        };
+
+        let attached = Me3WeaponDb::mods(&player.borrow().weapons_mods, &class_name);
+        let mods = category.mods.iter().map(|weapon_mod| {
+            let is_attached = attached.iter().any(|other| other.eq_ignore_ascii_case(&weapon_mod.class_name));
+            let (weapon, mod_class_name) = (class_name.clone(), weapon_mod.class_name.clone());
+            let (label, onclick) = if is_attached {
+                ("detach", ctx.link().callback(move |_| Msg::Detach(weapon.clone(), mod_class_name.clone())))
+            } else {
+                ("attach", ctx.link().callback(move |_| Msg::Attach(weapon.clone(), mod_class_name.clone())))
+            };
+            let mod_class_name = weapon_mod.class_name.clone();
+            html! {
+                <div class="flex items-center gap-1">
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        {onclick}
+                    >
+                        { label }
+                    </a>
+                    <InputNumber
+                        label={weapon_mod.name.clone()}
+                        value={NumberType::Int(RcCell::new(Me3WeaponDb::upgrade_level(&variables, &weapon_mod.class_name)))}
+                        onchange={ctx.link().callback(move |level| Msg::Level(mod_class_name.clone(), level))}
+                    />
+                </div>
+            }
+        });
+
+        let equip = ctx.link().callback(move |_| Msg::Equip(weapon_class_name.clone()));
+
+        html! {
+            <Table title={weapon.name.clone()}>
+                { for unlocked }
+                { level }
+                <button class="button" onclick={equip}>
+                    { "Equip" }
+                </button>
+                <Table title={format!("Mods (max level {}, two per weapon)", weapon_db.max_mod_level)}>
+                    { for mods }
+                </Table>
+            </Table>
+        }
+    }
+
+    fn category(&self, ctx: &Context<Self>, category: &Me3WeaponCategory) -> Html {
+        let Props { plot, variables, .. } = &ctx.props();
+        let (plot, variables) = (plot.borrow(), variables.borrow());
+
+        let weapons = category.weapons.iter().map(|weapon| {
+            let class_name = weapon.class_name.clone();
+            let state = if weapon.is_unlocked(&plot) {
+                format!("level {}", Me3WeaponDb::upgrade_level(&variables, &weapon.class_name))
+            } else {
+                "locked".to_owned()
+            };
+            html! {
+                <div class="flex items-center gap-1">
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        onclick={ctx.link().callback(move |_| Msg::Select(class_name.clone()))}
+                    >
+                        { &weapon.name }
+                    </a>
+                    { format!("({})", state) }
+                </div>
+            }
+        });
+
+        html! {
+            <Table title={category.name.clone()} opened=false>
+                { for weapons }
+            </Table>
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::Table,
+    save_data::{
+        shared::{
+            crew_db::{CrewDb, CrewFlags},
+            plot::BitVec,
+        },
+        RcRef,
+    },
+    services::database::Databases,
+};
+
+#[derive(Clone, PartialEq)]
+pub enum CrewType {
+    Me1(RcRef<BitVec>),
+    /// Plot and imported ME1 plot.
+    Me2(RcRef<BitVec>, RcRef<BitVec>),
+    Me3(RcRef<BitVec>),
+}
+
+impl CrewType {
+    fn crew_db(&self, databases: Databases) -> Option<Rc<CrewDb>> {
+        match self {
+            CrewType::Me1(_) => databases.get_me1_crew(),
+            CrewType::Me2(..) => databases.get_me2_crew(),
+            CrewType::Me3(_) => databases.get_me3_crew(),
+        }
+    }
+
+    /// The plot holding the flags, `None` for an imported ME1 plot without import.
+    fn booleans(&self, flags: &CrewFlags) -> Option<&RcRef<BitVec>> {
+        match self {
+            CrewType::Me2(_, me1_booleans) if flags.me1_plot => {
+                (!me1_booleans.borrow().is_empty()).then(|| me1_booleans)
+            }
+            CrewType::Me1(booleans) | CrewType::Me2(booleans, _) | CrewType::Me3(booleans) => {
+                Some(booleans)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum CrewFix {
+    Revive,
+    Kill,
+    MakeLoyal,
+}
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    Fix(usize, CrewFix),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub crew: CrewType,
+}
+
+pub struct Crew {
+    _db_handle: ContextHandle<Databases>,
+    crew_db: Option<Rc<CrewDb>>,
+}
+
+impl Component for Crew {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Crew { _db_handle, crew_db: ctx.props().crew.crew_db(databases) }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let crew = &ctx.props().crew;
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.crew_db = crew.crew_db(*dbs);
+            }
+            Msg::Fix(member_idx, fix) => {
+                if let Some(member) = self.crew_db.as_ref().and_then(|db| db.crew().get(member_idx))
+                {
+                    // Every game the save carries gets the fix
+                    for flags in &member.games {
+                        if let Some(booleans) = crew.booleans(flags) {
+                            let mut booleans = booleans.borrow_mut();
+                            match fix {
+                                CrewFix::Revive => flags.revive(&mut booleans),
+                                CrewFix::Kill => flags.kill(&mut booleans),
+                                CrewFix::MakeLoyal => flags.make_loyal(&mut booleans),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let crew_db = match self.crew_db {
+            Some(ref crew_db) => crew_db,
+            None => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        let crew = &ctx.props().crew;
+        let link = ctx.link();
+
+        let members = crew_db.crew().iter().enumerate().map(|(member_idx, member)| {
+            let games: Vec<_> = member
+                .games
+                .iter()
+                .map(|flags| {
+                    let status = match crew.booleans(flags) {
+                        Some(booleans) => flags.status(&booleans.borrow()).name(),
+                        None => "Not imported",
+                    };
+                    format!("{}: {}", flags.game, status)
+                })
+                .collect();
+
+            let fix = |label: &'static str, fix: CrewFix| {
+                html! {
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        onclick={link.callback(move |_| Msg::Fix(member_idx, fix))}
+                    >
+                        { label }
+                    </a>
+                }
+            };
+            let kill = member.can_be_killed().then(|| fix("kill", CrewFix::Kill));
+            let make_loyal = member.can_be_loyal().then(|| fix("make loyal", CrewFix::MakeLoyal));
+
+            html! {
+                <div class="flex flex-wrap items-center gap-1 cursor-default">
+                    { format!("{} - {}", member.name, games.join(", ")) }
+                    { fix("revive", CrewFix::Revive) }
+                    { for kill }
+                    { for make_loyal }
+                </div>
+            }
+        });
+
+        html! {
+            <Table title="Crew status" helper=
+                "Status of each character in this game and in the imports the save carries.\n\
+                A fix updates the flags of every game, reviving clears all the deaths."
+            >
+                { for members }
+            </Table>
+        }
+    }
+}
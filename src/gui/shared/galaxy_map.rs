@@ -0,0 +1,321 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{CheckBox, Table},
+    save_data::{
+        mass_effect_2::galaxy_map::{GalaxyMap as Me2GalaxyMap, Planet as Me2Planet},
+        mass_effect_3::galaxy_map::{GalaxyMap as Me3GalaxyMap, Planet as Me3Planet, System},
+        shared::galaxy_db::{cluster_id, system_id, GalaxyDb},
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+#[derive(Clone)]
+pub enum GalaxyMapType {
+    Me2(RcRef<Me2GalaxyMap>),
+    Me3(RcRef<Me3GalaxyMap>),
+}
+
+impl PartialEq for GalaxyMapType {
+    fn eq(&self, other: &GalaxyMapType) -> bool {
+        match (self, other) {
+            (GalaxyMapType::Me2(me2), GalaxyMapType::Me2(other)) => me2 == other,
+            (GalaxyMapType::Me3(me3), GalaxyMapType::Me3(other)) => me3 == other,
+            _ => false,
+        }
+    }
+}
+
+impl GalaxyMapType {
+    fn galaxy_db(&self, databases: Databases) -> Option<Rc<GalaxyDb>> {
+        match self {
+            GalaxyMapType::Me2(_) => databases.get_me2_galaxy(),
+            GalaxyMapType::Me3(_) => databases.get_me3_galaxy(),
+        }
+    }
+}
+
+// What both games store for a planet
+struct PlanetState {
+    id: i32,
+    visited: RcCell<bool>,
+    scanned: Option<RcCell<bool>>,
+    probes: usize,
+}
+
+impl PlanetState {
+    fn me2(planet: &RcRef<Me2Planet>) -> Self {
+        let planet = planet.borrow();
+        let probes = planet.probes().len();
+        PlanetState {
+            id: planet.id(),
+            visited: RcCell::clone(&planet.visited),
+            scanned: None,
+            probes,
+        }
+    }
+
+    fn me3(planet: &RcRef<Me3Planet>) -> Self {
+        let planet = planet.borrow();
+        let probes = planet.probes().len();
+        PlanetState {
+            id: planet.id(),
+            visited: RcCell::clone(&planet.visited),
+            scanned: Some(RcCell::clone(&planet.show_as_scanned)),
+            probes,
+        }
+    }
+
+    fn is_scanned(&self) -> bool {
+        self.scanned.as_ref().unwrap_or(&self.visited).get()
+    }
+}
+
+// Planets by system, with the system itself if the save has it
+type Systems = BTreeMap<i32, (Option<RcRef<System>>, Vec<PlanetState>)>;
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    MarkAll,
+    ClearProbes(Option<i32>),
+    ResetReaperAlerts,
+    ResetReaperAlert(RcRef<System>),
+    OnlyUnscanned(bool),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub galaxy_map: GalaxyMapType,
+}
+
+pub struct GalaxyMap {
+    _db_handle: ContextHandle<Databases>,
+    galaxy_db: Option<Rc<GalaxyDb>>,
+    only_unscanned: bool,
+}
+
+impl Component for GalaxyMap {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        GalaxyMap {
+            _db_handle,
+            galaxy_db: ctx.props().galaxy_map.galaxy_db(databases),
+            only_unscanned: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.galaxy_db = ctx.props().galaxy_map.galaxy_db(*dbs);
+                true
+            }
+            Msg::MarkAll => {
+                if let Some(ref galaxy_db) = self.galaxy_db {
+                    match ctx.props().galaxy_map {
+                        GalaxyMapType::Me2(ref me2) => me2.borrow_mut().mark_all_visited(galaxy_db),
+                        GalaxyMapType::Me3(ref me3) => me3.borrow_mut().mark_all_scanned(galaxy_db),
+                    }
+                }
+                true
+            }
+            Msg::ClearProbes(id) => {
+                match ctx.props().galaxy_map {
+                    GalaxyMapType::Me2(ref me2) => me2.borrow().clear_probes(id),
+                    GalaxyMapType::Me3(ref me3) => me3.borrow().clear_probes(id),
+                }
+                true
+            }
+            Msg::ResetReaperAlerts => {
+                if let GalaxyMapType::Me3(ref me3) = ctx.props().galaxy_map {
+                    me3.borrow().reset_reaper_alerts();
+                }
+                true
+            }
+            Msg::ResetReaperAlert(system) => {
+                system.borrow().reset_reaper_alert();
+                true
+            }
+            Msg::OnlyUnscanned(only_unscanned) => {
+                self.only_unscanned = only_unscanned;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let galaxy_db = match self.galaxy_db {
+            Some(ref galaxy_db) => galaxy_db,
+            None => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        let (planets, systems) = match ctx.props().galaxy_map {
+            GalaxyMapType::Me2(ref me2) => {
+                let planets =
+                    me2.borrow().planets().iter().map(PlanetState::me2).collect::<Vec<_>>();
+                (planets, Vec::new())
+            }
+            GalaxyMapType::Me3(ref me3) => {
+                let me3 = me3.borrow();
+                let planets = me3.planets().iter().map(PlanetState::me3).collect();
+                let systems = me3.systems().iter().map(RcRef::clone).collect();
+                (planets, systems)
+            }
+        };
+
+        // Cluster > system > planets, systems without planets are kept for their reaper alert
+        let mut clusters: BTreeMap<i32, Systems> = BTreeMap::new();
+        for system in systems {
+            let id = system.borrow().id();
+            clusters.entry(cluster_id(id)).or_default().entry(system_id(id)).or_default().0 =
+                Some(system);
+        }
+        for planet in planets {
+            if self.only_unscanned && planet.is_scanned() {
+                continue;
+            }
+            let id = planet.id;
+            clusters
+                .entry(cluster_id(id))
+                .or_default()
+                .entry(system_id(id))
+                .or_default()
+                .1
+                .push(planet);
+        }
+
+        let clusters = clusters
+            .into_iter()
+            .map(|(cluster, mut systems)| {
+                systems.retain(|_, (_, planets)| !self.only_unscanned || !planets.is_empty());
+                (cluster, systems)
+            })
+            .filter(|(_, systems)| !systems.is_empty())
+            .map(|(cluster, systems)| {
+                let systems = systems.into_iter().map(|(id, (system, mut planets))| {
+                    planets.sort_by_key(|planet| planet.id);
+                    self.system(ctx, galaxy_db, id, system, planets)
+                });
+                html! {
+                    <Table title={galaxy_db.cluster_name(cluster)} opened=false>
+                        { for systems }
+                    </Table>
+                }
+            });
+
+        let is_me3 = matches!(ctx.props().galaxy_map, GalaxyMapType::Me3(_));
+        let mark_all =
+            if is_me3 { "Mark everything visited and scanned" } else { "Mark everything visited" };
+        let reset_reaper_alerts = is_me3.then(|| {
+            html! {
+                <button class="button" onclick={ctx.link().callback(|_| Msg::ResetReaperAlerts)}>
+                    { "Reset reaper alerts" }
+                </button>
+            }
+        });
+
+        html! {
+            <div class="flex flex-col gap-1">
+                <Table title="Galaxy map" helper=
+                    "Only the planets and systems stored in the save are listed.\n\
+                    Planets of the database missing from the save are added when marking everything."
+                >
+                    <div class="flex flex-wrap items-center gap-1">
+                        <button class="button" onclick={ctx.link().callback(|_| Msg::MarkAll)}>
+                            { mark_all }
+                        </button>
+                        <button class="button" onclick={ctx.link().callback(|_| Msg::ClearProbes(None))}>
+                            { "Clear every probe marker" }
+                        </button>
+                        { for reset_reaper_alerts }
+                    </div>
+                    <CheckBox
+                        label="Only unscanned planets"
+                        value={RcCell::new(self.only_unscanned)}
+                        onchange={ctx.link().callback(Msg::OnlyUnscanned)}
+                    />
+                </Table>
+                { for clusters }
+            </div>
+        }
+    }
+}
+
+impl GalaxyMap {
+    fn system(
+        &self, ctx: &Context<Self>, galaxy_db: &GalaxyDb, id: i32, system: Option<RcRef<System>>,
+        planets: Vec<PlanetState>,
+    ) -> Html {
+        let reaper_alert = system.map(|system| {
+            let (level, detected) = {
+                let system = system.borrow();
+                (system.reaper_alert_level(), RcCell::clone(&system.reaper_detected))
+            };
+            html! {
+                <div class="flex items-center gap-1">
+                    { format!("Reaper alert level: {:.2}", level) }
+                    <CheckBox label="Reapers detected" value={detected} />
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        onclick={ctx.link().callback(move |_| Msg::ResetReaperAlert(RcRef::clone(&system)))}
+                    >
+                        { "reset" }
+                    </a>
+                </div>
+            }
+        });
+        let planets = planets.into_iter().map(|planet| self.planet(ctx, galaxy_db, planet));
+
+        html! {
+            <Table title={galaxy_db.system_name(id)}>
+                { for reaper_alert }
+                { for planets }
+            </Table>
+        }
+    }
+
+    fn planet(&self, ctx: &Context<Self>, galaxy_db: &GalaxyDb, planet: PlanetState) -> Html {
+        let PlanetState { id, visited, scanned, probes } = planet;
+        let anomaly = galaxy_db.planet(id).map_or(false, |planet| planet.anomaly);
+        let name = galaxy_db.planet_name(id);
+        let name = if anomaly { format!("{} (anomaly)", name) } else { name };
+
+        let scanned = scanned.map(|scanned| {
+            html! { <CheckBox label="Scanned" value={scanned} /> }
+        });
+        let probes = (probes > 0).then(|| {
+            html! {
+                <>
+                    { format!("{} probe markers", probes) }
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        onclick={ctx.link().callback(move |_| Msg::ClearProbes(Some(id)))}
+                    >
+                        { "clear" }
+                    </a>
+                </>
+            }
+        });
+
+        html! {
+            <div class="flex items-center gap-2">
+                <div class="w-64">{ name }</div>
+                <CheckBox label="Visited" value={visited} />
+                { for scanned }
+                { for probes }
+            </div>
+        }
+    }
+}
@@ -3,14 +3,24 @@ use std::cell::{Ref, RefMut};
 use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
-    gui::{components::Table, raw_ui::RawUiChildren},
-    save_data::{shared::appearance::HeadMorph as DataHeadMorph, RcRef},
+    gui::{
+        components::{CheckBox, ColorPicker, Table},
+        raw_ui::RawUiChildren,
+    },
+    save_data::{
+        shared::appearance::{HeadMorph as DataHeadMorph, HeadMorphPart},
+        RcCell, RcRef,
+    },
     services::save_handler::{Action, SaveHandler},
 };
 
 pub enum Msg {
     Import,
     HeadMorphImported(DataHeadMorph),
+    ApplyMerge,
+    ReplaceAll,
+    CancelMerge,
+    CopyToSimilar(String),
     Export,
     RemoveHeadMorph,
 }
@@ -30,9 +40,16 @@ impl Props {
     }
 }
 
+struct Merge {
+    imported: DataHeadMorph,
+    parts: Vec<(HeadMorphPart, RcCell<bool>)>,
+    error: Option<String>,
+}
+
 pub struct HeadMorph {
     _db_handle: ContextHandle<SaveHandler>,
     save_handler: SaveHandler,
+    merge: Option<Merge>,
 }
 
 impl Component for HeadMorph {
@@ -43,7 +60,7 @@ impl Component for HeadMorph {
         let (save_handler, _db_handle) =
             ctx.link().context::<SaveHandler>(Callback::noop()).expect("no save handler provider");
 
-        HeadMorph { _db_handle, save_handler }
+        HeadMorph { _db_handle, save_handler, merge: None }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -54,7 +71,52 @@ impl Component for HeadMorph {
                 false
             }
             Msg::HeadMorphImported(head_morph) => {
-                *ctx.props().head_morph_mut() = Some(head_morph.into());
+                if ctx.props().head_morph().is_some() {
+                    let parts =
+                        HeadMorphPart::ALL.iter().map(|part| (*part, RcCell::new(true))).collect();
+                    self.merge = Some(Merge { imported: head_morph, parts, error: None });
+                } else {
+                    *ctx.props().head_morph_mut() = Some(head_morph.into());
+                }
+                true
+            }
+            Msg::ApplyMerge => {
+                if let Some(mut merge) = self.merge.take() {
+                    let parts: Vec<_> = merge
+                        .parts
+                        .iter()
+                        .filter(|(_, selected)| selected.get())
+                        .map(|(part, _)| *part)
+                        .collect();
+
+                    let mut head_morph = ctx.props().head_morph_mut();
+                    if let Some(ref mut current) = *head_morph {
+                        let merged = current.borrow().merge(merge.imported.clone(), &parts);
+                        match merged {
+                            Ok(merged) => *current = merged.into(),
+                            Err(err) => {
+                                merge.error = Some(err.to_string());
+                                self.merge = Some(merge);
+                            }
+                        }
+                    }
+                }
+                true
+            }
+            Msg::ReplaceAll => {
+                if let Some(merge) = self.merge.take() {
+                    *ctx.props().head_morph_mut() = Some(merge.imported.into());
+                }
+                true
+            }
+            Msg::CancelMerge => {
+                self.merge = None;
+                true
+            }
+            Msg::CopyToSimilar(name) => {
+                if let Some(ref head_morph) = *ctx.props().head_morph() {
+                    head_morph.borrow_mut().copy_vector_parameter_to_similar(&name);
+                }
                 true
             }
             Msg::Export => {
@@ -85,6 +147,12 @@ impl Component for HeadMorph {
                 </>
             }
         });
+        let merge = self
+            .merge
+            .as_ref()
+            .zip(head_morph.as_ref())
+            .map(|(merge, current)| Self::merge(ctx, merge, &current.borrow()));
+        let colors = head_morph.as_ref().map(|head_morph| Self::colors(ctx, &head_morph.borrow()));
         let raw = head_morph.as_ref().map(|head_morph| {
             html! {
                 <Table title="Raw">
@@ -101,8 +169,81 @@ impl Component for HeadMorph {
                     { for export_remove }
                 </div>
                 <hr class="border-t border-default-border" />
+                { for merge }
+                { for colors }
                 { for raw }
             </div>
         }
     }
 }
+
+impl HeadMorph {
+    fn colors(ctx: &Context<Self>, head_morph: &DataHeadMorph) -> Html {
+        let vector_parameters = head_morph.vector_parameters();
+        let colors = vector_parameters.iter().map(|(name, color)| {
+            let similar = head_morph.similar_vector_parameters(name).len();
+            let copy = (similar > 0).then(|| {
+                let name = name.clone();
+                html! {
+                    <button class="button"
+                        onclick={ctx.link().callback(move |_| Msg::CopyToSimilar(name.clone()))}
+                    >
+                        { format!("Copy to similar ({})", similar) }
+                    </button>
+                }
+            });
+            html! {
+                <div class="flex items-start gap-2">
+                    <ColorPicker label={name.clone()} color={RcRef::clone(color)} />
+                    { for copy }
+                </div>
+            }
+        });
+        html! {
+            <Table title="Colors">
+                { for colors }
+            </Table>
+        }
+    }
+
+    fn merge(ctx: &Context<Self>, merge: &Merge, current: &DataHeadMorph) -> Html {
+        let parts = merge.parts.iter().map(|(part, selected)| {
+            let diff = current.diff(&merge.imported, *part);
+            let diff = if diff.is_empty() {
+                "identical".to_owned()
+            } else {
+                format!("+{} / -{} / ~{}", diff.added, diff.removed, diff.changed)
+            };
+            html! {
+                <div class="flex items-center gap-2">
+                    <div class="w-64">
+                        <CheckBox label={part.name().to_owned()} value={RcCell::clone(selected)} />
+                    </div>
+                    { diff }
+                </div>
+            }
+        });
+        let error = merge.error.as_ref().map(|error| {
+            html! { <p>{ format!("Error: {}", error) }</p> }
+        });
+        html! {
+            <Table title="Merge imported head morph">
+                { for parts }
+                { for error }
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::ApplyMerge)}>
+                        {"Apply selected"}
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::ReplaceAll)}>
+                        {"Replace all"}
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::CancelMerge)}>
+                        {"Cancel"}
+                    </button>
+                </div>
+            </Table>
+        }
+    }
+}
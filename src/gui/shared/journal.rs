@@ -0,0 +1,508 @@
+use std::rc::Rc;
+
+use gloo::storage::{LocalStorage, Storage};
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{CheckBox, InputNumber, InputText, NumberType, Table},
+    save_data::{
+        mass_effect_3::plot::{Codex as Me3Codex, Journal as Me3Journal},
+        shared::{
+            journal_db::{JournalDb, JournalQuest},
+            plot::{Codex, Journal as SharedJournal, PlotCodex},
+        },
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+type CodexType = (RcRef<Vec<RcCell<i32>>>, RcRef<Vec<RcRef<PlotCodex>>>);
+
+#[derive(Clone)]
+pub enum JournalType {
+    Me1Le(RcRef<SharedJournal>, RcRef<Codex>),
+    Me2(RcRef<SharedJournal>, RcRef<Codex>),
+    Me3(RcRef<Me3Journal>, RcRef<Me3Codex>),
+}
+
+impl PartialEq for JournalType {
+    fn eq(&self, other: &JournalType) -> bool {
+        match (self, other) {
+            (JournalType::Me1Le(journal, codex), JournalType::Me1Le(other, other_codex))
+            | (JournalType::Me2(journal, codex), JournalType::Me2(other, other_codex)) => {
+                journal == other && codex == other_codex
+            }
+            (JournalType::Me3(journal, codex), JournalType::Me3(other, other_codex)) => {
+                journal == other && codex == other_codex
+            }
+            _ => false,
+        }
+    }
+}
+
+impl JournalType {
+    fn journal_db(&self, databases: Databases) -> Option<Rc<JournalDb>> {
+        match self {
+            JournalType::Me1Le(..) => databases.get_me1_journal(),
+            JournalType::Me2(..) => databases.get_me2_journal(),
+            JournalType::Me3(..) => databases.get_me3_journal(),
+        }
+    }
+
+    fn names_key(&self) -> &'static str {
+        match self {
+            JournalType::Me1Le(..) => "me1_le_quest_names",
+            JournalType::Me2(..) => "me2_quest_names",
+            JournalType::Me3(..) => "me3_quest_names",
+        }
+    }
+
+    fn codex(&self) -> CodexType {
+        match self {
+            JournalType::Me1Le(_, codex) | JournalType::Me2(_, codex) => {
+                let codex = codex.borrow();
+                (RcRef::clone(&codex.codex_ids), RcRef::clone(&codex.codex_entries))
+            }
+            JournalType::Me3(_, codex) => {
+                let codex = codex.borrow();
+                (RcRef::clone(&codex.codex_ids), RcRef::clone(&codex.codex_entries))
+            }
+        }
+    }
+
+    fn quests(&self) -> Vec<QuestState> {
+        match self {
+            JournalType::Me1Le(journal, _) | JournalType::Me2(journal, _) => {
+                let journal = journal.borrow();
+                let (quest_ids, quests) = (journal.quest_ids(), journal.quest_progress());
+                let quests = quest_ids
+                    .iter()
+                    .zip(quests.iter())
+                    .map(|(id, quest)| {
+                        let quest = quest.borrow();
+                        let history = quest.history().iter().map(RcCell::get).collect();
+                        QuestState {
+                            id: id.get(),
+                            counter: quest.quest_counter(),
+                            updated: RcCell::clone(&quest.quest_updated),
+                            history,
+                            active_goal: None,
+                        }
+                    })
+                    .collect();
+                quests
+            }
+            JournalType::Me3(journal, _) => {
+                let journal = journal.borrow();
+                let (quest_ids, quests) = (journal.quest_ids(), journal.quest_progress());
+                let quests = quest_ids
+                    .iter()
+                    .zip(quests.iter())
+                    .map(|(id, quest)| {
+                        let quest = quest.borrow();
+                        let history = quest.history().iter().map(RcCell::get).collect();
+                        QuestState {
+                            id: id.get(),
+                            counter: quest.quest_counter(),
+                            updated: RcCell::clone(&quest.quest_updated),
+                            history,
+                            active_goal: Some(RcCell::clone(&quest.active_goal)),
+                        }
+                    })
+                    .collect();
+                quests
+            }
+        }
+    }
+}
+
+// What the games store for a quest
+struct QuestState {
+    id: i32,
+    counter: i32,
+    updated: RcCell<bool>,
+    history: Vec<i32>,
+    active_goal: Option<RcCell<i32>>,
+}
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    Changed,
+    AddGoal(i32),
+    RemoveGoal(i32, i32),
+    Complete(i32),
+    Reset(i32),
+    UnlockCodex,
+    Name(i32),
+    SaveName,
+    SetEndGoal(i32, i32),
+    ForgetNames,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub journal: JournalType,
+}
+
+pub struct Journal {
+    _db_handle: ContextHandle<Databases>,
+    journal_db: Option<Rc<JournalDb>>,
+    new_goal: RcCell<i32>,
+    user_quests: Vec<JournalQuest>,
+    naming: Option<i32>,
+    new_name: RcRef<String>,
+}
+
+impl Component for Journal {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Journal {
+            _db_handle,
+            journal_db: ctx.props().journal.journal_db(databases),
+            new_goal: Default::default(),
+            user_quests: LocalStorage::get(ctx.props().journal.names_key()).unwrap_or_default(),
+            naming: None,
+            new_name: Default::default(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let journal = &ctx.props().journal;
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.journal_db = journal.journal_db(*dbs);
+                true
+            }
+            Msg::Changed => true,
+            Msg::AddGoal(id) => {
+                Self::add_goal(journal, id, self.new_goal.get());
+                true
+            }
+            Msg::RemoveGoal(id, goal) => {
+                match journal {
+                    JournalType::Me1Le(journal, _) | JournalType::Me2(journal, _) => {
+                        journal.borrow_mut().remove_goal(id, goal)
+                    }
+                    JournalType::Me3(journal, _) => journal.borrow_mut().remove_goal(id, goal),
+                }
+                true
+            }
+            Msg::Complete(id) => {
+                let end_goal = self
+                    .journal_db
+                    .as_ref()
+                    .and_then(|journal_db| self.known_quest(journal_db, id))
+                    .and_then(|quest| quest.end_goals.first().copied());
+                if let Some(end_goal) = end_goal {
+                    Self::add_goal(journal, id, end_goal);
+                }
+                true
+            }
+            Msg::Reset(id) => {
+                match journal {
+                    JournalType::Me1Le(journal, _) | JournalType::Me2(journal, _) => {
+                        journal.borrow_mut().reset_quest(id)
+                    }
+                    JournalType::Me3(journal, _) => journal.borrow_mut().reset_quest(id),
+                }
+                true
+            }
+            Msg::UnlockCodex => {
+                if let Some(ref journal_db) = self.journal_db {
+                    let (codex_ids, codex_entries) = journal.codex();
+                    journal_db
+                        .unlock_codex(&mut codex_ids.borrow_mut(), &mut codex_entries.borrow_mut());
+                }
+                true
+            }
+            Msg::Name(id) => {
+                *self.new_name.borrow_mut() = self
+                    .journal_db
+                    .as_ref()
+                    .and_then(|journal_db| self.known_quest(journal_db, id))
+                    .map(|quest| quest.name)
+                    .unwrap_or_default();
+                self.naming = Some(id);
+                true
+            }
+            Msg::SaveName => {
+                if let Some(id) = self.naming.take() {
+                    let name = self.new_name.borrow().trim().to_owned();
+                    self.user_quest_mut(id).name = name;
+                    self.save_user_quests(journal);
+                }
+                true
+            }
+            Msg::SetEndGoal(id, goal) => {
+                // The override starts from the ending goals of the database
+                let known_end_goals = self
+                    .journal_db
+                    .as_ref()
+                    .and_then(|journal_db| self.known_quest(journal_db, id))
+                    .map(|quest| quest.end_goals)
+                    .unwrap_or_default();
+                let end_goals = &mut self.user_quest_mut(id).end_goals;
+                if end_goals.is_empty() {
+                    *end_goals = known_end_goals;
+                }
+                match end_goals.iter().position(|&end_goal| end_goal == goal) {
+                    Some(idx) => {
+                        end_goals.remove(idx);
+                    }
+                    None => end_goals.push(goal),
+                }
+                self.save_user_quests(journal);
+                true
+            }
+            Msg::ForgetNames => {
+                self.user_quests.clear();
+                LocalStorage::delete(journal.names_key());
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let journal_db = match self.journal_db {
+            Some(ref journal_db) => journal_db,
+            None => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        html! {
+            <div class="flex divide-solid divide-x divide-default-border">
+                <div class="flex-1 pr-1 flex flex-col gap-1">
+                    { self.quests(ctx, journal_db) }
+                </div>
+                <div class="flex-1 pl-1 flex flex-col gap-1">
+                    { Self::codex(ctx, journal_db) }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl Journal {
+    fn add_goal(journal: &JournalType, id: i32, goal: i32) {
+        match journal {
+            JournalType::Me1Le(journal, _) | JournalType::Me2(journal, _) => {
+                journal.borrow_mut().add_goal(id, goal)
+            }
+            JournalType::Me3(journal, _) => journal.borrow_mut().add_goal(id, goal),
+        }
+    }
+
+    fn user_quest_mut(&mut self, id: i32) -> &mut JournalQuest {
+        let idx = match self.user_quests.iter().position(|quest| quest.id == id) {
+            Some(idx) => idx,
+            None => {
+                self.user_quests.push(JournalQuest {
+                    id,
+                    name: String::new(),
+                    end_goals: Vec::new(),
+                });
+                self.user_quests.len() - 1
+            }
+        };
+        &mut self.user_quests[idx]
+    }
+
+    fn save_user_quests(&mut self, journal: &JournalType) {
+        // Nothing left to remember for a quest without a name or ending goals
+        self.user_quests.retain(|quest| !quest.name.is_empty() || !quest.end_goals.is_empty());
+        let _ = LocalStorage::set(journal.names_key(), &self.user_quests);
+    }
+
+    // The database, what the user told us overrides it
+    fn known_quest(&self, journal_db: &JournalDb, id: i32) -> Option<JournalQuest> {
+        journal_db.quest_with(id, &self.user_quests)
+    }
+
+    fn quest_name(&self, journal_db: &JournalDb, id: i32) -> String {
+        match self.known_quest(journal_db, id) {
+            Some(quest) if !quest.name.is_empty() => quest.name.clone(),
+            _ => format!("Quest {}", id),
+        }
+    }
+
+    fn quests(&self, ctx: &Context<Self>, journal_db: &JournalDb) -> Html {
+        // Most recent first, like the journal of the game
+        let mut quests = ctx.props().journal.quests();
+        quests.sort_by_key(|quest| -quest.counter);
+
+        let (mut updated, mut active, mut completed) = (Vec::new(), Vec::new(), Vec::new());
+        for quest in quests {
+            let is_completed = self
+                .known_quest(journal_db, quest.id)
+                .and_then(|known| known.is_completed(&quest.history));
+            let html = self.quest(ctx, journal_db, &quest, is_completed);
+            if quest.updated.get() {
+                updated.push(html);
+            } else if is_completed == Some(true) {
+                completed.push(html);
+            } else {
+                active.push(html);
+            }
+        }
+
+        let naming = self.naming.map(|id| {
+            html! {
+                <div class="flex items-center gap-1">
+                    <InputText label={format!("Name of quest {}", id)} value={RcRef::clone(&self.new_name)} />
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::SaveName)}>
+                        { "Save name" }
+                    </button>
+                </div>
+            }
+        });
+
+        html! {
+            <>
+                <div class="flex items-center gap-1">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::ForgetNames)}>
+                        { "Forget given quest names" }
+                    </button>
+                </div>
+                { for naming }
+                <Table title="Updated quests" helper=
+                    "Quests flagged as updated are highlighted in the journal and on the map.\n\
+                    Unflag or reset a quest if its marker is stuck."
+                >
+                    { for updated }
+                </Table>
+                <Table title="Active quests" helper=
+                    "A quest goes through goals, its history holds the goals already reached.\n\
+                    Quest names and ending goals are shown when they are known, the others are shown by their id.\n\
+                    Name a quest or mark the goals ending it, your browser remembers them over the database for every save of the game."
+                >
+                    { for active }
+                </Table>
+                <Table title="Completed quests" opened=false>
+                    { for completed }
+                </Table>
+            </>
+        }
+    }
+
+    fn quest(
+        &self, ctx: &Context<Self>, journal_db: &JournalDb, quest: &QuestState,
+        is_completed: Option<bool>,
+    ) -> Html {
+        let id = quest.id;
+        let link = ctx.link();
+
+        let end_goals =
+            self.known_quest(journal_db, id).map(|quest| quest.end_goals).unwrap_or_default();
+        let history = quest.history.iter().map(|&goal| {
+            let end_goal = {
+                let label = if end_goals.contains(&goal) { "ends the quest" } else { "mark as ending" };
+                html! {
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        onclick={link.callback(move |_| Msg::SetEndGoal(id, goal))}
+                    >
+                        { label }
+                    </a>
+                }
+            };
+            html! {
+                <div class="flex items-center gap-1">
+                    { format!("Goal {}", goal) }
+                    <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                        onclick={link.callback(move |_| Msg::RemoveGoal(id, goal))}
+                    >
+                        { "remove" }
+                    </a>
+                    { end_goal }
+                </div>
+            }
+        });
+        let active_goal = quest.active_goal.as_ref().map(|active_goal| {
+            html! {
+                <InputNumber
+                    label="Active goal"
+                    value={NumberType::Int(RcCell::clone(active_goal))}
+                    onchange={link.callback(|_| Msg::Changed)}
+                />
+            }
+        });
+        let complete = (is_completed == Some(false)).then(|| {
+            html! {
+                <button class="button" onclick={link.callback(move |_| Msg::Complete(id))}>
+                    { "Complete" }
+                </button>
+            }
+        });
+
+        html! {
+            <Table title={self.quest_name(journal_db, id)} opened=false>
+                <CheckBox
+                    label="Updated"
+                    value={RcCell::clone(&quest.updated)}
+                    onchange={link.callback(|_| Msg::Changed)}
+                />
+                { for active_goal }
+                { for history }
+                <div class="flex items-center gap-1">
+                    <InputNumber label="" value={NumberType::Int(RcCell::clone(&self.new_goal))} />
+                    <button class="button" onclick={link.callback(move |_| Msg::AddGoal(id))}>
+                        { "Add goal" }
+                    </button>
+                    { for complete }
+                    <button class="button" onclick={link.callback(move |_| Msg::Reset(id))}>
+                        { "Reset" }
+                    </button>
+                    <button class="button" onclick={link.callback(move |_| Msg::Name(id))}>
+                        { "Name" }
+                    </button>
+                </div>
+            </Table>
+        }
+    }
+
+    fn codex(ctx: &Context<Self>, journal_db: &JournalDb) -> Html {
+        let (codex_ids, codex_entries) = ctx.props().journal.codex();
+        let (codex_ids, codex_entries) = (codex_ids.borrow(), codex_entries.borrow());
+
+        let entries = codex_ids.iter().zip(codex_entries.iter()).map(|(id, entry)| {
+            let entry = entry.borrow();
+            let pages = entry.pages();
+            let pages = pages.iter().map(|page| {
+                let page = page.borrow();
+                html! {
+                    <CheckBox
+                        label={journal_db.page_name(page.page())}
+                        value={RcCell::clone(&page.is_new)}
+                        onchange={ctx.link().callback(|_| Msg::Changed)}
+                    />
+                }
+            });
+            html! {
+                <Table title={journal_db.section_name(id.get())} opened=false>
+                    { for pages }
+                </Table>
+            }
+        });
+
+        html! {
+            <Table title="Codex" helper=
+                "Pages are checked while they are flagged as new in the game.\n\
+                Unlocking all pages adds the pages known for each codex entry."
+            >
+                <button class="button" onclick={ctx.link().callback(|_| Msg::UnlockCodex)}>
+                    { "Unlock all codex pages" }
+                </button>
+                { for entries }
+            </Table>
+        }
+    }
+}
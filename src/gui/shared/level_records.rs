@@ -0,0 +1,286 @@
+use std::collections::BTreeMap;
+
+use gloo::storage::{LocalStorage, Storage};
+use yew::prelude::*;
+
+use crate::{
+    gui::components::{CheckBox, InputText, Table},
+    save_data::{
+        mass_effect_3::Placeable,
+        shared::{Door, Kismet},
+        Guid, RcCell, RcRef,
+    },
+};
+
+/// The records both games keep about the objects of the levels, ME3 adds placeables.
+#[derive(Clone, PartialEq)]
+pub struct RecordLists {
+    pub kismet_records: RcRef<Vec<RcRef<Kismet>>>,
+    pub doors: RcRef<Vec<RcRef<Door>>>,
+    pub placeables: Option<RcRef<Vec<RcRef<Placeable>>>>,
+    pub pawns: RcRef<Vec<RcRef<Guid>>>,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum LevelRecordsType {
+    Me2(RecordLists),
+    Me3(RecordLists),
+}
+
+impl LevelRecordsType {
+    fn names_key(&self) -> &'static str {
+        match self {
+            LevelRecordsType::Me2(_) => "me2_object_names",
+            LevelRecordsType::Me3(_) => "me3_object_names",
+        }
+    }
+
+    fn records(&self) -> &RecordLists {
+        match self {
+            LevelRecordsType::Me2(records) | LevelRecordsType::Me3(records) => records,
+        }
+    }
+}
+
+pub enum Msg {
+    Changed,
+    ResetKismet(RcRef<Kismet>),
+    ResetDoor(RcRef<Door>),
+    Reenable(RcRef<Placeable>),
+    ReenableAll,
+    Name(String),
+    SaveName,
+    ForgetNames,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub records: LevelRecordsType,
+}
+
+pub struct LevelRecords {
+    search: RcRef<String>,
+    // Hyphenated GUID => name given by the user, the saves only know the GUIDs
+    names: BTreeMap<String, String>,
+    naming: Option<String>,
+    new_name: RcRef<String>,
+}
+
+impl Component for LevelRecords {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        LevelRecords {
+            search: Default::default(),
+            names: LocalStorage::get(ctx.props().records.names_key()).unwrap_or_default(),
+            naming: None,
+            new_name: Default::default(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let records = ctx.props().records.records();
+        match msg {
+            Msg::Changed => {}
+            // The game uses the state of the level for objects without a record
+            Msg::ResetKismet(kismet) => {
+                records.kismet_records.borrow_mut().retain(|other| *other != kismet)
+            }
+            Msg::ResetDoor(door) => records.doors.borrow_mut().retain(|other| *other != door),
+            Msg::Reenable(placeable) => placeable.borrow_mut().reenable(),
+            Msg::ReenableAll => {
+                if let Some(ref placeables) = records.placeables {
+                    for placeable in placeables.borrow().iter() {
+                        placeable.borrow_mut().reenable();
+                    }
+                }
+            }
+            Msg::Name(guid) => {
+                *self.new_name.borrow_mut() = self.names.get(&guid).cloned().unwrap_or_default();
+                self.naming = Some(guid);
+            }
+            Msg::SaveName => {
+                if let Some(guid) = self.naming.take() {
+                    let name = self.new_name.borrow().trim().to_owned();
+                    if name.is_empty() {
+                        self.names.remove(&guid);
+                    } else {
+                        self.names.insert(guid, name);
+                    }
+                    let _ = LocalStorage::set(ctx.props().records.names_key(), &self.names);
+                }
+            }
+            Msg::ForgetNames => {
+                self.names.clear();
+                LocalStorage::delete(ctx.props().records.names_key());
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let records = ctx.props().records.records();
+        let link = ctx.link();
+        let search = self.search.borrow().to_lowercase();
+
+        // Records by kind, the names given by the user first
+        let row = |guid: &Guid, row: &dyn Fn(String) -> Html| {
+            let key = guid.hyphenated().to_lowercase();
+            let name = self.names.get(&key);
+            let matches = search.is_empty()
+                || key.contains(&search)
+                || name.map_or(false, |name| name.to_lowercase().contains(&search));
+            matches.then(|| {
+                let row = row(name.cloned().unwrap_or_else(|| key.clone()));
+                let html = html! {
+                    <div class="flex items-center gap-1">
+                        { row }
+                        <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                            onclick={link.callback(move |_| Msg::Name(key.clone()))}
+                        >
+                            { "name" }
+                        </a>
+                    </div>
+                };
+                (name.is_none(), html)
+            })
+        };
+        let table = |title: &str, mut rows: Vec<(bool, Html)>| {
+            rows.sort_by_key(|&(is_unnamed, _)| is_unnamed);
+            (!rows.is_empty()).then(|| {
+                html! {
+                    <Table title={title.to_owned()} opened=false>
+                        { for rows.into_iter().map(|(_, row)| row) }
+                    </Table>
+                }
+            })
+        };
+
+        let mut kismets = Vec::new();
+        for kismet in records.kismet_records.borrow().iter() {
+            let kismet_ref = RcRef::clone(kismet);
+            let kismet = kismet.borrow();
+            kismets.extend(row(&kismet.guid(), &|name| {
+                let kismet_ref = RcRef::clone(&kismet_ref);
+                html! {
+                    <div class="flex items-center gap-1">
+                        <CheckBox
+                            label={name}
+                            value={RcCell::clone(&kismet.value)}
+                            onchange={link.callback(|_| Msg::Changed)}
+                        />
+                        <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                            onclick={link.callback(move |_| Msg::ResetKismet(RcRef::clone(&kismet_ref)))}
+                        >
+                            { "reset" }
+                        </a>
+                    </div>
+                }
+            }));
+        }
+
+        let mut doors = Vec::new();
+        for door in records.doors.borrow().iter() {
+            let door_ref = RcRef::clone(door);
+            let door = door.borrow();
+            doors.extend(row(&door.guid(), &|name| {
+                let door_ref = RcRef::clone(&door_ref);
+                html! {
+                    <div class="flex items-center gap-1 cursor-default">
+                        { format!("{}: state {} (was {})", name, door.current_state(), door.old_state()) }
+                        <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                            onclick={link.callback(move |_| Msg::ResetDoor(RcRef::clone(&door_ref)))}
+                        >
+                            { "reset" }
+                        </a>
+                    </div>
+                }
+            }));
+        }
+
+        let mut placeable_rows = Vec::new();
+        if let Some(ref placeables) = records.placeables {
+            for placeable in placeables.borrow().iter() {
+                let placeable_ref = RcRef::clone(placeable);
+                let placeable = placeable.borrow();
+                placeable_rows.extend(row(&placeable.guid(), &|name| {
+                    let placeable_ref = RcRef::clone(&placeable_ref);
+                    let reenable = placeable.is_disabled().then(|| {
+                        html! {
+                            <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 cursor-pointer"
+                                onclick={link.callback(move |_| Msg::Reenable(RcRef::clone(&placeable_ref)))}
+                            >
+                                { "re-enable" }
+                            </a>
+                        }
+                    });
+                    let state = if placeable.is_disabled() { "disabled" } else { "enabled" };
+                    html! {
+                        <div class="flex items-center gap-1 cursor-default">
+                            { format!("{}: {}", name, state) }
+                            { for reenable }
+                        </div>
+                    }
+                }));
+            }
+        }
+
+        let mut pawns = Vec::new();
+        for pawn in records.pawns.borrow().iter() {
+            pawns.extend(row(&pawn.borrow(), &|name| {
+                html! {
+                    <div class="cursor-default">{ name }</div>
+                }
+            }));
+        }
+
+        let tables = [
+            table("Kismet latches", kismets),
+            table("Doors", doors),
+            table("Placeables", placeable_rows),
+            table("Pawns", pawns),
+        ];
+
+        let naming = self.naming.as_ref().map(|guid| {
+            html! {
+                <div class="flex items-center gap-1">
+                    <InputText label={format!("Name of {}", guid)} value={RcRef::clone(&self.new_name)} />
+                    <button class="button" onclick={link.callback(|_| Msg::SaveName)}>
+                        { "Save name" }
+                    </button>
+                </div>
+            }
+        });
+
+        let reenable_all = records.placeables.is_some().then(|| {
+            html! {
+                <button class="button" onclick={link.callback(|_| Msg::ReenableAll)}>
+                    { "Re-enable all placeables" }
+                </button>
+            }
+        });
+
+        html! {
+            <div class="flex flex-col gap-1">
+                <Table title="Level records" helper=
+                    "Doors, kismet latches, placeables and pawns the game saved, the save only knows them by GUID.\n\
+                    Resetting a record removes it, the object gets back the state it has in the level.\n\
+                    The names you give to records are remembered by your browser, for every save of the game."
+                >
+                    <div class="flex items-center gap-1">
+                        <InputText label="Search" value={RcRef::clone(&self.search)}
+                            oninput={link.callback(|_| Msg::Changed)}
+                        />
+                        { for reenable_all }
+                        <button class="button" onclick={link.callback(|_| Msg::ForgetNames)}>
+                            { "Forget given names" }
+                        </button>
+                    </div>
+                    { for naming }
+                </Table>
+                { for tables.into_iter().flatten() }
+            </div>
+        }
+    }
+}
@@ -1,10 +1,20 @@
 mod bonus_powers;
+mod crew;
+mod galaxy_map;
 mod head_morph;
+mod journal;
+mod level_records;
 mod link;
+mod morality;
 mod plot_category;
 mod raw_plot;
+mod romance;
+mod weapon_select;
 
-pub use self::{bonus_powers::*, head_morph::*, link::*, plot_category::*, raw_plot::*};
+pub use self::{
+    bonus_powers::*, crew::*, galaxy_map::*, head_morph::*, journal::*, level_records::*, link::*,
+    morality::*, plot_category::*, raw_plot::*, romance::*, weapon_select::*,
+};
 
 use indexmap::IndexMap;
 use yew::prelude::*;
@@ -22,6 +32,31 @@ pub enum IntPlotType {
     IndexMap(RcRef<IndexMap<i32, RcCell<i32>>>),
 }
 
+impl IntPlotType {
+    pub fn get(&self, idx: usize) -> Option<RcCell<i32>> {
+        match self {
+            IntPlotType::Vec(vec) => vec.borrow().get(idx).cloned(),
+            IntPlotType::IndexMap(index_map) => index_map.borrow().get(&(idx as i32)).cloned(),
+        }
+    }
+
+    /// Sets a plot integer, adding it if the plot is too short.
+    pub fn set(&self, idx: usize, value: i32) {
+        match self {
+            IntPlotType::Vec(vec) => {
+                let mut vec = vec.borrow_mut();
+                if idx >= vec.len() {
+                    vec.resize_with(idx + 1, Default::default);
+                }
+                vec[idx].set(value);
+            }
+            IntPlotType::IndexMap(index_map) => {
+                index_map.borrow_mut().entry(idx as i32).or_default().set(value)
+            }
+        }
+    }
+}
+
 impl PartialEq for IntPlotType {
     fn eq(&self, other: &IntPlotType) -> bool {
         match (self, other) {
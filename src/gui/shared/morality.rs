@@ -0,0 +1,288 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{InputNumber, NumberType, Table},
+    save_data::{
+        mass_effect_1_le::player::ComplexTalent,
+        shared::morality_db::{bar_percentage, points_for_percentage, MoralityDb, MoralityKind},
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+use super::IntPlotType;
+
+#[derive(Clone, PartialEq)]
+pub enum MoralityType {
+    /// Plot integers, Charm and Intimidate talents of ME1 LE.
+    Me1(IntPlotType, Option<(RcRef<ComplexTalent>, RcRef<ComplexTalent>)>),
+    Me2(IntPlotType),
+    Me3(IntPlotType),
+}
+
+impl MoralityType {
+    fn morality_db(&self, databases: Databases) -> Option<Rc<MoralityDb>> {
+        match self {
+            MoralityType::Me1(..) => databases.get_me1_morality(),
+            MoralityType::Me2(_) => databases.get_me2_morality(),
+            MoralityType::Me3(_) => databases.get_me3_morality(),
+        }
+    }
+
+    fn integers(&self) -> &IntPlotType {
+        match self {
+            MoralityType::Me1(integers, _)
+            | MoralityType::Me2(integers)
+            | MoralityType::Me3(integers) => integers,
+        }
+    }
+
+    fn talents(&self) -> Option<&(RcRef<ComplexTalent>, RcRef<ComplexTalent>)> {
+        match self {
+            MoralityType::Me1(_, talents) => talents.as_ref(),
+            MoralityType::Me2(_) | MoralityType::Me3(_) => None,
+        }
+    }
+}
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    Changed,
+    SetTarget(MoralityKind),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub morality: MoralityType,
+}
+
+pub struct Morality {
+    _db_handle: ContextHandle<Databases>,
+    morality_db: Option<Rc<MoralityDb>>,
+    bar_points: RcCell<i32>,
+    paragon_target: RcCell<f32>,
+    renegade_target: RcCell<f32>,
+}
+
+impl Component for Morality {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        let mut this = Morality {
+            _db_handle,
+            morality_db: None,
+            bar_points: Default::default(),
+            paragon_target: Default::default(),
+            renegade_target: Default::default(),
+        };
+        this.set_morality_db(ctx.props().morality.morality_db(databases));
+        this
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.set_morality_db(ctx.props().morality.morality_db(*dbs));
+            }
+            Msg::Changed => {}
+            Msg::SetTarget(kind) => {
+                if let Some((points, target)) = self.points(ctx, kind) {
+                    points.set(points_for_percentage(target.get(), self.bar_points(ctx, kind)));
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let morality_db = match self.morality_db {
+            Some(ref morality_db) => morality_db,
+            None => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        let morality = &ctx.props().morality;
+        let integers = morality.integers();
+        let link = ctx.link();
+
+        let bars = [MoralityKind::Paragon, MoralityKind::Renegade].into_iter().filter_map(|kind| {
+            let (points, target) = self.points(ctx, kind)?;
+            let bar_points = self.bar_points(ctx, kind);
+            let (label, set) = match kind {
+                MoralityKind::Paragon => ("Paragon", "Set paragon"),
+                _ => ("Renegade", "Set renegade"),
+            };
+            let percentage = (bar_points > 0).then(|| {
+                html! {
+                    <div class="cursor-default">
+                        { format!("{:.1}% of the bar", bar_percentage(points.get(), bar_points)) }
+                    </div>
+                }
+            });
+            Some(html! {
+                <div class="flex flex-wrap items-center gap-1">
+                    <InputNumber
+                        {label}
+                        value={NumberType::Int(points)}
+                        onchange={link.callback(|_| Msg::Changed)}
+                    />
+                    { for percentage }
+                    <InputNumber
+                        label="% target"
+                        value={NumberType::Float(target)}
+                    />
+                    <button class="button"
+                        disabled={bar_points <= 0}
+                        onclick={link.callback(move |_| Msg::SetTarget(kind))}
+                    >
+                        { set }
+                    </button>
+                </div>
+            })
+        });
+
+        let reputation = [
+            (morality_db.reputation, "Reputation"),
+            (morality_db.reputation_points, "Reputation Points"),
+            (morality_db.persuade_multiplier, "Persuade Multiplier"),
+        ]
+        .into_iter()
+        .filter_map(|(idx, label)| {
+            let value = integers.get(idx?)?;
+            Some(html! {
+                <InputNumber
+                    {label}
+                    value={NumberType::Int(value)}
+                    onchange={link.callback(|_| Msg::Changed)}
+                />
+            })
+        });
+
+        // Ranked in the talents, the bars unlock the maximum rank
+        let talents = morality.talents().map(|(charm, intimidate)| {
+            let rank = |label, talent: &RcRef<ComplexTalent>| {
+                let talent = talent.borrow();
+                html! {
+                    <div class="cursor-default">
+                        { format!("{} rank {} / {}", label, talent.current_rank(), talent.max_rank()) }
+                    </div>
+                }
+            };
+            html! {
+                <>
+                    { rank("Charm", charm) }
+                    { rank("Intimidate", intimidate) }
+                </>
+            }
+        });
+
+        // ME2 keeps the size of the bars in the plot
+        let full_bar = (morality_db.paragon_potential.is_none()).then(|| {
+            html! {
+                <InputNumber
+                    label="Full bar"
+                    value={NumberType::Int(RcCell::clone(&self.bar_points))}
+                    helper="Points filling a morality bar, used for the percentages and the targets.\n\
+                        It is only known for Mass Effect 1 and 2, enter the value of your game for the others."
+                    onchange={link.callback(|_| Msg::Changed)}
+                />
+            }
+        });
+
+        html! {
+            <Table title="Morality">
+                { for bars }
+                { for reputation }
+                { for talents }
+                { for full_bar }
+                { self.checks(ctx, morality_db) }
+            </Table>
+        }
+    }
+}
+
+impl Morality {
+    fn set_morality_db(&mut self, morality_db: Option<Rc<MoralityDb>>) {
+        if let Some(bar_points) = morality_db.as_ref().and_then(|db| db.bar_points) {
+            self.bar_points.set(bar_points);
+        }
+        self.morality_db = morality_db;
+    }
+
+    fn bar_points(&self, ctx: &Context<Self>, kind: MoralityKind) -> i32 {
+        let integers = ctx.props().morality.integers();
+        self.morality_db
+            .as_ref()
+            .and_then(|db| db.potential(kind, |idx| integers.get(idx).map(|value| value.get())))
+            .unwrap_or_else(|| self.bar_points.get())
+    }
+
+    fn points(
+        &self, ctx: &Context<Self>, kind: MoralityKind,
+    ) -> Option<(RcCell<i32>, RcCell<f32>)> {
+        let morality_db = self.morality_db.as_ref()?;
+        let integers = ctx.props().morality.integers();
+        match kind {
+            MoralityKind::Paragon => {
+                Some((integers.get(morality_db.paragon)?, RcCell::clone(&self.paragon_target)))
+            }
+            MoralityKind::Renegade => {
+                Some((integers.get(morality_db.renegade)?, RcCell::clone(&self.renegade_target)))
+            }
+            _ => None,
+        }
+    }
+
+    fn checks(&self, ctx: &Context<Self>, morality_db: &MoralityDb) -> Html {
+        let morality = &ctx.props().morality;
+        let integers = morality.integers();
+        let value = |kind| -> Option<f32> {
+            match kind {
+                MoralityKind::Paragon => Some(integers.get(morality_db.paragon)?.get() as f32),
+                MoralityKind::Renegade => Some(integers.get(morality_db.renegade)?.get() as f32),
+                MoralityKind::Reputation => {
+                    Some(integers.get(morality_db.reputation?)?.get() as f32)
+                }
+                MoralityKind::Charm => {
+                    morality.talents().map(|(charm, _)| charm.borrow().current_rank() as f32)
+                }
+                MoralityKind::Intimidate => morality
+                    .talents()
+                    .map(|(_, intimidate)| intimidate.borrow().current_rank() as f32),
+            }
+        };
+
+        if morality_db.checks().is_empty() {
+            return html! {
+                <p class="cursor-default">{ "No dialogue check is known for this game yet." }</p>
+            };
+        }
+
+        let checks = morality_db.checks().iter().map(|check| {
+            let needed = check.needed(|idx| integers.get(idx).map(|value| value.get()));
+            let passed = value(check.kind).map_or(false, |value| value >= needed);
+            html! {
+                <div class="cursor-default">
+                    { format!("{} {} ({})", if passed { "✓" } else { "✗" }, check.name, needed) }
+                </div>
+            }
+        });
+
+        html! {
+            <Table title="Dialogue checks" opened=false>
+                { for checks }
+            </Table>
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{Select, Table},
+    save_data::{
+        shared::{plot::BitVec, romance_db::RomanceDb},
+        RcRef,
+    },
+    services::database::Databases,
+};
+
+use super::IntPlotType;
+
+#[derive(Clone, PartialEq)]
+pub enum RomanceType {
+    Me1(RcRef<BitVec>, IntPlotType),
+    Me2(RcRef<BitVec>, IntPlotType),
+    Me3(RcRef<BitVec>, IntPlotType),
+}
+
+impl RomanceType {
+    fn romance_db(&self, databases: Databases) -> Option<Rc<RomanceDb>> {
+        match self {
+            RomanceType::Me1(..) => databases.get_me1_romance(),
+            RomanceType::Me2(..) => databases.get_me2_romance(),
+            RomanceType::Me3(..) => databases.get_me3_romance(),
+        }
+    }
+
+    fn plot(&self) -> (&RcRef<BitVec>, &IntPlotType) {
+        match self {
+            RomanceType::Me1(booleans, integers)
+            | RomanceType::Me2(booleans, integers)
+            | RomanceType::Me3(booleans, integers) => (booleans, integers),
+        }
+    }
+}
+
+pub enum Msg {
+    DatabaseLoaded(Box<Databases>),
+    SetStage(usize, usize),
+    SetImported(String, usize),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub romance: RomanceType,
+}
+
+pub struct Romance {
+    _db_handle: ContextHandle<Databases>,
+    romance_db: Option<Rc<RomanceDb>>,
+}
+
+impl Component for Romance {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(|dbs| Msg::DatabaseLoaded(Box::new(dbs))))
+            .expect("no database provider");
+
+        Romance { _db_handle, romance_db: ctx.props().romance.romance_db(databases) }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let (booleans, integers) = ctx.props().romance.plot();
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.romance_db = ctx.props().romance.romance_db(*dbs);
+            }
+            Msg::SetStage(romance_idx, stage_idx) => {
+                if let Some(romance) =
+                    self.romance_db.as_ref().and_then(|db| db.romances().get(romance_idx))
+                {
+                    if let Some(stage) = romance.stages().nth(stage_idx) {
+                        romance.set_stage(
+                            stage,
+                            &mut booleans.borrow_mut(),
+                            |idx| integers.get(idx).map(|value| value.get()),
+                            |idx, value| integers.set(idx, value),
+                        );
+                    }
+                }
+            }
+            Msg::SetImported(game, idx) => {
+                if let Some(ref romance_db) = self.romance_db {
+                    // 0 is no romance
+                    let romance = idx.checked_sub(1).and_then(|idx| {
+                        romance_db
+                            .imported()
+                            .iter()
+                            .filter(|imported| imported.game == game)
+                            .nth(idx)
+                            .map(|imported| imported.name.as_str())
+                    });
+                    romance_db.set_imported(&game, romance, &mut booleans.borrow_mut());
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let romance_db = match self.romance_db {
+            Some(ref romance_db) => romance_db,
+            None => {
+                return html! {
+                    <p>{ "Loading database..." }</p>
+                }
+            }
+        };
+
+        let (booleans, integers) = ctx.props().romance.plot();
+        let booleans = booleans.borrow();
+        let integer = |idx| integers.get(idx).map(|value| value.get());
+        let link = ctx.link();
+
+        let romances = romance_db.romances().iter().enumerate().map(|(romance_idx, romance)| {
+            let current = romance.stage(&booleans, integer);
+            let current_idx =
+                romance.stages().position(|stage| stage == current).unwrap_or_default();
+            let options: Vec<_> = romance.stages().map(|stage| stage.name().to_owned()).collect();
+            html! {
+                <div class="flex items-center gap-1 cursor-default">
+                    <Select
+                        {options}
+                        {current_idx}
+                        onselect={link.callback(move |idx| Msg::SetStage(romance_idx, idx))}
+                    />
+                    { &romance.name }
+                </div>
+            }
+        });
+
+        let mut games: Vec<_> =
+            romance_db.imported().iter().map(|imported| imported.game.as_str()).collect();
+        games.dedup();
+        let imported = games.into_iter().map(|game| {
+            let names = romance_db.imported().iter().filter(|imported| imported.game == game);
+            let current_idx = names
+                .clone()
+                .position(|imported| booleans.get(imported.boolean).map_or(false, |b| *b))
+                .map_or(0, |idx| idx + 1);
+            let options: Vec<_> = std::iter::once("None".to_owned())
+                .chain(names.map(|imported| imported.name.clone()))
+                .collect();
+            let owned_game = game.to_owned();
+            html! {
+                <div class="flex items-center gap-1 cursor-default">
+                    <Select
+                        {options}
+                        {current_idx}
+                        onselect={link.callback(move |idx| Msg::SetImported(owned_game.clone(), idx))}
+                    />
+                    { format!("Imported from {}", game) }
+                </div>
+            }
+        });
+        let imported = (!romance_db.imported().is_empty()).then(|| {
+            html! {
+                <Table title="Imported romances" helper=
+                    "Romance flags the import of the previous game left in the plot.\n\
+                    Selecting a romance sets its flag and clears the others."
+                >
+                    { for imported }
+                </Table>
+            }
+        });
+
+        let contradictions = romance_db.contradictions(&booleans, integer);
+        let contradictions = contradictions.into_iter().map(|contradiction| {
+            html! { <p class="text-red-500">{ contradiction }</p> }
+        });
+
+        html! {
+            <div class="flex flex-col gap-1">
+                <Table title="Romances" helper=
+                    "Current stage of each romance, selecting a stage writes all of its plot flags.\n\
+                    Only the stages with known flags are listed."
+                >
+                    { for contradictions }
+                    { for romances }
+                </Table>
+                { for imported }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use yew::prelude::*;
+
+use crate::gui::components::Select;
+
+#[derive(Clone)]
+enum Choice {
+    Keep,
+    Unequip,
+    Equip(String),
+}
+
+pub enum Msg {
+    Select(usize),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub label: String,
+    /// Class names and names of the weapons of the slot.
+    pub weapons: Vec<(String, String)>,
+    pub equipped: String,
+    /// Adds a `-` option to empty the slot.
+    #[prop_or_default]
+    pub unequip: bool,
+    /// Class name of the chosen weapon, `None` to empty the slot.
+    pub onselect: Callback<Option<String>>,
+}
+
+pub struct WeaponSelect;
+
+impl WeaponSelect {
+    // Empty slots are blank or `None` depending on the game
+    fn is_empty(class_name: &str) -> bool {
+        class_name.is_empty() || class_name == "None"
+    }
+
+    fn choices(props: &Props) -> Vec<(String, Choice)> {
+        let mut choices = Vec::new();
+        if props.unequip {
+            choices.push(("-".to_owned(), Choice::Unequip));
+        }
+
+        // An empty slot or an unknown weapon is kept as it is until another weapon is chosen
+        let is_known = props
+            .weapons
+            .iter()
+            .any(|(class_name, _)| class_name.eq_ignore_ascii_case(&props.equipped));
+        if Self::is_empty(&props.equipped) {
+            if !props.unequip {
+                choices.push(("-".to_owned(), Choice::Keep));
+            }
+        } else if !is_known {
+            choices.push((props.equipped.clone(), Choice::Keep));
+        }
+
+        choices.extend(
+            props
+                .weapons
+                .iter()
+                .map(|(class_name, name)| (name.clone(), Choice::Equip(class_name.clone()))),
+        );
+        choices
+    }
+}
+
+impl Component for WeaponSelect {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        WeaponSelect
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Select(idx) => {
+                match Self::choices(ctx.props()).into_iter().nth(idx) {
+                    Some((_, Choice::Equip(class_name))) => {
+                        ctx.props().onselect.emit(Some(class_name))
+                    }
+                    Some((_, Choice::Unequip)) => ctx.props().onselect.emit(None),
+                    Some((_, Choice::Keep)) | None => {}
+                }
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let choices = Self::choices(props);
+
+        let current_idx = choices
+            .iter()
+            .position(|(_, choice)| match choice {
+                Choice::Equip(class_name) => class_name.eq_ignore_ascii_case(&props.equipped),
+                Choice::Keep => true,
+                Choice::Unequip => Self::is_empty(&props.equipped),
+            })
+            .unwrap_or_default();
+        let options: Vec<_> = choices.into_iter().map(|(label, _)| label).collect();
+
+        html! {
+            <div class="flex items-center gap-1 cursor-default">
+                <Select {options} {current_idx} onselect={ctx.link().callback(Msg::Select)} />
+                { &props.label }
+            </div>
+        }
+    }
+}
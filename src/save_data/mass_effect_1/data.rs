@@ -7,7 +7,7 @@ use crate::{
         shared::{appearance::LinearColor, Rotator, Vector},
         Dummy,
     },
-    save_data::{RcCell, RcRef},
+    save_data::{RcCell, RcRef, SeqAccessExt},
     unreal,
 };
 
@@ -22,7 +22,7 @@ impl Data {
     where
         A: de::SeqAccess<'de>,
     {
-        let _osef = seq.next_element()?.unwrap();
+        let _osef = seq.next_field()?;
         let properties = List::<RcRef<Property>>::visit_seq(names, seq)?;
         Ok(Self { _osef, properties })
     }
@@ -36,8 +36,11 @@ impl Data {
     }
 }
 
-fn get_name(names: &[Name], id: u32) -> String {
-    names[id as usize].string.borrow().clone()
+fn get_name<E: de::Error>(names: &[Name], id: u32) -> Result<String, E> {
+    names
+        .get(id as usize)
+        .map(|name| name.string.borrow().clone())
+        .ok_or_else(|| de::Error::custom(format!("unknown name id {}", id)))
 }
 
 impl List<RcRef<Property>> {
@@ -169,31 +172,31 @@ impl Property {
         A: de::SeqAccess<'de>,
     {
         // Name
-        let name_id = seq.next_element()?.unwrap();
-        let _osef1 = seq.next_element()?.unwrap();
+        let name_id = seq.next_field()?;
+        let _osef1 = seq.next_field()?;
 
-        let name = get_name(names, name_id);
+        let name = get_name(names, name_id)?;
         if name == "None" {
             return Ok(Property::None { name_id, _osef: _osef1 });
         }
 
         // Type
-        let type_id = seq.next_element()?.unwrap();
-        let _osef2 = seq.next_element()?.unwrap();
+        let type_id = seq.next_field()?;
+        let _osef2 = seq.next_field()?;
         // Size
-        let size = seq.next_element()?.unwrap();
-        let _osef3 = seq.next_element()?.unwrap();
+        let size = seq.next_field()?;
+        let _osef3 = seq.next_field()?;
 
-        let type_name = get_name(names, type_id);
+        let type_name = get_name(names, type_id)?;
         let property = match type_name.as_str() {
             "ArrayProperty" => {
-                let len: u32 = seq.next_element()?.unwrap();
+                let len: u32 = seq.next_field()?;
                 let mut array = Vec::new();
                 // Hardcodé sinon je dois chercher dans toutes les classes du jeu...
                 match name.as_str() {
                     "m_PrereqTalentIDArray" | "m_PrereqTalentRankArray" => {
                         for _ in 0..len {
-                            let array_int = ArrayType::Int(seq.next_element()?.unwrap());
+                            let array_int = ArrayType::Int(seq.next_field()?);
                             array.push(array_int);
                         }
                     }
@@ -203,19 +206,19 @@ impl Property {
                     | "m_QuickSlotArray"
                     | "m_savedBuybackItems" => {
                         for _ in 0..len {
-                            let array_object = ArrayType::Object(seq.next_element()?.unwrap());
+                            let array_object = ArrayType::Object(seq.next_field()?);
                             array.push(array_object);
                         }
                     }
                     "m_vPosition" => {
                         for _ in 0..len {
-                            let array_vector = ArrayType::Vector(seq.next_element()?.unwrap());
+                            let array_vector = ArrayType::Vector(seq.next_field()?);
                             array.push(array_vector);
                         }
                     }
                     "m_DependentPackages" => {
                         for _ in 0..len {
-                            let array_string = ArrayType::String(seq.next_element()?.unwrap());
+                            let array_string = ArrayType::String(seq.next_field()?);
                             array.push(array_string);
                         }
                     }
@@ -231,16 +234,16 @@ impl Property {
                 Property::Array { name_id, _osef1, type_id, _osef2, size, _osef3, array }
             }
             "BoolProperty" => {
-                let value = seq.next_element()?.unwrap();
+                let value = seq.next_field()?;
                 Property::Bool { name_id, _osef1, type_id, _osef2, size, _osef3, value }
             }
             "ByteProperty" => {
                 if size == 1 {
-                    let value = seq.next_element()?.unwrap();
+                    let value = seq.next_field()?;
                     Property::Byte { name_id, _osef1, type_id, _osef2, size, _osef3, value }
                 } else {
-                    let value_name_id = seq.next_element()?.unwrap();
-                    let _osef4 = seq.next_element()?.unwrap();
+                    let value_name_id = seq.next_field()?;
+                    let _osef4 = seq.next_field()?;
                     Property::Name {
                         name_id,
                         _osef1,
@@ -254,16 +257,16 @@ impl Property {
                 }
             }
             "FloatProperty" => {
-                let value = seq.next_element()?.unwrap();
+                let value = seq.next_field()?;
                 Property::Float { name_id, _osef1, type_id, _osef2, size, _osef3, value }
             }
             "IntProperty" => {
-                let value = seq.next_element()?.unwrap();
+                let value = seq.next_field()?;
                 Property::Int { name_id, _osef1, type_id, _osef2, size, _osef3, value }
             }
             "NameProperty" => {
-                let value_name_id = seq.next_element()?.unwrap();
-                let _osef4 = seq.next_element()?.unwrap();
+                let value_name_id = seq.next_field()?;
+                let _osef4 = seq.next_field()?;
                 Property::Name {
                     name_id,
                     _osef1,
@@ -276,26 +279,26 @@ impl Property {
                 }
             }
             "ObjectProperty" => {
-                let object_id = seq.next_element()?.unwrap();
+                let object_id = seq.next_field()?;
                 Property::Object { name_id, _osef1, type_id, _osef2, size, _osef3, object_id }
             }
             "StrProperty" => {
-                let string = seq.next_element()?.unwrap();
+                let string = seq.next_field()?;
                 Property::Str { name_id, _osef1, type_id, _osef2, size, _osef3, string }
             }
             "StringRefProperty" => {
-                let value = seq.next_element()?.unwrap();
+                let value = seq.next_field()?;
                 Property::StringRef { name_id, _osef1, type_id, _osef2, size, _osef3, value }
             }
             "StructProperty" => {
-                let struct_name_id = seq.next_element()?.unwrap();
-                let _osef4 = seq.next_element()?.unwrap();
+                let struct_name_id = seq.next_field()?;
+                let _osef4 = seq.next_field()?;
 
-                let struct_name = get_name(names, struct_name_id);
+                let struct_name = get_name(names, struct_name_id)?;
                 let struct_type = match struct_name.as_str() {
-                    "LinearColor" => StructType::LinearColor(seq.next_element()?.unwrap()),
-                    "Vector" => StructType::Vector(seq.next_element()?.unwrap()),
-                    "Rotator" => StructType::Rotator(seq.next_element()?.unwrap()),
+                    "LinearColor" => StructType::LinearColor(seq.next_field()?),
+                    "Vector" => StructType::Vector(seq.next_field()?),
+                    "Rotator" => StructType::Rotator(seq.next_field()?),
                     _ => StructType::Properties(List::<RcRef<Property>>::visit_seq(names, seq)?),
                 };
                 Property::Struct {
@@ -310,7 +313,7 @@ impl Property {
                     struct_type,
                 }
             }
-            _ => unimplemented!(),
+            _ => return Err(de::Error::custom(format!("unknown property type `{}`", type_name))),
         };
         Ok(property)
     }
@@ -14,7 +14,7 @@ use serde::ser::SerializeTupleStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
 
-use super::{Dummy, List};
+use super::{Dummy, List, SeqAccessExt};
 use crate::unreal;
 
 #[rcize_fields]
@@ -105,17 +105,17 @@ impl<'de> Deserialize<'de> for Me1SaveGame {
             where
                 A: de::SeqAccess<'de>,
             {
-                let magic_number = seq.next_element()?.unwrap();
-                let begin = seq.next_element()?.unwrap();
-                let zip_offset = seq.next_element()?.unwrap();
+                let magic_number = seq.next_field()?;
+                let begin = seq.next_field()?;
+                let zip_offset = seq.next_field()?;
 
                 // No man's land
                 let mut no_mans_land = Vec::new();
                 for _ in 0..(zip_offset - 12) {
-                    no_mans_land.push(seq.next_element()?.unwrap());
+                    no_mans_land.push(seq.next_field()?);
                 }
 
-                let zip_data: List<u8> = seq.next_element()?.unwrap();
+                let zip_data: List<u8> = seq.next_field()?;
                 let (player, state, world_save_package) =
                     Me1SaveGame::unzip(&zip_data).map_err(de::Error::custom)?;
 
@@ -8,6 +8,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use super::{data::Data, List};
 use crate::save_data::Dummy;
 use crate::save_data::RcRef;
+use crate::save_data::SeqAccessExt;
 use crate::unreal;
 
 #[derive(Clone)]
@@ -58,39 +59,39 @@ impl<'de> Deserialize<'de> for Player {
             where
                 A: de::SeqAccess<'de>,
             {
-                let begin = seq.next_element()?.unwrap();
-                let header_offset = seq.next_element()?.unwrap();
+                let begin = seq.next_field()?;
+                let header_offset = seq.next_field()?;
 
                 // No man's land 1
                 let mut no_mans_land1 = Vec::new();
                 for _ in 0..(header_offset - 12) {
-                    no_mans_land1.push(seq.next_element()?.unwrap());
+                    no_mans_land1.push(seq.next_field()?);
                 }
 
-                let header: Header = seq.next_element()?.unwrap();
+                let header: Header = seq.next_field()?;
 
                 // Names
                 let mut names = Vec::new();
                 for _ in 0..header.name_len {
-                    names.push(seq.next_element()?.unwrap());
+                    names.push(seq.next_field()?);
                 }
 
                 // Imports
                 let mut classes = Vec::new();
                 for _ in 0..header.classes_len {
-                    classes.push(seq.next_element()?.unwrap());
+                    classes.push(seq.next_field()?);
                 }
 
                 // Objects
                 let mut objects = Vec::new();
                 for _ in 0..header.objects_len {
-                    objects.push(seq.next_element()?.unwrap());
+                    objects.push(seq.next_field()?);
                 }
 
                 // No man's land 2
                 let mut no_mans_land2 = Vec::new();
                 for _ in 0..(header.data_offset - header.no_mans_land_offset) {
-                    no_mans_land2.push(seq.next_element()?.unwrap());
+                    no_mans_land2.push(seq.next_field()?);
                 }
 
                 // Data
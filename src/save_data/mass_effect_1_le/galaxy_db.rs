@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Me1LePlanet {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct Me1LeSystem {
+    pub id: i32,
+    pub name: String,
+    pub planets: Vec<Me1LePlanet>,
+}
+
+#[derive(Deserialize)]
+pub struct Me1LeCluster {
+    pub id: i32,
+    pub name: String,
+    pub systems: Vec<Me1LeSystem>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct Me1LeSafeSpot {
+    pub name: String,
+    pub base_level_name: String,
+    pub map_name: String,
+    pub destination_area_map: String,
+    pub location: (f32, f32, f32),
+}
+
+#[derive(Deserialize)]
+pub struct Me1LeGalaxyDb {
+    clusters: Vec<Me1LeCluster>,
+    safe_spots: Vec<Me1LeSafeSpot>,
+}
+
+impl Me1LeGalaxyDb {
+    pub fn clusters(&self) -> &[Me1LeCluster] {
+        &self.clusters
+    }
+
+    pub fn safe_spots(&self) -> &[Me1LeSafeSpot] {
+        &self.safe_spots
+    }
+
+    pub fn systems(&self) -> impl Iterator<Item = &Me1LeSystem> {
+        self.clusters.iter().flat_map(|cluster| &cluster.systems)
+    }
+
+    pub fn planets(&self) -> impl Iterator<Item = &Me1LePlanet> {
+        self.systems().flat_map(|system| &system.planets)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::{save_data::mass_effect_1_le::Me1LeSaveGame, unreal};
+    use anyhow::Result;
+
+    #[test]
+    fn deserialize_galaxy_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_le_galaxy_db.ron")?;
+        let me1_le_galaxy_db: Me1LeGalaxyDb = ron::from_str(&input)?;
+
+        // Ids have to be unique or the toggles would edit each other
+        let mut planet_ids: Vec<_> = me1_le_galaxy_db.planets().map(|planet| planet.id).collect();
+        planet_ids.sort_unstable();
+        planet_ids.dedup();
+        assert_eq!(planet_ids.len(), me1_le_galaxy_db.planets().count());
+
+        assert!(!me1_le_galaxy_db.safe_spots().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn relocate_to_safe_spot() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_le_galaxy_db.ron")?;
+        let me1_le_galaxy_db: Me1LeGalaxyDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // The Noveria spot was captured from this save
+        let current = me1_save_game.save_data.borrow().current_spot();
+        let noveria = &me1_le_galaxy_db.safe_spots()[0];
+        assert_eq!(current.base_level_name, noveria.base_level_name);
+        assert_eq!(current.map_name, noveria.map_name);
+
+        for spot in me1_le_galaxy_db.safe_spots() {
+            me1_save_game.save_data.borrow_mut().relocate(spot);
+
+            let output = unreal::Serializer::to_vec(&me1_save_game)?;
+            let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+            let save_data = me1_save_game.save_data.borrow();
+
+            assert_eq!(*save_data.map_name(), spot.map_name);
+            assert_eq!(save_data.location().xyz(), spot.location);
+
+            let world = save_data.world().expect("no world");
+            let world = world.borrow();
+            assert_eq!(*world.destination_area_map(), spot.destination_area_map);
+            assert_eq!(world.destination().xyz(), spot.location);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::save_data::RcRef;
+
+use super::{player::ComplexTalent, squad::Henchman};
+
+#[derive(Deserialize)]
+pub struct Me1LeHenchmanClass {
+    pub tag: String,
+    pub name: String,
+    pub class_name: String,
+    pub class_talent: i32,
+    pub complex_talents: Vec<RcRef<ComplexTalent>>,
+}
+
+#[derive(Deserialize)]
+pub struct Me1LeHenchmanDb {
+    talent_names: IndexMap<i32, String>,
+    henchmen: Vec<Me1LeHenchmanClass>,
+}
+
+impl Me1LeHenchmanDb {
+    pub fn henchmen(&self) -> &[Me1LeHenchmanClass] {
+        &self.henchmen
+    }
+
+    pub fn get(&self, tag: &str) -> Option<&Me1LeHenchmanClass> {
+        self.henchmen.iter().find(|henchman| henchman.tag == tag)
+    }
+
+    pub fn talent_name(&self, talent_id: i32) -> String {
+        self.talent_names
+            .get(&talent_id)
+            .cloned()
+            .unwrap_or_else(|| format!("Unknown talent ({})", talent_id))
+    }
+
+    /// Talents of the henchman which are ranked past their prerequisites.
+    pub fn unmet_prerequisites(&self, henchman: &Henchman) -> Vec<String> {
+        let class = match self.get(&henchman.tag()) {
+            Some(class) => class,
+            None => return Vec::new(),
+        };
+
+        let complex_talents = henchman.complex_talents();
+        let rank_of = |talent_id| {
+            complex_talents
+                .iter()
+                .find(|talent| talent.borrow().talent_id() == talent_id)
+                .map(|talent| talent.borrow().current_rank())
+                .unwrap_or_default()
+        };
+
+        class
+            .complex_talents
+            .iter()
+            .filter(|talent| rank_of(talent.borrow().talent_id()) > 0)
+            .flat_map(|talent| {
+                let talent = talent.borrow();
+                talent
+                    .prerequisites()
+                    .into_iter()
+                    .filter(|&(prereq_id, prereq_rank)| rank_of(prereq_id) < prereq_rank)
+                    .map(|(prereq_id, prereq_rank)| {
+                        format!(
+                            "{} requires {} rank {}",
+                            self.talent_name(talent.talent_id()),
+                            self.talent_name(prereq_id),
+                            prereq_rank
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::{save_data::mass_effect_1_le::Me1LeSaveGame, unreal};
+    use anyhow::Result;
+
+    #[test]
+    fn deserialize_henchman_class_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_le_henchman_class_db.ron")?;
+        let me1_le_henchman_db: Me1LeHenchmanDb = ron::from_str(&input)?;
+
+        for henchman in me1_le_henchman_db.henchmen() {
+            assert!(henchman
+                .complex_talents
+                .iter()
+                .any(|talent| talent.borrow().talent_id() == henchman.class_talent));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn henchman_class_db_matches_saves() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_le_henchman_class_db.ron")?;
+        let me1_le_henchman_db: Me1LeHenchmanDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let save_data = me1_save_game.save_data.borrow();
+
+        for henchman in save_data.squad().iter() {
+            let henchman = henchman.borrow();
+            let class = me1_le_henchman_db.get(&henchman.tag()).expect("unknown henchman");
+
+            let ids = |talents: &[RcRef<ComplexTalent>]| {
+                talents
+                    .iter()
+                    .map(|talent| {
+                        let talent = talent.borrow();
+                        (talent.talent_id(), talent.prerequisites())
+                    })
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(ids(&henchman.complex_talents()), ids(&class.complex_talents));
+        }
+
+        Ok(())
+    }
+}
@@ -1,14 +1,187 @@
 use indexmap::IndexMap;
 use serde::Deserialize;
 
+use super::player::{Item, ItemLevel, ItemMod};
+
 #[derive(Deserialize, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct DbItem {
     pub item_id: i32,
     pub manufacturer_id: i32,
 }
 
-#[derive(Deserialize, Deref, From, PartialEq, Eq)]
-pub struct Me1ItemDb(IndexMap<DbItem, String>);
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq)]
+pub enum ItemCategory {
+    None,
+    Armor,
+    AssaultRifle,
+    Shotgun,
+    Pistol,
+    SniperRifle,
+    OmniTool,
+    BioticAmp,
+    Grenade,
+    ArmorMod,
+    WeaponMod,
+    AmmoMod,
+    GrenadeMod,
+}
+
+impl ItemCategory {
+    pub fn allowed_mods(self) -> &'static [ItemCategory] {
+        match self {
+            ItemCategory::Armor => &[ItemCategory::ArmorMod],
+            ItemCategory::AssaultRifle
+            | ItemCategory::Shotgun
+            | ItemCategory::Pistol
+            | ItemCategory::SniperRifle => &[ItemCategory::WeaponMod, ItemCategory::AmmoMod],
+            ItemCategory::Grenade => &[ItemCategory::GrenadeMod],
+            _ => &[],
+        }
+    }
+}
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Display)]
+pub enum ItemRace {
+    Human,
+    Turian,
+    Krogan,
+    Quarian,
+}
+
+impl ItemRace {
+    /// Armor race of a henchman, Liara wears human armors.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "hench_humanfemale" | "hench_humanmale" | "hench_asari" => Some(ItemRace::Human),
+            "hench_turian" => Some(ItemRace::Turian),
+            "hench_krogan" => Some(ItemRace::Krogan),
+            "hench_quarian" => Some(ItemRace::Quarian),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct DbItemData {
+    pub name: String,
+    pub category: ItemCategory,
+    #[serde(default)]
+    pub race: Option<ItemRace>,
+    #[serde(default = "DbItemData::default_levels")]
+    pub levels: (ItemLevel, ItemLevel),
+}
+
+impl DbItemData {
+    fn default_levels() -> (ItemLevel, ItemLevel) {
+        (ItemLevel::I, ItemLevel::X)
+    }
+
+    pub fn is_valid_level(&self, level: ItemLevel) -> bool {
+        let (min, max) = self.levels;
+        (min as usize..=max as usize).contains(&(level as usize))
+    }
+
+    pub fn clamp_level(&self, level: ItemLevel) -> ItemLevel {
+        let (min, max) = self.levels;
+        ItemLevel::from((level as usize).clamp(min as usize, max as usize))
+    }
+}
+
+#[derive(Deserialize, Deref, From, PartialEq)]
+pub struct Me1ItemDb(IndexMap<DbItem, DbItemData>);
+
+impl Me1ItemDb {
+    pub fn get_item(&self, item: &Item) -> Option<&DbItemData> {
+        self.get(&DbItem { item_id: item.item_id(), manufacturer_id: item.manufacturer_id() })
+    }
+
+    pub fn get_mod(&self, item_mod: &ItemMod) -> Option<&DbItemData> {
+        self.get(&DbItem {
+            item_id: item_mod.item_id(),
+            manufacturer_id: item_mod.manufacturer_id(),
+        })
+    }
+
+    /// Sets the level of an item and its mods, within the levels they exist at.
+    pub fn upgrade(&self, item: &mut Item, level: ItemLevel) {
+        if item.item_id() == 0 {
+            return;
+        }
+
+        let item_level = self.get_item(item).map(|data| data.clamp_level(level)).unwrap_or(level);
+        *item.item_level_mut() = item_level;
+
+        for item_mod in item.attached_mods().iter() {
+            let mut item_mod = item_mod.borrow_mut();
+            let mod_level =
+                self.get_mod(&item_mod).map(|data| data.clamp_level(level)).unwrap_or(level);
+            *item_mod.item_level_mut() = mod_level;
+        }
+    }
+
+    /// Lists the issues of an item, `race` is the race of the character it is equipped on.
+    pub fn validate(&self, item: &Item, race: Option<ItemRace>) -> Vec<String> {
+        if item.item_id() == 0 {
+            return vec![];
+        }
+
+        let data = match self.get_item(item) {
+            Some(data) => data,
+            None => {
+                return vec![format!(
+                    "Unknown item (id: {}, manufacturer: {})",
+                    item.item_id(),
+                    item.manufacturer_id()
+                )]
+            }
+        };
+
+        let mut issues = vec![];
+        let item_level = *item.item_level();
+        if !data.is_valid_level(item_level) {
+            issues.push(format!(
+                "{}: level {} doesn't exist ({} - {})",
+                data.name,
+                ItemLevel::variants()[item_level as usize],
+                ItemLevel::variants()[data.levels.0 as usize],
+                ItemLevel::variants()[data.levels.1 as usize],
+            ));
+        }
+
+        if let (Some(race), Some(item_race)) = (race, data.race) {
+            if race != item_race {
+                issues.push(format!("{}: can't be worn by a {}", data.name, race));
+            }
+        }
+
+        for item_mod in item.attached_mods().iter() {
+            let item_mod = item_mod.borrow();
+            match self.get_mod(&item_mod) {
+                Some(mod_data) => {
+                    if !data.category.allowed_mods().contains(&mod_data.category) {
+                        issues
+                            .push(format!("{}: can't be attached to {}", mod_data.name, data.name));
+                    }
+                    let mod_level = *item_mod.item_level();
+                    if !mod_data.is_valid_level(mod_level) {
+                        issues.push(format!(
+                            "{}: level {} doesn't exist",
+                            mod_data.name,
+                            ItemLevel::variants()[mod_level as usize],
+                        ));
+                    }
+                }
+                None => issues.push(format!(
+                    "{}: unknown mod (id: {}, manufacturer: {})",
+                    data.name,
+                    item_mod.item_id(),
+                    item_mod.manufacturer_id()
+                )),
+            }
+        }
+        issues
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -21,7 +194,12 @@ mod test {
     #[test]
     fn deserialize_item_db() -> Result<()> {
         let input = fs::read_to_string("databases/me1_item_db.ron")?;
-        let _me1_item_db: Me1ItemDb = ron::from_str(&input)?;
+        let me1_item_db: Me1ItemDb = ron::from_str(&input)?;
+
+        // Only armors are race restricted
+        for data in me1_item_db.values() {
+            assert!(data.race.is_none() || data.category == ItemCategory::Armor, "{}", data.name);
+        }
 
         Ok(())
     }
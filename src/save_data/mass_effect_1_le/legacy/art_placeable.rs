@@ -7,13 +7,13 @@ use crate::save_data::Dummy;
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct ArtPlaceableBehavior {
     is_dead: bool,
-    generated_treasure: bool,
+    pub generated_treasure: bool,
     challenge_scaled: bool,
     owner: Option<BaseObject>,
     health: f32,
     current_health: f32,
     enabled: bool,
-    current_fsm_state_name: String,
+    pub current_fsm_state_name: String,
     is_destroyed: bool,
     state_0: String,
     state_1: String,
@@ -21,11 +21,29 @@ pub struct ArtPlaceableBehavior {
     use_case_override: bool,
     player_only: bool,
     skill_difficulty: u8,
-    inventory: Option<BaseObject>,
+    pub inventory: Option<BaseObject>,
     skill_game_failed: bool,
     skill_game_xp_awarded: bool,
 }
 
+impl ArtPlaceableBehavior {
+    /// The loot is generated again the next time the placeable is opened.
+    pub fn reset(&mut self) {
+        self.is_dead.set(false);
+        self.is_destroyed.set(false);
+        self.set_generated_treasure(false);
+        self.skill_game_failed.set(false);
+        self.skill_game_xp_awarded.set(false);
+        self.current_health.set(self.health.get());
+        *self.inventory_mut() = None;
+
+        let mut state = self.current_fsm_state_name_mut();
+        if *state == "Opened" {
+            *state = "Closed".to_owned();
+        }
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct ArtPlaceable {
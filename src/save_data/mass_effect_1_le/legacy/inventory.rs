@@ -1,3 +1,4 @@
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 use super::{BaseObject, OptionObjectProxy};
@@ -6,26 +7,73 @@ use crate::save_data::mass_effect_1_le::player::ItemLevel;
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct Shop {
-    last_player_level: i32,
-    is_initialized: bool,
-    inventory: Vec<OptionObjectProxy>,
+    pub last_player_level: i32,
+    pub is_initialized: bool,
+    pub inventory: Vec<OptionObjectProxy>,
+}
+
+impl Shop {
+    /// Empties the stock, the game generates a new one the next time the shop is opened.
+    pub fn restock(&mut self) {
+        self.set_is_initialized(false);
+        self.set_last_player_level(0);
+        for inventory in self.inventory().iter() {
+            *inventory.borrow_mut().proxy_mut() = None;
+        }
+    }
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct Inventory {
-    items: Vec<BaseObject>,
-    plot_items: Vec<PlotItem>,
+    pub items: Vec<BaseObject>,
+    pub plot_items: Vec<PlotItem>,
     credits: i32,
     grenades: i32,
     medigel: f32,
     omnigel: f32,
 }
 
+impl Inventory {
+    /// Appends a copy of the item at `idx`, its owner name gets the next free index.
+    pub fn duplicate_item(&mut self, idx: usize) -> Result<()> {
+        let new_item = {
+            let items = self.items();
+            let item = match items.get(idx) {
+                Some(item) => item.borrow(),
+                None => return Ok(()),
+            };
+
+            let owner_name = item.owner_name().clone();
+            let prefix =
+                owner_name.rsplit_once('_').map(|(prefix, _)| prefix).unwrap_or(&owner_name);
+            let next_idx = items
+                .iter()
+                .filter_map(|other| {
+                    let other = other.borrow();
+                    let other_name = other.owner_name();
+                    let (other_prefix, other_idx) = other_name.rsplit_once('_')?;
+                    if other_prefix == prefix {
+                        other_idx.parse::<u32>().ok()
+                    } else {
+                        None
+                    }
+                })
+                .max()
+                .map_or(0, |idx| idx + 1);
+
+            item.duplicate(format!("{}_{}", prefix, next_idx))?
+        };
+
+        self.items_mut().push(new_item.into());
+        Ok(())
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
-struct PlotItem {
+pub struct PlotItem {
     localized_name: i32,
     localized_desc: i32,
     export_id: i32,
@@ -37,27 +85,27 @@ struct PlotItem {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct Item {
-    item_id: i32,
-    item_level: ItemLevel,
-    manufacturer_id: i32,
+    pub item_id: i32,
+    pub item_level: ItemLevel,
+    pub manufacturer_id: i32,
     plot_conditional_id: i32,
-    slot_specs: Vec<ModdableSlotSpec>,
+    pub slot_specs: Vec<ModdableSlotSpec>,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
-struct ModdableSlotSpec {
+pub struct ModdableSlotSpec {
     type_id: i32,
-    mods: Vec<OptionObjectProxy>,
+    pub mods: Vec<OptionObjectProxy>,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct ItemMod {
-    item_id: i32,
-    item_level: ItemLevel,
-    manufacturer_id: i32,
+    pub item_id: i32,
+    pub item_level: ItemLevel,
+    pub manufacturer_id: i32,
     plot_conditional_id: i32,
     type_id: i32,
 }
@@ -1,32 +1,95 @@
-mod art_placeable;
-mod inventory;
-mod pawn;
+pub mod art_placeable;
+pub mod inventory;
+pub mod pawn;
 
 use self::{art_placeable::*, inventory::*, pawn::*};
 
 use std::fmt;
 
+use anyhow::Result;
 use indexmap::IndexMap;
 use serde::{de, Deserialize, Deserializer, Serialize};
 
-use crate::save_data::RcRef;
+use crate::save_data::{mass_effect_1_le::galaxy_db::Me1LeGalaxyDb, RcCell, RcRef, SeqAccessExt};
 use crate::save_data::{
     shared::{Rotator, Vector},
-    Dummy,
+    Dummy, List,
 };
+use crate::unreal;
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, RawUi, RawUiChildren)]
 pub struct Map {
-    levels: IndexMap<String, Level>,
-    world: Option<BaseObject>,
+    pub levels: IndexMap<String, Level>,
+    pub world: Option<BaseObject>,
+}
+
+impl Map {
+    pub fn revive_pawns(&self) {
+        for level in self.levels().values() {
+            level.borrow_mut().revive_pawns();
+        }
+    }
+
+    pub fn reset_placeables(&self) {
+        for level in self.levels().values() {
+            level.borrow_mut().reset_placeables();
+        }
+    }
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, RawUi, RawUiChildren)]
 pub struct Level {
-    objects: Vec<BaseObject>,
-    actors: Vec<String>,
+    pub objects: Vec<BaseObject>,
+    pub actors: Vec<String>, // Destroyed actors
+}
+
+impl Level {
+    /// Brings back the pawns killed in the level.
+    pub fn revive_pawns(&mut self) {
+        self.actors_mut().retain(|actor| !actor.borrow().contains(".BioPawnBehavior"));
+
+        let pawns = self.pawns();
+
+        // The most a living pawn of the same level has
+        let full = |level| {
+            pawns
+                .iter()
+                .map(|pawn| pawn.borrow())
+                .filter(|pawn| !pawn.is_dead() && pawn.level() == level)
+                .map(|pawn| (pawn.health(), pawn.shield()))
+                .reduce(|(health, shield), (other_health, other_shield)| {
+                    (health.max(other_health), shield.max(other_shield))
+                })
+        };
+        for pawn in &pawns {
+            let level = pawn.borrow().level();
+            let full = full(level);
+            pawn.borrow_mut().revive(full);
+        }
+    }
+
+    fn pawns(&self) -> Vec<RcRef<PawnBehavior>> {
+        self.objects()
+            .iter()
+            .filter_map(|object| match object.borrow()._object {
+                Object::PawnBehavior(ref pawn_behavior) => Some(RcRef::clone(pawn_behavior)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Resets the containers and other placeables of the level to their unlooted state.
+    pub fn reset_placeables(&mut self) {
+        self.actors_mut().retain(|actor| !actor.borrow().contains(".BioArtPlaceableBehavior"));
+
+        for object in self.objects().iter() {
+            if let Object::ArtPlaceableBehavior(ref placeable) = object.borrow()._object {
+                placeable.borrow_mut().reset();
+            }
+        }
+    }
 }
 
 #[rcize_fields]
@@ -56,31 +119,34 @@ impl<'de> Deserialize<'de> for BaseObject {
             where
                 A: de::SeqAccess<'de>,
             {
-                let class_name: String = seq.next_element()?.unwrap();
-                let owner_name = seq.next_element()?.unwrap();
-                let owner_class = seq.next_element()?.unwrap();
+                let class_name: String = seq.next_field()?;
+                let owner_name = seq.next_field()?;
+                let owner_class = seq.next_field()?;
                 let object = match class_name.as_str() {
-                    "BioPawnBehaviorSaveObject" => {
-                        Object::PawnBehavior(seq.next_element()?.unwrap())
-                    }
-                    "BioPawnSaveObject" => Object::Pawn(seq.next_element()?.unwrap()),
-                    "BioBaseSquadSaveObject" => Object::BaseSquad(seq.next_element()?.unwrap()),
-                    "BioShopSaveObject" => Object::Shop(seq.next_element()?.unwrap()),
-                    "BioInventorySaveObject" => Object::Inventory(seq.next_element()?.unwrap()),
-                    "BioItemXModdableSaveObject" => Object::Item(seq.next_element()?.unwrap()),
-                    "BioItemXModSaveObject" => Object::ItemMod(seq.next_element()?.unwrap()),
+                    "BioPawnBehaviorSaveObject" => Object::PawnBehavior(seq.next_field()?),
+                    "BioPawnSaveObject" => Object::Pawn(seq.next_field()?),
+                    "BioBaseSquadSaveObject" => Object::BaseSquad(seq.next_field()?),
+                    "BioShopSaveObject" => Object::Shop(seq.next_field()?),
+                    "BioInventorySaveObject" => Object::Inventory(seq.next_field()?),
+                    "BioItemXModdableSaveObject" => Object::Item(seq.next_field()?),
+                    "BioItemXModSaveObject" => Object::ItemMod(seq.next_field()?),
                     "BioArtPlaceableBehaviorSaveObject" => {
-                        Object::ArtPlaceableBehavior(seq.next_element()?.unwrap())
+                        Object::ArtPlaceableBehavior(seq.next_field()?)
                     }
-                    "BioArtPlaceableSaveObject" => {
-                        Object::ArtPlaceable(seq.next_element()?.unwrap())
+                    "BioArtPlaceableSaveObject" => Object::ArtPlaceable(seq.next_field()?),
+                    "BioVehicleBehaviorSaveObject" => Object::VehicleBehavior(seq.next_field()?),
+                    "BioVehicleSaveObject" => Object::Vehicle(seq.next_field()?),
+                    "BioWorldInfoSaveObject" => Object::World(seq.next_field()?),
+                    // Added by a mod
+                    _ if class_name.ends_with("SaveObject") => {
+                        Object::Opaque(seq.next_field::<OpaqueObject>()?.0)
                     }
-                    "BioVehicleBehaviorSaveObject" => {
-                        Object::VehicleBehavior(seq.next_element()?.unwrap())
+                    _ => {
+                        return Err(de::Error::custom(format!(
+                            "unknown save object class `{}`",
+                            class_name
+                        )))
                     }
-                    "BioVehicleSaveObject" => Object::Vehicle(seq.next_element()?.unwrap()),
-                    "BioWorldInfoSaveObject" => Object::World(seq.next_element()?.unwrap()),
-                    _ => unreachable!(),
                 };
 
                 Ok(BaseObject { _class_name: class_name, owner_name, owner_class, _object: object })
@@ -90,6 +156,137 @@ impl<'de> Deserialize<'de> for BaseObject {
     }
 }
 
+impl BaseObject {
+    /// Deep copy of the object, `owner_name` must be unique among its siblings.
+    pub fn duplicate(&self, owner_name: String) -> Result<BaseObject> {
+        let bytes = unreal::Serializer::to_vec(self)?;
+        let mut object: BaseObject = from_bytes_with_opaque_objects(&bytes)?;
+        *object.owner_name_mut() = owner_name;
+        Ok(object)
+    }
+}
+
+/// Payload of a save object of an unknown class, added by a mod.
+struct OpaqueObject(List<u8>);
+
+impl<'de> Deserialize<'de> for OpaqueObject {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct OpaqueObjectVisitor;
+        impl<'de> de::Visitor<'de> for OpaqueObjectVisitor {
+            type Value = OpaqueObject;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an OpaqueObject")
+            }
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(OpaqueObject(v.into()))
+            }
+        }
+        deserializer.deserialize_newtype_struct(unreal::OPAQUE_OBJECT, OpaqueObjectVisitor)
+    }
+}
+
+/// Reads legacy world data, the save objects of unknown classes are kept as is.
+///
+/// Save objects aren't prefixed with their size, an unknown object is assumed to end where the
+/// next object or the destroyed actors of its level start. The first ends that let all the
+/// data be read are kept.
+pub fn from_bytes_with_opaque_objects<'de, T: Deserialize<'de>>(
+    input: &'de [u8],
+) -> unreal::Result<T> {
+    const MAX_TRIES: usize = 1000;
+
+    // Lengths of the unknown objects met so far and the lengths left to try for each of them
+    let mut lengths = Vec::new();
+    let mut untried: Vec<Vec<usize>> = Vec::new();
+    let mut first_error = None;
+
+    for _ in 0..MAX_TRIES {
+        match unreal::Deserializer::from_bytes_with_opaque(input, &lengths) {
+            Ok(value) => return Ok(value),
+            Err(unreal::Error::UnknownObject(position)) => {
+                first_error.get_or_insert(unreal::Error::UnknownObject(position));
+                let mut ends: Vec<_> =
+                    object_ends(input, position).map(|end| end - position).collect();
+                ends.reverse();
+                untried.push(ends);
+            }
+            Err(err) => {
+                let err = first_error.get_or_insert(err).clone();
+                // The last length tried is wrong
+                if lengths.pop().is_none() {
+                    return Err(err);
+                }
+            }
+        }
+
+        // Next length to try, back to the previous unknown object when none is left
+        loop {
+            match untried.last_mut() {
+                Some(ends) => match ends.pop() {
+                    Some(len) => {
+                        lengths.push(len);
+                        break;
+                    }
+                    None => {
+                        untried.pop();
+                        lengths.pop();
+                    }
+                },
+                None => {
+                    return Err(first_error.unwrap_or(unreal::Error::UnknownObject(0)));
+                }
+            }
+        }
+    }
+    Err(first_error.unwrap_or(unreal::Error::UnknownObject(0)))
+}
+
+/// Where an object starting at `position` may end: before the class name of a save object or
+/// before the destroyed actors of its level.
+fn object_ends(input: &[u8], position: usize) -> impl Iterator<Item = usize> + '_ {
+    let u32_at = |at: usize| {
+        let bytes = input.get(at..at + 4)?;
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+    // An ascii string, with its i32 length and trailing 0
+    let string_at = move |at: usize| -> Option<&[u8]> {
+        let len = u32_at(at)? as usize;
+        let bytes = input.get(at + 4..at + 4 + len)?;
+        match bytes.split_last() {
+            None => Some(bytes),
+            Some((0, string)) if string.iter().all(|b| b.is_ascii_graphic() || *b == b' ') => {
+                Some(string)
+            }
+            _ => None,
+        }
+    };
+
+    (position..=input.len()).filter(move |&end| {
+        let is_class_name = string_at(end).map_or(false, |string| string.ends_with(b"SaveObject"));
+        let is_actors = match u32_at(end) {
+            // Followed by the next level, the world of the map or the end
+            Some(0) => {
+                end + 4 == input.len()
+                    || string_at(end + 4).is_some()
+                    || matches!(u32_at(end + 4), Some(0 | 1))
+            }
+            Some(count) if count < 0x10000 => {
+                string_at(end + 4).map_or(false, |string| !string.is_empty())
+            }
+            _ => false,
+        };
+        is_class_name || is_actors
+    })
+}
+
 #[derive(Serialize, Clone)]
 pub enum Object {
     PawnBehavior(RcRef<PawnBehavior>),
@@ -104,6 +301,8 @@ pub enum Object {
     VehicleBehavior(RcRef<VehicleBehavior>),
     Vehicle(RcRef<Vehicle>),
     World(RcRef<World>),
+    /// Object of an unknown class, kept as is.
+    Opaque(List<u8>),
     Default,
 }
 
@@ -158,12 +357,12 @@ struct WorldStreamingState {
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct World {
     streaming_states: Vec<WorldStreamingState>,
-    destination_area_map: String,
-    destination: Vector,
-    cinematics_seen: Vec<String>,
-    scanned_clusters: Vec<i32>,
-    scanned_systems: Vec<i32>,
-    scanned_planets: Vec<i32>,
+    pub destination_area_map: String,
+    pub destination: Vector,
+    pub cinematics_seen: Vec<String>,
+    pub scanned_clusters: Vec<i32>,
+    pub scanned_systems: Vec<i32>,
+    pub scanned_planets: Vec<i32>,
     journal_sort_method: u8,
     journal_showing_missions: bool,
     journal_last_selected_mission: i32,
@@ -176,3 +375,229 @@ pub struct World {
     _browser_alerts: Dummy<8>, // [u8; 8]
     pending_loot: Option<BaseObject>,
 }
+
+impl World {
+    /// Scans every cluster, system and planet of the galaxy map.
+    pub fn mark_all_scanned(&mut self, galaxy_db: &Me1LeGalaxyDb) {
+        for cluster in galaxy_db.clusters() {
+            add_id(&mut self.scanned_clusters_mut(), cluster.id);
+            for system in &cluster.systems {
+                add_id(&mut self.scanned_systems_mut(), system.id);
+                for planet in &system.planets {
+                    add_id(&mut self.scanned_planets_mut(), planet.id);
+                }
+            }
+        }
+    }
+
+    pub fn is_planet_scanned(&self, planet_id: i32) -> bool {
+        self.scanned_planets().iter().any(|id| id.get() == planet_id)
+    }
+
+    pub fn set_planet_scanned(&mut self, planet_id: i32, scanned: bool) {
+        if scanned {
+            add_id(&mut self.scanned_planets_mut(), planet_id);
+        } else {
+            self.scanned_planets_mut().retain(|id| id.get() != planet_id);
+        }
+    }
+
+    /// Lets the game play the cinematics again.
+    pub fn reset_cinematics(&mut self) {
+        self.cinematics_seen_mut().clear();
+    }
+}
+
+fn add_id(ids: &mut Vec<RcCell<i32>>, id: i32) {
+    if !ids.iter().any(|other| other.get() == id) {
+        ids.push(id.into());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::save_data::mass_effect_1_le::{Me1LeSaveGame, NoExportData};
+
+    fn inventories(level: &Level) -> Vec<RcRef<Inventory>> {
+        level
+            .objects()
+            .iter()
+            .filter_map(|object| match object.borrow()._object {
+                Object::Shop(ref shop) => Some(shop.borrow().inventory().clone()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|proxy| match proxy.borrow().proxy().as_ref()?.borrow()._object {
+                Object::Inventory(ref inventory) => Some(RcRef::clone(inventory)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn edit_legacy_world() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let (item_count, owner_names, map_name, level_name, pawn, full) = {
+            let save_data = me1_save_game.save_data.borrow();
+            let no_export = save_data.no_export();
+            let legacy_maps = no_export.as_ref().unwrap().borrow().legacy_maps().clone();
+
+            let inventory = legacy_maps
+                .values()
+                .flat_map(|map| map.borrow().levels().values().cloned().collect::<Vec<_>>())
+                .flat_map(|level| inventories(&level.borrow()))
+                .find(|inventory| !inventory.borrow().items().is_empty())
+                .unwrap();
+            inventory.borrow_mut().duplicate_item(0)?;
+
+            // Kill a pawn that has an unharmed pawn of the same level next to it
+            let level_of =
+                |map: &str, level: &str| legacy_maps[map].borrow().levels()[level].clone();
+            let (map_name, level_name, pawn, full) = legacy_maps
+                .iter()
+                .flat_map(|(map_name, map)| {
+                    let levels = map.borrow().levels().clone();
+                    levels.into_iter().map(move |(name, level)| (map_name.clone(), name, level))
+                })
+                .find_map(|(map_name, level_name, level)| {
+                    let pawns = level.borrow().pawns();
+                    pawns.iter().enumerate().find_map(|(idx, pawn)| {
+                        let level = pawn.borrow().level();
+                        let full = pawns
+                            .iter()
+                            .enumerate()
+                            .filter(|&(other_idx, other)| {
+                                other_idx != idx && other.borrow().level() == level
+                            })
+                            .map(|(_, other)| (other.borrow().health(), other.borrow().shield()))
+                            .filter(|&(health, shield)| health > 1.0 && shield > 0.0)
+                            .reduce(|(health, shield), (other_health, other_shield)| {
+                                (health.max(other_health), shield.max(other_shield))
+                            })?;
+                        Some((map_name.clone(), level_name.clone(), idx, full))
+                    })
+                })
+                .unwrap();
+            {
+                let pawn = &level_of(&map_name, &level_name).borrow().pawns()[pawn];
+                let mut pawn = pawn.borrow_mut();
+                pawn.set_is_dead(true);
+                pawn.set_health(0.0);
+                pawn.set_shield(0.0);
+            }
+
+            for map in legacy_maps.values() {
+                map.borrow().revive_pawns();
+                map.borrow().reset_placeables();
+            }
+
+            let inventory = inventory.borrow();
+            let items = inventory.items();
+            let owner_names: Vec<_> =
+                items.iter().map(|item| item.borrow().owner_name().clone()).collect();
+            (items.len(), owner_names, map_name, level_name, pawn, full)
+        };
+
+        // Names stay unique
+        let mut unique_names = owner_names.clone();
+        unique_names.sort();
+        unique_names.dedup();
+        assert_eq!(unique_names.len(), owner_names.len());
+
+        // The edited world still round trips
+        let output = unreal::Serializer::to_vec(&me1_save_game)?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+
+        let save_data = me1_save_game.save_data.borrow();
+        let no_export = save_data.no_export();
+        let no_export = no_export.as_ref().unwrap().borrow();
+        let legacy_maps = no_export.legacy_maps();
+        for map in legacy_maps.values() {
+            for level in map.borrow().levels().values() {
+                let level = level.borrow();
+                assert!(level.actors().iter().all(|actor| {
+                    let actor = actor.borrow();
+                    !actor.contains(".BioPawnBehavior")
+                        && !actor.contains(".BioArtPlaceableBehavior")
+                }));
+            }
+        }
+
+        let duplicated = legacy_maps
+            .values()
+            .flat_map(|map| map.borrow().levels().values().cloned().collect::<Vec<_>>())
+            .flat_map(|level| inventories(&level.borrow()))
+            .any(|inventory| inventory.borrow().items().len() == item_count);
+        assert!(duplicated);
+
+        // The revived pawn is back to full health and shield
+        let level = legacy_maps[&map_name].borrow().levels()[&level_name].clone();
+        let pawn = &level.borrow().pawns()[pawn];
+        let pawn = pawn.borrow();
+        assert!(!pawn.is_dead());
+        assert_eq!((pawn.health(), pawn.shield()), full);
+
+        Ok(())
+    }
+
+    #[test]
+    fn keep_unknown_objects() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let save_data = me1_save_game.save_data.borrow();
+        let no_export = save_data.no_export();
+        let no_export = no_export.as_ref().unwrap().borrow();
+
+        // A modded object in the middle of a level and another one at the end of a level
+        let levels: Vec<_> = no_export
+            .legacy_maps()
+            .values()
+            .flat_map(|map| map.borrow().levels().values().cloned().collect::<Vec<_>>())
+            .collect();
+        let placeable = levels
+            .iter()
+            .flat_map(|level| level.borrow().objects().clone())
+            .find(|object| matches!(object.borrow()._object, Object::ArtPlaceableBehavior(_)))
+            .unwrap();
+        let last =
+            levels.iter().rev().find_map(|level| level.borrow().objects().last().cloned()).unwrap();
+        for object in [&placeable, &last] {
+            object.borrow_mut()._class_name = "BioModSaveObject".to_owned();
+        }
+        let pawn_count = levels.iter().map(|level| level.borrow().pawns().len()).sum::<usize>();
+
+        let bytes = unreal::Serializer::to_vec(&*no_export)?;
+        assert!(unreal::Deserializer::from_bytes::<NoExportData>(&bytes).is_err());
+        let modded: NoExportData = from_bytes_with_opaque_objects(&bytes)?;
+
+        // The modded objects are kept with their class name, the others can still be edited
+        let levels: Vec<_> = modded
+            .legacy_maps()
+            .values()
+            .flat_map(|map| map.borrow().levels().values().cloned().collect::<Vec<_>>())
+            .collect();
+        let opaque: Vec<_> = levels
+            .iter()
+            .flat_map(|level| level.borrow().objects().clone())
+            .filter(|object| matches!(object.borrow()._object, Object::Opaque(_)))
+            .map(|object| object.borrow()._class_name.clone())
+            .collect();
+        assert_eq!(opaque, ["BioModSaveObject", "BioModSaveObject"]);
+        assert_eq!(
+            levels.iter().map(|level| level.borrow().pawns().len()).sum::<usize>(),
+            pawn_count
+        );
+
+        // And written back unchanged
+        assert_eq!(unreal::Serializer::to_vec(&modded)?, bytes);
+
+        Ok(())
+    }
+}
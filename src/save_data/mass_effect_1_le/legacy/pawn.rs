@@ -9,18 +9,18 @@ use crate::save_data::{
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct PawnBehavior {
-    is_dead: bool,
+    pub is_dead: bool,
     generated_treasure: bool,
     challenge_scaled: bool,
     owner: Option<BaseObject>,
-    health: f32,
-    shield: f32,
+    pub health: f32,
+    pub shield: f32,
     first_name: String,
     localized_last_name: i32,
     health_max: f32,
     health_regen_rate: f32,
     radar_range: f32,
-    level: i32,
+    pub level: i32,
     health_per_level: f32,
     stability: f32,
     gender: u8,
@@ -61,6 +61,26 @@ pub struct PawnBehavior {
     equipment: Vec<OptionObjectProxy>,
 }
 
+impl PawnBehavior {
+    /// `full` is the health and shield of an unharmed pawn of the same level, if there is one.
+    /// Otherwise the pawn gets back the health it gains with its levels and its shield recharges in game.
+    pub fn revive(&mut self, full: Option<(f32, f32)>) {
+        if self.is_dead() {
+            let (health, shield) = full.unwrap_or_else(|| {
+                // The game doesn't save `health_max`, it's usually 0
+                let health =
+                    self.health_max.get().max(self.health_per_level.get() * self.level() as f32);
+                (health.max(1.0), self.shield())
+            });
+            self.set_health(health);
+            self.set_shield(shield);
+            self.set_is_dead(false);
+            self.generated_treasure.set(false);
+            *self.inventory.borrow_mut() = None;
+        }
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", talent_id)]
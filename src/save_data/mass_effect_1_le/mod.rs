@@ -1,10 +1,13 @@
+pub mod galaxy_db;
+pub mod henchman_db;
 pub mod item_db;
 pub mod legacy;
 pub mod player;
 pub mod player_class_db;
+pub mod progression_db;
 pub mod squad;
 
-use self::{legacy::*, player::*, squad::*};
+use self::{galaxy_db::Me1LeSafeSpot, legacy::*, player::*, squad::*};
 
 use std::fmt;
 use std::io::Read;
@@ -17,7 +20,10 @@ use serde::de;
 use serde::ser::{self, SerializeStruct};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{save_data::RcRef, unreal};
+use crate::{
+    save_data::{RcRef, SeqAccessExt},
+    unreal,
+};
 
 use super::{
     shared::{
@@ -61,23 +67,23 @@ impl<'de> Deserialize<'de> for Me1LeSaveGame {
             where
                 A: de::SeqAccess<'de>,
             {
-                let magic_number = seq.next_element()?.unwrap();
-                let block_size = seq.next_element()?.unwrap();
+                let magic_number = seq.next_field()?;
+                let block_size = seq.next_field()?;
 
                 // Headers
                 let mut headers = Vec::new();
                 {
                     let full_header = ChunkHeader {
-                        compressed_size: seq.next_element()?.unwrap(),
-                        uncompressed_size: seq.next_element()?.unwrap(),
+                        compressed_size: seq.next_field()?,
+                        uncompressed_size: seq.next_field()?,
                     };
                     headers.push(full_header);
 
                     let mut finished = false;
                     while !finished {
                         let header = ChunkHeader {
-                            compressed_size: seq.next_element()?.unwrap(),
-                            uncompressed_size: seq.next_element()?.unwrap(),
+                            compressed_size: seq.next_field()?,
+                            uncompressed_size: seq.next_field()?,
                         };
                         if header.uncompressed_size < block_size {
                             finished = true;
@@ -93,7 +99,7 @@ impl<'de> Deserialize<'de> for Me1LeSaveGame {
                     for header in &headers[1..] {
                         let mut compressed = Vec::new();
                         for _ in 0..header.compressed_size {
-                            compressed.push(seq.next_element()?.unwrap());
+                            compressed.push(seq.next_field()?);
                         }
 
                         let mut z = ZlibDecoder::new(&compressed[..]);
@@ -103,9 +109,9 @@ impl<'de> Deserialize<'de> for Me1LeSaveGame {
                     unreal::Deserializer::from_bytes(&uncompressed).map_err(de::Error::custom)?
                 };
 
-                let checksum = seq.next_element()?.unwrap();
-                let compression_flag = seq.next_element()?.unwrap();
-                let uncompressed_size = seq.next_element()?.unwrap();
+                let checksum = seq.next_field()?;
+                let compression_flag = seq.next_field()?;
+                let uncompressed_size = seq.next_field()?;
 
                 Ok(Me1LeSaveGame {
                     magic_number,
@@ -202,15 +208,15 @@ pub struct Me1LeSaveData {
     character_id: String,
     created_date: SaveTimeStamp,
     pub plot: PlotTable,
-    journal: Journal,
-    codex: Codex,
+    pub journal: Journal,
+    pub codex: Codex,
     timestamp: SaveTimeStamp,
     seconds_played: i32,
     pub player: Player,
-    base_level_name: String,
-    map_name: String,
+    pub base_level_name: String,
+    pub map_name: String,
     parent_map_name: String,
-    location: Vector,
+    pub location: Vector,
     rotation: Rotator,
     pub squad: Vec<Henchman>,
     display_name: String,
@@ -218,6 +224,51 @@ pub struct Me1LeSaveData {
     pub no_export: NoExport, // Only serialized for normal savegames, not for character export
 }
 
+impl Me1LeSaveData {
+    /// World state of the current map, if the save has one.
+    pub fn world(&self) -> Option<RcRef<World>> {
+        let no_export = self.no_export();
+        let no_export = no_export.as_ref()?.borrow();
+        let legacy_maps = no_export.legacy_maps();
+
+        let base_level_name = self.base_level_name();
+        let mut maps = legacy_maps.get(&*base_level_name).into_iter().chain(legacy_maps.values());
+        maps.find_map(|map| match map.borrow().world().as_ref()?.borrow()._object {
+            Object::World(ref world) => Some(RcRef::clone(world)),
+            _ => None,
+        })
+    }
+
+    /// Moves the player to a known position, the world destination is moved along.
+    pub fn relocate(&mut self, spot: &Me1LeSafeSpot) {
+        let (x, y, z) = spot.location;
+        *self.base_level_name_mut() = spot.base_level_name.clone();
+        *self.map_name_mut() = spot.map_name.clone();
+        *self.location_mut() = Vector::new(x, y, z);
+
+        if let Some(world) = self.world() {
+            let mut world = world.borrow_mut();
+            *world.destination_area_map_mut() = spot.destination_area_map.clone();
+            *world.destination_mut() = Vector::new(x, y, z);
+        }
+    }
+
+    /// The current position as a spot to move other saves to, named after the map.
+    pub fn current_spot(&self) -> Me1LeSafeSpot {
+        let destination_area_map = self
+            .world()
+            .map(|world| world.borrow().destination_area_map().clone())
+            .unwrap_or_default();
+        Me1LeSafeSpot {
+            name: format!("{} / {}", self.base_level_name(), self.map_name()),
+            base_level_name: self.base_level_name().clone(),
+            map_name: self.map_name().clone(),
+            destination_area_map,
+            location: self.location().xyz(),
+        }
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me1LeVersion(i32);
 
@@ -239,11 +290,34 @@ impl<'de> Deserialize<'de> for Me1LeVersion {
 }
 
 #[derive(Clone)]
-pub struct NoExport(Option<RcRef<NoExportData>>);
+pub enum NoExport {
+    None,
+    Data(RcRef<NoExportData>),
+    /// World data that couldn't be read, kept as is.
+    ///
+    /// Save objects of unknown classes are kept as is one at a time, see
+    /// [`legacy::from_bytes_with_opaque_objects`]. This is only left when the end of one of
+    /// them can't be found.
+    Opaque {
+        raw: List<u8>,
+        error: String,
+    },
+}
 
 impl NoExport {
     pub fn as_ref(&self) -> Option<&RcRef<NoExportData>> {
-        self.0.as_ref()
+        match self {
+            NoExport::Data(no_export_data) => Some(no_export_data),
+            NoExport::None | NoExport::Opaque { .. } => None,
+        }
+    }
+
+    /// Why the world data couldn't be read, if so.
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            NoExport::Opaque { error, .. } => Some(error),
+            NoExport::None | NoExport::Data(_) => None,
+        }
     }
 }
 
@@ -264,15 +338,21 @@ impl<'de> Deserialize<'de> for NoExport {
             where
                 D: Deserializer<'de>,
             {
-                let no_export_data: NoExportData = Deserialize::deserialize(deserializer)?;
-                Ok(NoExport(Some(no_export_data.into())))
+                // The world data is at the end of the save, if it can't be read it's kept
+                // as raw bytes so the rest of the save can still be edited
+                let raw: List<u8> = Deserialize::deserialize(deserializer)?;
+                let no_export = match legacy::from_bytes_with_opaque_objects::<NoExportData>(&raw) {
+                    Ok(no_export_data) => NoExport::Data(no_export_data.into()),
+                    Err(err) => NoExport::Opaque { raw, error: err.to_string() },
+                };
+                Ok(no_export)
             }
 
             fn visit_none<E>(self) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                Ok(NoExport(None))
+                Ok(NoExport::None)
             }
         }
         deserializer.deserialize_any(NoExportVisitor)
@@ -284,9 +364,10 @@ impl serde::Serialize for NoExport {
     where
         S: Serializer,
     {
-        match self.0 {
-            Some(ref no_export_data) => no_export_data.serialize(serializer),
-            None => serializer.serialize_unit(),
+        match self {
+            NoExport::Data(no_export_data) => no_export_data.serialize(serializer),
+            NoExport::Opaque { raw, .. } => raw.serialize(serializer),
+            NoExport::None => serializer.serialize_unit(),
         }
     }
 }
@@ -294,7 +375,7 @@ impl serde::Serialize for NoExport {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct NoExportData {
-    legacy_maps: IndexMap<String, Map>,
+    pub legacy_maps: IndexMap<String, Map>,
     mako: Vehicle,
 }
 
@@ -389,6 +470,59 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn unknown_save_object() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let mut uncompressed = unreal::Serializer::to_vec(&me1_save_game.save_data)?;
+
+        // Pretend a mod added its own save object class
+        let class_name = b"BioShopSaveObject";
+        let offset =
+            uncompressed.windows(class_name.len()).position(|window| window == class_name).unwrap();
+        uncompressed[offset..offset + class_name.len()].copy_from_slice(b"ModShopSaveObject");
+
+        // Only that object is kept as is, the rest of the world can still be read
+        let save_data: Me1LeSaveData = unreal::Deserializer::from_bytes(&uncompressed)?;
+        assert!(save_data.no_export().error().is_none());
+        let modded = save_data
+            .no_export()
+            .as_ref()
+            .unwrap()
+            .borrow()
+            .legacy_maps()
+            .values()
+            .flat_map(|map| map.borrow().levels().values().cloned().collect::<Vec<_>>())
+            .flat_map(|level| level.borrow().objects().clone())
+            .filter(|object| matches!(object.borrow()._object, Object::Opaque(_)))
+            .map(|object| object.borrow()._class_name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(modded, ["ModShopSaveObject"]);
+
+        // Kept as is
+        let output = unreal::Serializer::to_vec(&save_data)?;
+        assert!(output == uncompressed);
+
+        Ok(())
+    }
+
+    #[test]
+    fn persuasion_talents() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let save_data = me1_save_game.save_data.borrow();
+        let player = save_data.player();
+
+        // The skills are never written, the talents hold the ranks and what the morality unlocked
+        assert_eq!((player.skill_charm(), player.skill_intimidate()), (0.0, 0.0));
+        let (charm, intimidate) = player.persuasion_talents().unwrap();
+        let (charm, intimidate) = (charm.borrow(), intimidate.borrow());
+        assert_eq!((charm.current_rank(), charm.max_rank()), (1, 6));
+        assert_eq!((intimidate.current_rank(), intimidate.max_rank()), (10, 10));
+
+        Ok(())
+    }
+
     // #[test]
     // fn uncompress() -> Result<()> {
     //     let input = fs::read("test/ME1Le_Export.pcsav")?;
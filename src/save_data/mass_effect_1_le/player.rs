@@ -5,7 +5,7 @@ use crate::save_data::{
         appearance::HeadMorph,
         player::{Notoriety, Origin},
     },
-    Dummy,
+    Dummy, RcCell, RcRef,
 };
 
 #[rcize_fields]
@@ -46,12 +46,12 @@ pub struct Player {
     coordination: i32,
     attribute_primary: u8,
     attribute_secondary: u8,
-    skill_charm: f32,
-    skill_intimidate: f32,
+    pub skill_charm: f32,
+    pub skill_intimidate: f32,
     skill_haggle: f32,
     health: f32,
     shield: f32,
-    xp_level: i32,
+    pub xp_level: i32,
     is_driving: bool,
     pub game_options: Vec<i32>,
     helmet_shown: bool,
@@ -63,6 +63,30 @@ pub struct Player {
     secondary_weapon: String,
 }
 
+impl Player {
+    pub fn spent_talent_points(&self) -> i32 {
+        ComplexTalent::spent_talent_points(&self.complex_talents())
+    }
+
+    /// Resets all the complex talents and refunds their talent points.
+    pub fn respec(&mut self) {
+        ComplexTalent::respec(&self.talent_points, &self.complex_talents());
+    }
+
+    /// Charm and Intimidate talents, the ranks checked by the dialogues.
+    ///
+    /// The game doesn't keep `skill_charm` and `skill_intimidate` up to date, they stay at 0.
+    pub fn persuasion_talents(&self) -> Option<(RcRef<ComplexTalent>, RcRef<ComplexTalent>)> {
+        let talent = |talent_id| {
+            self.complex_talents()
+                .iter()
+                .find(|talent| talent.borrow().talent_id() == talent_id)
+                .cloned()
+        };
+        Some((talent(ComplexTalent::CHARM)?, talent(ComplexTalent::INTIMIDATE)?))
+    }
+}
+
 #[derive(Deserialize, Serialize, Clone, RawUi, PartialEq)]
 pub enum Me1LeClass {
     Soldier,
@@ -95,6 +119,28 @@ pub struct ComplexTalent {
     prereq_talent_ranks: Vec<i32>,
 }
 
+impl ComplexTalent {
+    pub const CHARM: i32 = 108;
+    pub const INTIMIDATE: i32 = 109;
+
+    /// Pairs of `(talent_id, rank)` that must be reached before this talent can be ranked up.
+    pub fn prerequisites(&self) -> Vec<(i32, i32)> {
+        let (ids, ranks) = (self.prereq_talent_ids.borrow(), self.prereq_talent_ranks.borrow());
+        ids.iter().zip(ranks.iter()).map(|(id, rank)| (id.get(), rank.get())).collect()
+    }
+
+    pub fn spent_talent_points(complex_talents: &[RcRef<ComplexTalent>]) -> i32 {
+        complex_talents.iter().map(|talent| talent.borrow().current_rank()).sum()
+    }
+
+    pub fn respec(talent_points: &RcCell<i32>, complex_talents: &[RcRef<ComplexTalent>]) {
+        for talent in complex_talents {
+            talent_points.update(|tp| tp + talent.borrow().current_rank());
+            talent.borrow_mut().set_current_rank(0);
+        }
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, RawUi)]
 pub struct Inventory {
@@ -104,8 +150,17 @@ pub struct Inventory {
     pub buy_pack: Vec<Item>,
 }
 
+impl Inventory {
+    pub const MAX_ITEMS: usize = 150;
+
+    /// Removes the items marked as junk from the inventory.
+    pub fn remove_junk(&mut self) {
+        self.inventory_mut().retain(|item| !item.borrow().junk.get());
+    }
+}
+
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Deserialize, Serialize, Copy, Clone, RawUi)]
+#[derive(Deserialize, Serialize, Copy, Clone, PartialEq, RawUi)]
 pub enum ItemLevel {
     None,
     I,
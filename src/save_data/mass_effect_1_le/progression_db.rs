@@ -0,0 +1,116 @@
+use serde::Deserialize;
+
+use super::{player::Player, squad::Henchman};
+
+#[derive(Deserialize)]
+pub struct Me1LeProgressionDb {
+    pub xp_per_level: Vec<i32>,
+    pub player_talent_points: Vec<i32>,
+    pub henchman_talent_points: Vec<i32>,
+}
+
+impl Me1LeProgressionDb {
+    pub fn max_level(&self) -> i32 {
+        self.xp_per_level.len() as i32
+    }
+
+    fn clamp_level(&self, level: i32) -> i32 {
+        level.clamp(1, self.max_level())
+    }
+
+    /// Total XP needed to reach `level`.
+    pub fn xp_for_level(&self, level: i32) -> f32 {
+        let level = self.clamp_level(level);
+        self.xp_per_level[level as usize - 1] as f32
+    }
+
+    fn granted_talent_points(talent_points: &[i32], level: i32) -> i32 {
+        talent_points.iter().take(level as usize).sum()
+    }
+
+    pub fn player_talent_points(&self, level: i32) -> i32 {
+        Self::granted_talent_points(&self.player_talent_points, self.clamp_level(level))
+    }
+
+    pub fn henchman_talent_points(&self, level: i32) -> i32 {
+        Self::granted_talent_points(&self.henchman_talent_points, self.clamp_level(level))
+    }
+
+    /// Sets the player level with the matching XP and unspent talent points.
+    pub fn set_player_level(&self, player: &mut Player, level: i32) {
+        let level = self.clamp_level(level);
+        player.set_level(level);
+        player.set_xp_level(level);
+
+        // Keep the XP progression toward the next level if it's still valid
+        let (min_xp, next_xp) = (self.xp_for_level(level), self.xp_for_level(level + 1));
+        let current_xp = player.current_xp();
+        if current_xp < min_xp || (level < self.max_level() && current_xp >= next_xp) {
+            player.set_current_xp(min_xp);
+        }
+
+        let spent = player.spent_talent_points();
+        player.set_talent_points((self.player_talent_points(level) - spent).max(0));
+    }
+
+    /// Sets the henchman level with the matching unspent talent points.
+    pub fn set_henchman_level(&self, henchman: &mut Henchman, level: i32) {
+        let level = self.clamp_level(level);
+        henchman.set_level(level);
+
+        let spent = henchman.spent_talent_points();
+        henchman.set_talent_points((self.henchman_talent_points(level) - spent).max(0));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::{save_data::mass_effect_1_le::Me1LeSaveGame, unreal};
+    use anyhow::Result;
+
+    #[test]
+    fn deserialize_progression_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_le_progression_db.ron")?;
+        let me1_le_progression_db: Me1LeProgressionDb = ron::from_str(&input)?;
+
+        let max_level = me1_le_progression_db.max_level() as usize;
+        assert_eq!(me1_le_progression_db.player_talent_points.len(), max_level);
+        assert_eq!(me1_le_progression_db.henchman_talent_points.len(), max_level);
+
+        Ok(())
+    }
+
+    #[test]
+    fn progression_matches_saves() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_le_progression_db.ron")?;
+        let db: Me1LeProgressionDb = ron::from_str(&input)?;
+
+        for file in ["test/ME1LeSave.pcsav", "test/ME1LeExport.pcsav"] {
+            let input = fs::read(file)?;
+            let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+            let save_data = me1_save_game.save_data.borrow();
+
+            let player = save_data.player();
+            let level = player.level();
+            assert!(db.xp_for_level(level) <= player.current_xp());
+            assert!(player.current_xp() < db.xp_for_level(level + 1));
+            assert_eq!(
+                db.player_talent_points(level),
+                player.spent_talent_points() + player.talent_points()
+            );
+
+            for henchman in save_data.squad().iter() {
+                let henchman = henchman.borrow();
+                assert_eq!(
+                    db.henchman_talent_points(henchman.level()),
+                    henchman.spent_talent_points() + henchman.talent_points()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -30,8 +30,19 @@ pub struct Henchman {
     attribute_secondary: u8,
     health: f32,
     shield: f32,
-    level: i32,
+    pub level: i32,
     helmet_shown: bool,
     current_quick_slot: u8,
     health_max: f32,
 }
+
+impl Henchman {
+    pub fn spent_talent_points(&self) -> i32 {
+        ComplexTalent::spent_talent_points(&self.complex_talents())
+    }
+
+    /// Resets all the complex talents and refunds their talent points.
+    pub fn respec(&mut self) {
+        ComplexTalent::respec(&self.talent_points, &self.complex_talents());
+    }
+}
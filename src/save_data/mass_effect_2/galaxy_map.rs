@@ -1,18 +1,81 @@
 use serde::{Deserialize, Serialize};
 
-use crate::save_data::shared::Vector2d;
+use crate::save_data::shared::{galaxy_db::GalaxyDb, Vector2d};
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
 pub struct GalaxyMap {
-    planets: Vec<Planet>,
+    pub planets: Vec<Planet>,
+}
+
+impl GalaxyMap {
+    /// Marks every planet visited, the planets of the database the save doesn't know yet are added.
+    pub fn mark_all_visited(&mut self, galaxy_db: &GalaxyDb) {
+        for galaxy_planet in galaxy_db.planets() {
+            if self.planets().iter().all(|planet| planet.borrow().id() != galaxy_planet.id) {
+                let planet = Planet::default();
+                planet.id.set(galaxy_planet.id);
+                self.planets_mut().push(planet.into());
+            }
+        }
+
+        for planet in self.planets().iter() {
+            planet.borrow().visited.set(true);
+        }
+    }
+
+    /// Clears the probe markers of a planet or every planet if `id` is `None`.
+    pub fn clear_probes(&self, id: Option<i32>) {
+        let planets = self.planets();
+        let planets =
+            planets.iter().filter(|planet| id.map_or(true, |id| planet.borrow().id() == id));
+        for planet in planets {
+            planet.borrow_mut().probes_mut().clear();
+        }
+    }
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", id)]
 pub struct Planet {
-    id: i32,
-    visited: bool,
-    probes: Vec<Vector2d>,
+    pub id: i32,
+    pub visited: bool,
+    pub probes: Vec<Vector2d>,
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    #[test]
+    fn mark_all_visited() -> Result<()> {
+        let galaxy_db = ron::from_str(
+            r#"(clusters: [(id: 99, systems: [(id: 990100, planets: [(id: 990101)])])])"#,
+        )?;
+
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        {
+            let mut galaxy_map = me2_save_game.galaxy_map.borrow_mut();
+            galaxy_map.mark_all_visited(&galaxy_db);
+            galaxy_map.clear_probes(None);
+        }
+
+        let output = unreal::Serializer::to_vec(&me2_save_game)?;
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let galaxy_map = me2_save_game.galaxy_map.borrow();
+
+        assert!(galaxy_map.planets().iter().any(|planet| planet.borrow().id() == 990101));
+        assert!(galaxy_map.planets().iter().all(|planet| {
+            let planet = planet.borrow();
+            planet.visited() && planet.probes().is_empty()
+        }));
+
+        Ok(())
+    }
 }
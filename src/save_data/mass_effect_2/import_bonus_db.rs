@@ -0,0 +1,68 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct LevelBonus {
+    /// Lowest ME1 level of the tier.
+    pub me1_level: i32,
+    pub me2_level: i32,
+    pub xp: f32,
+    pub resources: f32,
+}
+
+#[derive(Deserialize)]
+pub struct MoralityBonus {
+    /// Lowest ME1 paragon or renegade points of the tier.
+    pub me1_points: i32,
+    pub bonus: f32,
+}
+
+#[derive(Deserialize)]
+pub struct CreditsBonus {
+    /// Share of the ME1 credits.
+    pub share: f64,
+    pub bonus: f32,
+}
+
+#[derive(Deserialize)]
+pub struct Me2ImportBonusDb {
+    levels: Vec<LevelBonus>,
+    morality: Vec<MoralityBonus>,
+    credits: CreditsBonus,
+}
+
+impl Me2ImportBonusDb {
+    /// Bonus of the highest tier reached by `me1_level`, `None` below the known tiers.
+    pub fn level_bonus(&self, me1_level: i32) -> Option<&LevelBonus> {
+        self.levels.iter().rev().find(|tier| me1_level >= tier.me1_level)
+    }
+
+    /// Paragon or renegade bonus of `me1_points`, `None` below the known tiers.
+    pub fn morality_bonus(&self, me1_points: i32) -> Option<f32> {
+        self.morality.iter().rev().find(|tier| me1_points >= tier.me1_points).map(|tier| tier.bonus)
+    }
+
+    pub fn credits_bonus(&self, me1_credits: i32) -> f32 {
+        (me1_credits as f64 * self.credits.share).floor() as f32 + self.credits.bonus
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_import_bonus_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me2_import_bonus_db.ron")?;
+        let import_bonus_db: Me2ImportBonusDb = ron::from_str(&input)?;
+
+        // Tiers are searched from the highest
+        assert!(import_bonus_db.levels.windows(2).all(|w| w[0].me1_level < w[1].me1_level));
+        assert!(import_bonus_db.morality.windows(2).all(|w| w[0].me1_points < w[1].me1_points));
+
+        Ok(())
+    }
+}
@@ -1,14 +1,23 @@
-mod galaxy_map;
+pub mod galaxy_map;
+pub mod import_bonus_db;
 pub mod player;
+pub mod player_class_db;
 pub mod plot_db;
-mod squad;
+pub mod research_db;
+pub mod squad;
+pub mod squad_db;
+pub mod suicide_mission;
+pub mod weapon_db;
 
 use self::{galaxy_map::*, player::*, squad::*};
 
 use anyhow::Result;
 use serde::{de, Deserialize, Deserializer, Serialize};
 
+use self::import_bonus_db::Me2ImportBonusDb;
 use super::shared::{
+    location_db::SafeLocation,
+    morality_db::MoralityDb,
     plot::{Codex, Journal, PlotTable},
     Door, EndGameState, Kismet, Level, Rotator, SaveTimeStamp, StreamingState, Vector,
 };
@@ -21,28 +30,52 @@ pub struct Me2SaveGame {
     debug_name: String,
     seconds_played: f32,
     disc: i32,
-    base_level_name: String,
+    pub base_level_name: String,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
     timestamp: SaveTimeStamp,
-    location: Vector,
+    pub location: Vector,
     rotation: Rotator,
     current_loading_tip: i32,
-    levels: Vec<Level>,
-    streaming_states: Vec<StreamingState>,
-    kismet_records: Vec<Kismet>,
-    doors: Vec<Door>,
-    pawns: Vec<Guid>,
+    pub levels: Vec<Level>,
+    pub streaming_states: Vec<StreamingState>,
+    pub kismet_records: Vec<Kismet>,
+    pub doors: Vec<Door>,
+    pub pawns: Vec<Guid>,
     pub player: Player,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
-    journal: Journal,
-    codex: Codex,
+    pub journal: Journal,
+    pub codex: Codex,
     pub me1_plot: PlotTable,
-    galaxy_map: GalaxyMap,
+    pub galaxy_map: GalaxyMap,
     dependant_dlcs: Vec<DependentDlc>,
 }
 
+impl Me2SaveGame {
+    /// Moves the player to a known location, the streamed levels are replaced along.
+    pub fn relocate(&mut self, spot: &SafeLocation) {
+        *self.base_level_name.borrow_mut() = spot.base_level_name.clone();
+        *self.location.borrow_mut() = spot.location();
+        *self.rotation.borrow_mut() = spot.rotation();
+        *self.levels.borrow_mut() = spot.levels();
+        *self.streaming_states.borrow_mut() = spot.streaming_states();
+    }
+
+    /// The current location, named after its level.
+    pub fn current_spot(&self) -> SafeLocation {
+        SafeLocation::capture(
+            self.base_level_name().clone(),
+            self.base_level_name().clone(),
+            None,
+            &self.location(),
+            &self.rotation.borrow(),
+            &self.levels(),
+            &self.streaming_states(),
+        )
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me2Version {
     version: i32,
@@ -80,39 +113,117 @@ pub struct Me2LeSaveGame {
     debug_name: String,
     seconds_played: f32,
     disc: i32,
-    base_level_name: String,
+    pub base_level_name: String,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
     timestamp: SaveTimeStamp,
-    location: Vector,
+    pub location: Vector,
     rotation: Rotator,
     current_loading_tip: i32,
-    levels: Vec<Level>,
-    streaming_states: Vec<StreamingState>,
-    kismet_records: Vec<Kismet>,
-    doors: Vec<Door>,
-    pawns: Vec<Guid>,
+    pub levels: Vec<Level>,
+    pub streaming_states: Vec<StreamingState>,
+    pub kismet_records: Vec<Kismet>,
+    pub doors: Vec<Door>,
+    pub pawns: Vec<Guid>,
     pub player: Player,
-    me1_import_bonus: Me1ImportBonus,
-    squad: Vec<Henchman>,
+    pub me1_import_bonus: Me1ImportBonus,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
-    journal: Journal,
-    codex: Codex,
+    pub journal: Journal,
+    pub codex: Codex,
     pub me1_plot: PlotTable,
-    galaxy_map: GalaxyMap,
+    pub galaxy_map: GalaxyMap,
     dependant_dlcs: Vec<DependentDlc>,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
-struct Me1ImportBonus {
-    imported_me1_level: i32,
-    starting_me2_level: i32,
-    bonus_xp: f32,
-    bonus_credits: f32,
-    bonus_resources: f32,
-    bonus_paragon: f32,
-    bonus_renegade: f32,
+pub struct Me1ImportBonus {
+    pub imported_me1_level: i32,
+    pub starting_me2_level: i32,
+    pub bonus_xp: f32,
+    pub bonus_credits: f32,
+    pub bonus_resources: f32,
+    pub bonus_paragon: f32,
+    pub bonus_renegade: f32,
+}
+
+impl Me2LeSaveGame {
+    /// Recomputes the bonus from the imported ME1 level, credits and the ME1 paragon and renegade.
+    /// Values the known tiers don't cover are left as is, so are the credits if `me1_credits` isn't given.
+    pub fn recompute_me1_import_bonus(
+        &self, import_bonus_db: &Me2ImportBonusDb, me1_morality_db: &MoralityDb,
+        me1_credits: Option<i32>,
+    ) {
+        let bonus = self.me1_import_bonus();
+        if let Some(tier) = import_bonus_db.level_bonus(bonus.imported_me1_level()) {
+            bonus.starting_me2_level.set(tier.me2_level);
+            bonus.bonus_xp.set(tier.xp);
+            bonus.bonus_resources.set(tier.resources);
+        }
+        if let Some(me1_credits) = me1_credits {
+            bonus.bonus_credits.set(import_bonus_db.credits_bonus(me1_credits));
+        }
+
+        let me1_plot = self.me1_plot();
+        let me1_integers = me1_plot.integers();
+        for (idx, value) in [
+            (me1_morality_db.paragon, &bonus.bonus_paragon),
+            (me1_morality_db.renegade, &bonus.bonus_renegade),
+        ] {
+            let points = me1_integers.get(idx).map(|points| points.get());
+            if let Some(bonus) = points.and_then(|points| import_bonus_db.morality_bonus(points)) {
+                value.set(bonus);
+            }
+        }
+    }
+
+    /// Adds the bonus to the credits, resources, paragon and renegade, like the game does on import.
+    pub fn apply_me1_import_bonus(&self, morality_db: &MoralityDb) {
+        let bonus = self.me1_import_bonus();
+        let player = self.player();
+
+        let credits = bonus.bonus_credits() as i32;
+        player.credits.update(|value| value + credits);
+
+        let resources = bonus.bonus_resources() as i32;
+        for resource in [&player.eezo, &player.iridium, &player.palladium, &player.platinum] {
+            resource.update(|value| value + resources);
+        }
+
+        let plot = self.plot();
+        let integers = plot.integers();
+        for (idx, bonus) in [
+            (morality_db.paragon, bonus.bonus_paragon()),
+            (morality_db.renegade, bonus.bonus_renegade()),
+        ] {
+            if let Some(points) = integers.get(idx) {
+                points.update(|points| points + bonus as i32);
+            }
+        }
+    }
+
+    /// Moves the player to a known location, the streamed levels are replaced along.
+    pub fn relocate(&mut self, spot: &SafeLocation) {
+        *self.base_level_name.borrow_mut() = spot.base_level_name.clone();
+        *self.location.borrow_mut() = spot.location();
+        *self.rotation.borrow_mut() = spot.rotation();
+        *self.levels.borrow_mut() = spot.levels();
+        *self.streaming_states.borrow_mut() = spot.streaming_states();
+    }
+
+    /// The current location, named after its level.
+    pub fn current_spot(&self) -> SafeLocation {
+        SafeLocation::capture(
+            self.base_level_name().clone(),
+            self.base_level_name().clone(),
+            None,
+            &self.location(),
+            &self.rotation.borrow(),
+            &self.levels(),
+            &self.streaming_states(),
+        )
+    }
 }
 
 #[derive(Serialize, Clone)]
@@ -160,7 +271,7 @@ mod test {
     use crc::{Crc, CRC_32_BZIP2};
 
     use super::*;
-    use crate::unreal;
+    use crate::{save_data::mass_effect_1_le::Me1LeSaveGame, unreal};
 
     #[test]
     fn deserialize_serialize_vanilla() -> Result<()> {
@@ -256,4 +367,62 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn apply_me1_import_bonus() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let import_bonus_db: Me2ImportBonusDb =
+            ron::from_str(&fs::read_to_string("databases/me2_import_bonus_db.ron")?)?;
+        let me1_morality_db: MoralityDb =
+            ron::from_str(&fs::read_to_string("databases/me1_morality_db.ron")?)?;
+        let morality_db: MoralityDb =
+            ron::from_str(&fs::read_to_string("databases/me2_morality_db.ron")?)?;
+
+        // The character imported in this save
+        let input = fs::read("test/ME1LeExport.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let me1_credits = me1_save_game.save_data.borrow().player().credits();
+
+        // The recomputed bonus matches the one granted by the game
+        let bonus_values = |bonus: &Me1ImportBonus| {
+            (
+                bonus.starting_me2_level(),
+                bonus.bonus_xp(),
+                bonus.bonus_credits(),
+                bonus.bonus_resources(),
+                bonus.bonus_paragon(),
+                bonus.bonus_renegade(),
+            )
+        };
+        let granted = bonus_values(&me2_save_game.me1_import_bonus());
+        {
+            let bonus = me2_save_game.me1_import_bonus();
+            bonus.bonus_xp.set(0.0);
+            bonus.bonus_credits.set(0.0);
+            bonus.bonus_paragon.set(0.0);
+        }
+        me2_save_game.recompute_me1_import_bonus(
+            &import_bonus_db,
+            &me1_morality_db,
+            Some(me1_credits),
+        );
+        assert_eq!(bonus_values(&me2_save_game.me1_import_bonus()), granted);
+
+        // The bonus is added to what the player has
+        let player = me2_save_game.player();
+        let (credits, eezo) = (player.credits(), player.eezo());
+        let paragon = me2_save_game.plot().integers()[morality_db.paragon].get();
+        me2_save_game.apply_me1_import_bonus(&morality_db);
+
+        let bonus = me2_save_game.me1_import_bonus();
+        assert_eq!(player.credits(), credits + bonus.bonus_credits() as i32);
+        assert_eq!(player.eezo(), eezo + bonus.bonus_resources() as i32);
+        assert_eq!(
+            me2_save_game.plot().integers()[morality_db.paragon].get(),
+            paragon + bonus.bonus_paragon() as i32
+        );
+
+        Ok(())
+    }
 }
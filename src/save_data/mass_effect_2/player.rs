@@ -17,13 +17,13 @@ pub struct Player {
     pub origin: Origin,
     pub notoriety: Notoriety,
     pub talent_points: i32,
-    mapped_power_1: String,
-    mapped_power_2: String,
-    mapped_power_3: String,
+    pub mapped_power_1: String,
+    pub mapped_power_2: String,
+    pub mapped_power_3: String,
     pub appearance: Appearance,
     pub powers: Vec<Power>,
     weapons: Vec<Weapon>,
-    weapons_loadout: WeaponLoadout,
+    pub weapons_loadout: WeaponLoadout,
     hotkeys: Vec<Hotkey>,
     pub credits: i32,
     pub medigel: i32,
@@ -34,7 +34,7 @@ pub struct Player {
     pub probes: i32,
     pub current_fuel: f32,
     pub face_code: String,
-    localized_class_name: i32,
+    pub localized_class_name: i32,
 }
 
 #[rcize_fields]
@@ -42,9 +42,16 @@ pub struct Player {
 #[display(fmt = "{}", name)]
 pub struct Power {
     pub name: String,
-    rank: f32,
+    pub rank: f32,
     pub power_class_name: String,
-    wheel_display_index: i32,
+    pub wheel_display_index: i32,
+}
+
+impl Power {
+    /// Talent points needed to reach `rank`, each rank costs its number of points.
+    pub fn rank_cost(rank: i32) -> i32 {
+        rank * (rank + 1) / 2
+    }
 }
 
 #[rcize_fields]
@@ -0,0 +1,180 @@
+use anyhow::{bail, Result};
+use serde::Deserialize;
+
+use super::{
+    player::{Player, Power},
+    squad_db::EVOLVED_RANK,
+};
+
+#[derive(Deserialize)]
+pub struct Me2ClassPower {
+    pub name: String,
+    pub power_class_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct Me2PlayerClass {
+    pub class_name: String,
+    pub localized_class_name: i32,
+    #[serde(default)]
+    pub powers: Vec<Me2ClassPower>,
+}
+
+impl Me2PlayerClass {
+    /// A class power or one of its evolutions, named after it like `EvolvedWarpProjectile_Heavy`.
+    fn owns(&self, power: &Power) -> bool {
+        let name = power.name();
+        let evolved_name = name.strip_prefix("Evolved").unwrap_or(&name);
+        self.powers.iter().any(|class_power| {
+            let is_evolution = power.rank() >= EVOLVED_RANK
+                && evolved_name
+                    .strip_prefix(class_power.name.as_str())
+                    .map_or(false, |rest| rest.is_empty() || rest.starts_with('_'));
+            is_evolution
+                || class_power.power_class_name.eq_ignore_ascii_case(&power.power_class_name())
+        })
+    }
+}
+
+#[derive(Deserialize, Deref)]
+pub struct Me2PlayerClassDb(Vec<Me2PlayerClass>);
+
+impl Me2PlayerClassDb {
+    pub fn class(&self, class_name: &str) -> Option<&Me2PlayerClass> {
+        self.iter().find(|class| class.class_name.eq_ignore_ascii_case(class_name))
+    }
+
+    /// Changes the class of the player, replacing the old class powers by the new ones at rank 0.
+    /// Their talent points are refunded, bonus powers are kept.
+    /// If the powers of one of the classes aren't known nothing is changed and an error is returned.
+    pub fn change_class(&self, player: &mut Player, class_name: &str) -> Result<()> {
+        let new_class = match self.class(class_name) {
+            Some(new_class) => new_class,
+            None => bail!("Unknown class: {}", class_name),
+        };
+        let old_class = match self.class(&player.class_name()) {
+            Some(old_class) if !old_class.powers.is_empty() && !new_class.powers.is_empty() => {
+                old_class
+            }
+            _ => bail!("The class powers aren't in the database, the class wasn't changed"),
+        };
+
+        *player.class_name_mut() = new_class.class_name.clone();
+        player.set_localized_class_name(new_class.localized_class_name);
+
+        // Old class powers
+        let mut refund = 0;
+        let mut removed = Vec::new();
+        player.powers_mut().retain(|power| {
+            let power = power.borrow();
+            let is_class_power = old_class.owns(&power);
+            if is_class_power {
+                refund += Power::rank_cost(power.rank() as i32);
+                removed.push(power.power_class_name().clone());
+            }
+            !is_class_power
+        });
+        player.talent_points.update(|tp| tp + refund);
+
+        // New class powers, unless the player already has them as bonus powers
+        for (idx, class_power) in new_class.powers.iter().enumerate() {
+            let has_power = player.powers().iter().any(|power| {
+                power
+                    .borrow()
+                    .power_class_name()
+                    .eq_ignore_ascii_case(&class_power.power_class_name)
+            });
+            if has_power {
+                continue;
+            }
+
+            let power = Power::default();
+            *power.name.borrow_mut() = class_power.name.clone();
+            *power.power_class_name.borrow_mut() = class_power.power_class_name.clone();
+            power.wheel_display_index.set(idx as i32);
+            player.powers_mut().push(power.into());
+        }
+
+        // Mapped powers are stored without their package
+        for mapped_power in [&player.mapped_power_1, &player.mapped_power_2, &player.mapped_power_3]
+        {
+            let mut mapped_power = mapped_power.borrow_mut();
+            let is_removed = removed.iter().any(|class_name| {
+                let class_name = class_name.rsplit('.').next().unwrap_or_default();
+                class_name.eq_ignore_ascii_case(&mapped_power)
+            });
+            if is_removed {
+                *mapped_power = "None".to_owned();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2LeSaveGame, unreal};
+
+    fn player_class_db() -> Result<Me2PlayerClassDb> {
+        let input = fs::read_to_string("databases/me2_player_class_db.ron")?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn change_class() -> Result<()> {
+        let player_class_db = player_class_db()?;
+        let adept = "SFXGame.SFXPawn_PlayerAdept";
+
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let spent = {
+            let mut player = me2_save_game.player.borrow_mut();
+            let spent: i32 = player
+                .powers()
+                .iter()
+                .filter(|power| player_class_db.class(adept).unwrap().owns(&power.borrow()))
+                .map(|power| Power::rank_cost(power.borrow().rank() as i32))
+                .sum();
+
+            // The Soldier powers aren't known, nothing is changed
+            let powers = player.powers().len();
+            let soldier = "SFXGame.SFXPawn_PlayerSoldier";
+            assert!(player_class_db.change_class(&mut player, soldier).is_err());
+            assert_eq!(*player.class_name(), adept);
+            assert_eq!(player.powers().len(), powers);
+            assert_eq!(player.talent_points(), 0);
+
+            // Back to the Adept powers at rank 0
+            player_class_db.change_class(&mut player, adept)?;
+            spent
+        };
+
+        let output = unreal::Serializer::to_vec(&me2_save_game)?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let player = me2_save_game.player();
+        assert_eq!(*player.class_name(), adept);
+        assert_eq!(player.localized_class_name(), 93954);
+        assert_eq!(player.talent_points(), spent);
+        assert_eq!(*player.mapped_power_1(), "None");
+
+        // Class powers are back to rank 0 without evolutions, bonus and melee powers are kept
+        let class_powers: Vec<_> = player
+            .powers()
+            .iter()
+            .filter(|power| player_class_db.class(adept).unwrap().owns(&power.borrow()))
+            .cloned()
+            .collect();
+        assert_eq!(class_powers.len(), 6);
+        assert!(class_powers.iter().all(|power| power.borrow().rank() == 0.0));
+        assert!(player.powers().iter().any(|power| *power.borrow().name() == "WarpAmmo"));
+        assert!(player.powers().iter().any(|power| *power.borrow().name() == "FirstAid"));
+
+        Ok(())
+    }
+}
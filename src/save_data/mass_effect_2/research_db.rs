@@ -0,0 +1,276 @@
+use anyhow::{bail, Result};
+use serde::Deserialize;
+
+use super::player::Player;
+use crate::save_data::{shared::plot::PlotTable, RcCell};
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Display)]
+pub enum Me2Resource {
+    Eezo,
+    Iridium,
+    Palladium,
+    Platinum,
+}
+
+impl Me2Resource {
+    pub const ALL: [Me2Resource; 4] =
+        [Me2Resource::Eezo, Me2Resource::Iridium, Me2Resource::Palladium, Me2Resource::Platinum];
+
+    pub fn amount(self, player: &Player) -> &RcCell<i32> {
+        match self {
+            Me2Resource::Eezo => &player.eezo,
+            Me2Resource::Iridium => &player.iridium,
+            Me2Resource::Palladium => &player.palladium,
+            Me2Resource::Platinum => &player.platinum,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub enum Me2Prerequisite {
+    Upgrade(String),
+    Plot(usize, String),
+}
+
+#[derive(Deserialize)]
+pub struct Me2Upgrade {
+    pub name: String,
+    pub category: String,
+    #[serde(default)]
+    pub discovered: Option<usize>,
+    #[serde(default)]
+    pub booleans: Vec<usize>,
+    pub integer: (usize, i32),
+    #[serde(default)]
+    pub cost: Option<(Me2Resource, i32)>,
+    #[serde(default)]
+    pub prerequisites: Vec<Me2Prerequisite>,
+}
+
+impl Me2Upgrade {
+    // The game resets some of the booleans once an upgrade is bought, only the integer is reliable
+    pub fn is_unlocked(&self, plot: &PlotTable) -> bool {
+        let (idx, value) = self.integer;
+        plot.integers().get(idx).map_or(false, |integer| integer.get() >= value)
+    }
+
+    fn set_unlocked(&self, plot: &mut PlotTable) {
+        for idx in self.discovered.iter().chain(&self.booleans).copied() {
+            let mut booleans = plot.booleans_mut();
+            if idx >= booleans.len() {
+                booleans.resize(idx + 1, false);
+            }
+            booleans.set(idx, true);
+        }
+
+        let (idx, value) = self.integer;
+        let mut integers = plot.integers_mut();
+        if idx >= integers.len() {
+            integers.resize_with(idx + 1, Default::default);
+        }
+        integers[idx].update(|integer| integer.max(value));
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Me2ResearchDb {
+    upgrades: Vec<Me2Upgrade>,
+}
+
+impl Me2ResearchDb {
+    pub fn upgrades(&self) -> &[Me2Upgrade] {
+        &self.upgrades
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.upgrades.iter().position(|upgrade| upgrade.name == name)
+    }
+
+    /// Upgrades that still have to be unlocked for `idx` to be, prerequisites first.
+    pub fn pending(&self, plot: &PlotTable, idx: usize) -> Vec<usize> {
+        let mut pending = Vec::new();
+        self.add_pending(plot, idx, &mut pending);
+        pending
+    }
+
+    fn add_pending(&self, plot: &PlotTable, idx: usize, pending: &mut Vec<usize>) {
+        let upgrade = &self.upgrades[idx];
+        if pending.contains(&idx) || upgrade.is_unlocked(plot) {
+            return;
+        }
+
+        for prerequisite in &upgrade.prerequisites {
+            if let Me2Prerequisite::Upgrade(name) = prerequisite {
+                if let Some(prerequisite) = self.position(name) {
+                    self.add_pending(plot, prerequisite, pending);
+                }
+            }
+        }
+        pending.push(idx);
+    }
+
+    /// Total cost of the upgrades, by resource.
+    pub fn cost(&self, upgrades: &[usize]) -> Vec<(Me2Resource, i32)> {
+        Me2Resource::ALL
+            .into_iter()
+            .map(|resource| {
+                let amount = upgrades
+                    .iter()
+                    .filter_map(|&idx| self.upgrades[idx].cost)
+                    .filter(|&(other, _)| other == resource)
+                    .map(|(_, amount)| amount)
+                    .sum();
+                (resource, amount)
+            })
+            .filter(|&(_, amount)| amount > 0)
+            .collect()
+    }
+
+    /// Unlocks the upgrades with the upgrades they require.
+    /// If a player is given their cost is taken from its resources, nothing is unlocked if they can't be paid for.
+    pub fn unlock(
+        &self, plot: &mut PlotTable, player: Option<&Player>,
+        upgrades: impl IntoIterator<Item = usize>,
+    ) -> Result<()> {
+        let mut pending = Vec::new();
+        for idx in upgrades {
+            self.add_pending(plot, idx, &mut pending);
+        }
+
+        if let Some(player) = player {
+            let cost = self.cost(&pending);
+            for &(resource, amount) in &cost {
+                let available = resource.amount(player).get();
+                if available < amount {
+                    bail!("Not enough {}: {} needed, {} available", resource, amount, available);
+                }
+            }
+            for (resource, amount) in cost {
+                resource.amount(player).update(|available| available - amount);
+            }
+        }
+
+        for idx in pending {
+            self.upgrades[idx].set_unlocked(plot);
+        }
+        Ok(())
+    }
+
+    pub fn unlock_all(&self, plot: &mut PlotTable, player: Option<&Player>) -> Result<()> {
+        self.unlock(plot, player, 0..self.upgrades.len())
+    }
+
+    /// Unlocked upgrades whose prerequisites are missing.
+    pub fn inconsistencies(&self, plot: &PlotTable) -> Vec<String> {
+        let mut inconsistencies = Vec::new();
+        for upgrade in &self.upgrades {
+            if !upgrade.is_unlocked(plot) {
+                continue;
+            }
+
+            for prerequisite in &upgrade.prerequisites {
+                let missing = match prerequisite {
+                    Me2Prerequisite::Upgrade(name) => self
+                        .position(name)
+                        .filter(|&prerequisite| !self.upgrades[prerequisite].is_unlocked(plot))
+                        .map(|_| name),
+                    Me2Prerequisite::Plot(plot_idx, label) => {
+                        let set = plot.booleans().get(*plot_idx).map_or(false, |b| *b);
+                        (!set).then(|| label)
+                    }
+                };
+                if let Some(missing) = missing {
+                    inconsistencies
+                        .push(format!("{} is unlocked without {}", upgrade.name, missing));
+                }
+            }
+        }
+        inconsistencies
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2LeSaveGame, unreal};
+
+    #[test]
+    fn deserialize_research_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me2_research_db.ron")?;
+        let me2_research_db: Me2ResearchDb = ron::from_str(&input)?;
+
+        // Prerequisites come first so unlocking in order never skips one
+        for (idx, upgrade) in me2_research_db.upgrades().iter().enumerate() {
+            assert_eq!(me2_research_db.position(&upgrade.name), Some(idx));
+            for prerequisite in &upgrade.prerequisites {
+                if let Me2Prerequisite::Upgrade(name) = prerequisite {
+                    assert!(me2_research_db.position(name).unwrap() < idx);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn unlock_research() -> Result<()> {
+        let input = fs::read_to_string("databases/me2_research_db.ron")?;
+        let me2_research_db: Me2ResearchDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let mut plot = me2_save_game.plot.borrow_mut();
+
+        // A save from the game is consistent
+        assert!(me2_research_db.inconsistencies(&plot).is_empty());
+
+        // A rank pulls the previous ones
+        let rank_1 = me2_research_db.position("Damage (Kinetic Pulsar) 1").unwrap();
+        let rank_3 = me2_research_db.position("Damage (Kinetic Pulsar) 3").unwrap();
+        let (idx, _) = me2_research_db.upgrades()[rank_1].integer;
+        plot.integers()[idx].set(0);
+        assert_eq!(me2_research_db.pending(&plot, rank_3), [rank_1, rank_1 + 1, rank_3]);
+        me2_research_db.unlock(&mut plot, None, [rank_3])?;
+        assert_eq!(plot.integers()[idx].get(), 3);
+
+        // Legion isn't recruited in this save
+        me2_research_db.unlock_all(&mut plot, None)?;
+        assert!(me2_research_db.upgrades().iter().all(|upgrade| upgrade.is_unlocked(&plot)));
+        let inconsistencies = me2_research_db.inconsistencies(&plot);
+        assert_eq!(inconsistencies.len(), 2);
+        assert!(inconsistencies.iter().all(|inconsistency| inconsistency.starts_with("[Legion]")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn deduct_resources() -> Result<()> {
+        let input = r#"(upgrades: [
+            (name: "A", category: "", booleans: [10000], integer: (10000, 1), cost: Some((Iridium, 100))),
+            (name: "B", category: "", integer: (10001, 2), cost: Some((Iridium, 50)), prerequisites: [Upgrade("A")]),
+        ])"#;
+        let me2_research_db: Me2ResearchDb = ron::from_str(input)?;
+
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let mut plot = me2_save_game.plot.borrow_mut();
+        let player = me2_save_game.player();
+
+        player.iridium.set(120);
+        assert!(me2_research_db.unlock(&mut plot, Some(&player), [1]).is_err());
+        assert_eq!(player.iridium(), 120);
+        assert!(!me2_research_db.upgrades()[0].is_unlocked(&plot));
+
+        player.iridium.set(200);
+        me2_research_db.unlock(&mut plot, Some(&player), [1])?;
+        assert_eq!(player.iridium(), 50);
+        assert!(plot.booleans()[10000]);
+        assert!(me2_research_db.upgrades().iter().all(|upgrade| upgrade.is_unlocked(&plot)));
+
+        Ok(())
+    }
+}
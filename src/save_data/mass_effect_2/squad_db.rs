@@ -0,0 +1,225 @@
+use serde::Deserialize;
+
+use super::{player::Power, squad::Henchman, suicide_mission::Member};
+use crate::save_data::RcRef;
+
+/// Rank of an evolved power, base powers go from 0 to 3.
+pub const EVOLVED_RANK: f32 = 4.0;
+
+#[derive(Deserialize)]
+pub struct Me2SquadMember {
+    pub tag: String,
+    pub name: String,
+    #[serde(default)]
+    pub member: Option<Member>,
+}
+
+#[derive(Deserialize)]
+pub struct Me2Evolution {
+    pub power: String,
+    pub name: String,
+    pub class_name: String,
+    pub label: String,
+}
+
+#[derive(Deserialize)]
+pub struct Me2SquadDb {
+    henchmen: Vec<Me2SquadMember>,
+    evolutions: Vec<Me2Evolution>,
+}
+
+impl Me2SquadDb {
+    pub fn henchmen(&self) -> &[Me2SquadMember] {
+        &self.henchmen
+    }
+
+    pub fn henchman(&self, tag: &str) -> Option<&Me2SquadMember> {
+        self.henchmen.iter().find(|henchman| henchman.tag.eq_ignore_ascii_case(tag))
+    }
+
+    /// Name of a squadmate, the tag if it's unknown.
+    pub fn name(&self, tag: &str) -> String {
+        self.henchman(tag).map(|henchman| henchman.name.clone()).unwrap_or_else(|| tag.to_owned())
+    }
+
+    /// Known evolutions of a base power, by its class name.
+    pub fn evolutions(&self, power_class_name: &str) -> Vec<&Me2Evolution> {
+        self.evolutions
+            .iter()
+            .filter(|evolution| evolution.power.eq_ignore_ascii_case(power_class_name))
+            .collect()
+    }
+
+    pub fn is_evolution(&self, power: &Power) -> bool {
+        power.rank() >= EVOLVED_RANK
+            || self.evolutions.iter().any(|evolution| {
+                evolution.class_name.eq_ignore_ascii_case(&power.power_class_name())
+            })
+    }
+
+    /// Powers ranked from 0 to 3, their evolutions aside.
+    pub fn base_powers(&self, henchman: &Henchman) -> Vec<RcRef<Power>> {
+        henchman
+            .powers()
+            .iter()
+            .filter(|power| !self.is_evolution(&power.borrow()))
+            .cloned()
+            .collect()
+    }
+
+    /// Evolution taken by a base power, unknown evolutions are matched by name.
+    pub fn evolution(&self, henchman: &Henchman, base: &Power) -> Option<RcRef<Power>> {
+        let evolutions = self.evolutions(&base.power_class_name());
+        henchman
+            .powers()
+            .iter()
+            .find(|power| {
+                let power = power.borrow();
+                let class_name = power.power_class_name();
+                if evolutions
+                    .iter()
+                    .any(|evolution| evolution.class_name.eq_ignore_ascii_case(&class_name))
+                {
+                    return true;
+                }
+                let is_known = self
+                    .evolutions
+                    .iter()
+                    .any(|evolution| evolution.class_name.eq_ignore_ascii_case(&class_name));
+                !is_known
+                    && power.rank() >= EVOLVED_RANK
+                    && power.name().contains(base.name().as_str())
+            })
+            .cloned()
+    }
+
+    /// Sets a base power from rank 0 to 3, removing its evolution.
+    pub fn set_rank(&self, henchman: &mut Henchman, base: &RcRef<Power>, rank: i32) {
+        if let Some(evolution) = self.evolution(henchman, &base.borrow()) {
+            henchman.powers_mut().retain(|power| *power != evolution);
+        }
+        base.borrow().rank.set(rank.clamp(0, 3) as f32);
+    }
+
+    /// Evolves a base power, replacing the evolution it already has.
+    pub fn evolve(&self, henchman: &mut Henchman, base: &RcRef<Power>, evolution: &Me2Evolution) {
+        self.set_rank(henchman, base, 0);
+
+        let power = Power::default();
+        *power.name.borrow_mut() = evolution.name.clone();
+        power.rank.set(EVOLVED_RANK);
+        *power.power_class_name.borrow_mut() = evolution.class_name.clone();
+        power.wheel_display_index.set(base.borrow().wheel_display_index());
+        henchman.powers_mut().push(power.into());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::mass_effect_2::{Me2LeSaveGame, Me2SaveGame},
+        unreal,
+    };
+
+    fn squad_db() -> Result<Me2SquadDb> {
+        let input = fs::read_to_string("databases/me2_squad_db.ron")?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn deserialize_squad_db() -> Result<()> {
+        let squad_db = squad_db()?;
+
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Every squadmate of the save is known and every evolution is linked to its base power
+        for henchman in me2_save_game.squad().iter() {
+            let henchman = henchman.borrow();
+            assert!(squad_db.henchman(&henchman.tag()).is_some());
+
+            let evolutions = henchman
+                .powers()
+                .iter()
+                .filter(|power| squad_db.is_evolution(&power.borrow()))
+                .count();
+            let linked = squad_db
+                .base_powers(&henchman)
+                .iter()
+                .filter(|base| squad_db.evolution(&henchman, &base.borrow()).is_some())
+                .count();
+            assert_eq!(evolutions, linked);
+
+            // Both evolutions of every squad passive are known, Kenson isn't in the squad
+            for base in squad_db.base_powers(&henchman) {
+                let base = base.borrow();
+                if henchman.tag().starts_with("hench_") && base.name().ends_with("Passive") {
+                    assert_eq!(squad_db.evolutions(&base.power_class_name()).len(), 2);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn evolve_powers() -> Result<()> {
+        let squad_db = squad_db()?;
+
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        {
+            let squad = me2_save_game.squad();
+            let samara = squad.iter().find(|henchman| *henchman.borrow().tag() == "hench_mystic");
+            let mut samara = samara.unwrap().borrow_mut();
+            let base_powers = squad_db.base_powers(&samara);
+            let passive =
+                base_powers.iter().find(|power| *power.borrow().name() == "SamaraPassive").unwrap();
+            let reave = base_powers.iter().find(|power| *power.borrow().name() == "Reave").unwrap();
+
+            // Samara's passive takes the other branch, Reave goes back to rank 1
+            let evolutions = squad_db.evolutions(&passive.borrow().power_class_name());
+            let evolution = evolutions
+                .iter()
+                .find(|evolution| evolution.class_name.ends_with("_Evolved1"))
+                .unwrap();
+            squad_db.evolve(&mut samara, passive, evolution);
+            squad_db.set_rank(&mut samara, reave, 1);
+        }
+
+        let output = unreal::Serializer::to_vec(&me2_save_game)?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let squad = me2_save_game.squad();
+        let samara = squad.iter().find(|henchman| *henchman.borrow().tag() == "hench_mystic");
+        let samara = samara.unwrap().borrow();
+        let base_powers = squad_db.base_powers(&samara);
+        let passive =
+            base_powers.iter().find(|power| *power.borrow().name() == "SamaraPassive").unwrap();
+        let reave = base_powers.iter().find(|power| *power.borrow().name() == "Reave").unwrap();
+
+        assert_eq!(passive.borrow().rank(), 0.0);
+        let evolution = squad_db.evolution(&samara, &passive.borrow()).unwrap();
+        assert!(evolution.borrow().power_class_name().ends_with("SamaraPassive_Evolved1"));
+        assert_eq!(
+            evolution.borrow().wheel_display_index(),
+            passive.borrow().wheel_display_index()
+        );
+        assert_eq!(reave.borrow().rank(), 1.0);
+        assert!(squad_db.evolution(&samara, &reave.borrow()).is_none());
+
+        // The passive keeps a single evolution
+        let evolutions = samara
+            .powers()
+            .iter()
+            .filter(|power| *power.borrow().name() == "SamaraPassive")
+            .count();
+        assert_eq!(evolutions, 2);
+
+        Ok(())
+    }
+}
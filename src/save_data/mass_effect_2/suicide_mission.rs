@@ -0,0 +1,850 @@
+//! Predicts who survives the Collector base from the plots of a save.
+//!
+//! The rules are the ones documented by the community from the game scripts:
+//! ship upgrades, specialists, escort, final squad and "hold the line" defense scores.
+
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::save_data::shared::plot::PlotTable;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Member {
+    Garrus,
+    Grunt,
+    Jack,
+    Jacob,
+    Kasumi,
+    Legion,
+    Miranda,
+    Mordin,
+    Samara,
+    Morinth,
+    Tali,
+    Thane,
+    Zaeed,
+}
+
+impl fmt::Display for Member {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl Member {
+    pub const ALL: [Member; 13] = [
+        Member::Garrus,
+        Member::Grunt,
+        Member::Jack,
+        Member::Jacob,
+        Member::Kasumi,
+        Member::Legion,
+        Member::Miranda,
+        Member::Mordin,
+        Member::Samara,
+        Member::Morinth,
+        Member::Tali,
+        Member::Thane,
+        Member::Zaeed,
+    ];
+
+    /// (recruited, loyal, dead, in current squad) boolean ids, Morinth takes Samara's ones.
+    pub fn plots(self) -> (usize, usize, usize, usize) {
+        match self {
+            Member::Garrus => (38, 183, 200, 26),
+            Member::Grunt => (42, 189, 204, 30),
+            Member::Jack => (35, 179, 197, 23),
+            Member::Jacob => (34, 178, 196, 22),
+            Member::Kasumi => (37, 182, 199, 25),
+            Member::Legion => (36, 180, 198, 24),
+            Member::Miranda => (33, 177, 195, 21),
+            Member::Mordin => (41, 188, 203, 29),
+            Member::Samara | Member::Morinth => (43, 191, 205, 31),
+            Member::Tali => (40, 186, 202, 28),
+            Member::Thane => (39, 185, 201, 27),
+            Member::Zaeed => (44, 192, 206, 32),
+        }
+    }
+
+    /// Contribution to the "hold the line" defense, without loyalty.
+    fn defense(self) -> i32 {
+        match self {
+            Member::Garrus | Member::Grunt | Member::Zaeed => 3,
+            Member::Jacob
+            | Member::Legion
+            | Member::Miranda
+            | Member::Samara
+            | Member::Morinth
+            | Member::Thane => 1,
+            Member::Jack | Member::Kasumi | Member::Mordin | Member::Tali => 0,
+        }
+    }
+
+    fn is_tech_expert(self) -> bool {
+        matches!(self, Member::Kasumi | Member::Legion | Member::Tali)
+    }
+
+    fn is_leader(self) -> bool {
+        matches!(self, Member::Garrus | Member::Jacob | Member::Miranda)
+    }
+
+    fn is_biotic_expert(self) -> bool {
+        matches!(self, Member::Jack | Member::Samara | Member::Morinth)
+    }
+}
+
+// Victims are taken in these orders, skipping Shepard's squad for the ship upgrades
+const ARMOR_VICTIMS: &[Member] = &[Member::Jack];
+const SHIELDING_VICTIMS: &[Member] = &[
+    Member::Kasumi,
+    Member::Legion,
+    Member::Tali,
+    Member::Thane,
+    Member::Garrus,
+    Member::Zaeed,
+    Member::Grunt,
+    Member::Samara,
+    Member::Morinth,
+];
+const CANNON_VICTIMS: &[Member] = &[
+    Member::Thane,
+    Member::Garrus,
+    Member::Zaeed,
+    Member::Grunt,
+    Member::Jack,
+    Member::Samara,
+    Member::Morinth,
+];
+const DEFENSE_VICTIMS: &[Member] = &[
+    Member::Mordin,
+    Member::Tali,
+    Member::Kasumi,
+    Member::Jack,
+    Member::Miranda,
+    Member::Jacob,
+    Member::Garrus,
+    Member::Samara,
+    Member::Morinth,
+    Member::Legion,
+    Member::Thane,
+    Member::Zaeed,
+    Member::Grunt,
+];
+
+const HEAVY_SHIP_ARMOR: (usize, usize) = (237, 114);
+const MULTICORE_SHIELDING: (usize, usize) = (236, 110);
+const THANIX_CANNON: (usize, usize) = (238, 117);
+const MORINTH_RECRUITED: usize = 1664;
+
+#[derive(Clone)]
+pub struct MemberState {
+    pub member: Member,
+    pub loyal: bool,
+    pub in_squad: bool,
+}
+
+/// Everything the outcome depends on before the Omega 4 relay.
+#[derive(Clone)]
+pub struct SquadState {
+    pub members: Vec<MemberState>,
+    pub heavy_ship_armor: bool,
+    pub multicore_shielding: bool,
+    pub thanix_cannon: bool,
+}
+
+impl SquadState {
+    pub fn from_plot(plot: &PlotTable) -> Self {
+        let booleans = plot.booleans();
+        let is_set = |idx: usize| booleans.get(idx).map(|b| *b).unwrap_or_default();
+
+        let morinth = is_set(MORINTH_RECRUITED);
+        let members = Member::ALL
+            .into_iter()
+            .filter(|&member| match member {
+                Member::Samara => !morinth,
+                Member::Morinth => morinth,
+                _ => true,
+            })
+            .filter(|member| {
+                let (recruited, _, dead, _) = member.plots();
+                is_set(recruited) && !is_set(dead)
+            })
+            .map(|member| {
+                let (_, loyal, _, in_squad) = member.plots();
+                MemberState { member, loyal: is_set(loyal), in_squad: is_set(in_squad) }
+            })
+            .collect();
+
+        SquadState {
+            members,
+            heavy_ship_armor: is_set(HEAVY_SHIP_ARMOR.0),
+            multicore_shielding: is_set(MULTICORE_SHIELDING.0),
+            thanix_cannon: is_set(THANIX_CANNON.0),
+        }
+    }
+
+    fn get(&self, member: Member) -> Option<&MemberState> {
+        self.members.iter().find(|state| state.member == member)
+    }
+
+    fn is_loyal(&self, member: Member) -> bool {
+        self.get(member).map(|state| state.loyal).unwrap_or_default()
+    }
+}
+
+const ROLES: [Role; 7] = [
+    Role::TechSpecialist,
+    Role::FirstLeader,
+    Role::BioticSpecialist,
+    Role::SecondLeader,
+    Role::CrewEscort,
+    Role::LongWalkSquad,
+    Role::FinalSquad,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    TechSpecialist,
+    FirstLeader,
+    BioticSpecialist,
+    SecondLeader,
+    CrewEscort,
+    LongWalkSquad,
+    FinalSquad,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Role::TechSpecialist => "Tech specialist",
+            Role::FirstLeader => "First fireteam leader",
+            Role::BioticSpecialist => "Biotic specialist",
+            Role::SecondLeader => "Second fireteam leader",
+            Role::CrewEscort => "Crew escort",
+            Role::LongWalkSquad => "Long Walk squad",
+            Role::FinalSquad => "Final squad",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Decisions taken during the mission, `None` lets the simulator pick the best one.
+#[derive(Clone, Default)]
+pub struct Choices {
+    pub tech_specialist: Option<Member>,
+    pub first_leader: Option<Member>,
+    pub biotic_specialist: Option<Member>,
+    pub second_leader: Option<Member>,
+    /// `Some(None)` sends nobody with the crew.
+    pub crew_escort: Option<Option<Member>>,
+    /// The 2 members following Shepard in the biotic bubble.
+    pub long_walk_squad: Option<(Member, Member)>,
+    pub final_squad: Option<(Member, Member)>,
+}
+
+impl Choices {
+    fn assigned(&self) -> Vec<Member> {
+        [
+            self.tech_specialist,
+            self.first_leader,
+            self.biotic_specialist,
+            self.second_leader,
+            self.crew_escort.flatten(),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(
+            [self.long_walk_squad, self.final_squad]
+                .into_iter()
+                .flatten()
+                .flat_map(|(first, second)| [first, second]),
+        )
+        .collect()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Death {
+    pub member: Member,
+    pub reason: &'static str,
+}
+
+#[derive(Clone, Debug)]
+pub struct Outcome {
+    pub tech_specialist: Option<Member>,
+    pub first_leader: Option<Member>,
+    pub biotic_specialist: Option<Member>,
+    pub second_leader: Option<Member>,
+    pub crew_escort: Option<Member>,
+    pub long_walk_squad: Vec<Member>,
+    pub final_squad: Vec<Member>,
+    pub deaths: Vec<Death>,
+}
+
+impl Outcome {
+    pub fn survives(&self, member: Member) -> bool {
+        !self.deaths.iter().any(|death| death.member == member)
+    }
+
+    /// Shepard needs at least 2 squadmates left to survive.
+    pub fn shepard_survives(&self, state: &SquadState) -> bool {
+        state.members.len() - self.deaths.len() >= 2
+    }
+}
+
+/// Runs the mission with the given decisions, missing or impossible ones are replaced by the best ones.
+pub fn simulate(state: &SquadState, choices: &Choices) -> Outcome {
+    let mut best = Choices::default();
+    let mut outcome = run(state, choices, &best);
+
+    // Each decision is improved in turn until none of them saves anyone
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for role in ROLES {
+            for candidate in candidates(state, role) {
+                let mut tried = best.clone();
+                candidate.set(&mut tried);
+                let tried_outcome = run(state, choices, &tried);
+                if tried_outcome.deaths.len() < outcome.deaths.len() {
+                    best = tried;
+                    outcome = tried_outcome;
+                    improved = true;
+                }
+            }
+        }
+    }
+
+    outcome
+}
+
+/// Outcome of every possible pick for each decision, the other decisions being the best ones.
+pub fn decision_paths(
+    state: &SquadState, choices: &Choices,
+) -> Vec<(Role, Vec<(Candidate, Outcome)>)> {
+    ROLES
+        .into_iter()
+        .map(|role| {
+            let paths = candidates(state, role)
+                .into_iter()
+                .map(|candidate| {
+                    let mut choices = choices.clone();
+                    candidate.set(&mut choices);
+                    (candidate, simulate(state, &choices))
+                })
+                .collect();
+            (role, paths)
+        })
+        .collect()
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PlotEdit {
+    pub description: String,
+    pub booleans: Vec<usize>,
+    pub integers: Vec<(usize, i32)>,
+}
+
+impl PlotEdit {
+    pub fn apply(&self, plot: &mut PlotTable) {
+        for &idx in &self.booleans {
+            if let Some(mut boolean) = plot.booleans_mut().get_mut(idx) {
+                *boolean = true;
+            }
+        }
+        for &(idx, value) in &self.integers {
+            if let Some(integer) = plot.integers().get(idx) {
+                integer.set(value);
+            }
+        }
+    }
+
+    /// Same as `apply` on the plots the state was read from.
+    pub fn apply_to_state(&self, state: &mut SquadState) {
+        for &idx in &self.booleans {
+            if idx == HEAVY_SHIP_ARMOR.0 {
+                state.heavy_ship_armor = true;
+            } else if idx == MULTICORE_SHIELDING.0 {
+                state.multicore_shielding = true;
+            } else if idx == THANIX_CANNON.0 {
+                state.thanix_cannon = true;
+            }
+
+            for member in &mut state.members {
+                if member.member.plots().1 == idx {
+                    member.loyal = true;
+                }
+            }
+        }
+    }
+}
+
+/// Fewest ship upgrades and loyalties to give so that every member of `survivors` lives.
+///
+/// Edits are added one at a time, the one saving the most of `survivors` first.
+/// The result may not be enough if nothing can save some of them.
+pub fn propose_edits(state: &SquadState, choices: &Choices, survivors: &[Member]) -> Vec<PlotEdit> {
+    let saved = |state: &SquadState| {
+        let outcome = simulate(state, choices);
+        let saved = survivors.iter().filter(|&&member| outcome.survives(member)).count();
+        (saved, usize::MAX - outcome.deaths.len())
+    };
+
+    let mut state = state.clone();
+    let mut edits = Vec::new();
+    let mut score = saved(&state);
+
+    while score.0 < survivors.len() {
+        let best = possible_edits(&state)
+            .into_iter()
+            .map(|edit| {
+                let mut tried = state.clone();
+                edit.apply_to_state(&mut tried);
+                (saved(&tried), edit, tried)
+            })
+            .max_by_key(|(score, ..)| *score);
+
+        match best {
+            Some((new_score, edit, new_state)) if new_score > score => {
+                score = new_score;
+                state = new_state;
+                edits.push(edit);
+            }
+            _ => break,
+        }
+    }
+
+    edits
+}
+
+fn possible_edits(state: &SquadState) -> Vec<PlotEdit> {
+    let upgrades = [
+        (state.heavy_ship_armor, HEAVY_SHIP_ARMOR, "Heavy Ship Armor"),
+        (state.multicore_shielding, MULTICORE_SHIELDING, "Multicore Shielding"),
+        (state.thanix_cannon, THANIX_CANNON, "Thanix Cannon"),
+    ];
+    let upgrades = upgrades.into_iter().filter(|(applied, ..)| !applied).map(
+        |(_, (applied, purchased), name)| PlotEdit {
+            description: format!("Apply the {}", name),
+            booleans: vec![applied],
+            integers: vec![(purchased, 1)],
+        },
+    );
+
+    let loyalties = state.members.iter().filter(|member| !member.loyal).map(|member| PlotEdit {
+        description: format!("Make {} loyal", member.member),
+        booleans: vec![member.member.plots().1],
+        integers: Vec::new(),
+    });
+
+    upgrades.chain(loyalties).collect()
+}
+
+/// A possible pick for a decision.
+#[derive(Clone, Copy)]
+pub enum Candidate {
+    Member(Role, Member),
+    NoEscort,
+    Pair(Role, Member, Member),
+}
+
+impl Candidate {
+    pub fn set(self, choices: &mut Choices) {
+        match self {
+            Candidate::Member(Role::TechSpecialist, member) => {
+                choices.tech_specialist = Some(member)
+            }
+            Candidate::Member(Role::FirstLeader, member) => choices.first_leader = Some(member),
+            Candidate::Member(Role::BioticSpecialist, member) => {
+                choices.biotic_specialist = Some(member)
+            }
+            Candidate::Member(Role::SecondLeader, member) => choices.second_leader = Some(member),
+            Candidate::Member(_, member) => choices.crew_escort = Some(Some(member)),
+            Candidate::NoEscort => choices.crew_escort = Some(None),
+            Candidate::Pair(Role::LongWalkSquad, first, second) => {
+                choices.long_walk_squad = Some((first, second))
+            }
+            Candidate::Pair(_, first, second) => choices.final_squad = Some((first, second)),
+        }
+    }
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Candidate::Member(_, member) => member.fmt(f),
+            Candidate::NoEscort => f.write_str("Nobody"),
+            Candidate::Pair(_, first, second) => write!(f, "{} and {}", first, second),
+        }
+    }
+}
+
+fn candidates(state: &SquadState, role: Role) -> Vec<Candidate> {
+    let members = state.members.iter().map(|state| state.member);
+    match role {
+        Role::CrewEscort => std::iter::once(Candidate::NoEscort)
+            .chain(members.map(|member| Candidate::Member(role, member)))
+            .collect(),
+        Role::LongWalkSquad | Role::FinalSquad => {
+            let members: Vec<_> = members.collect();
+            members
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &first)| {
+                    members[i + 1..].iter().map(move |&second| Candidate::Pair(role, first, second))
+                })
+                .collect()
+        }
+        _ => members.map(|member| Candidate::Member(role, member)).collect(),
+    }
+}
+
+/// Plays the mission, the user's `choices` win over the `fallback` ones when they are possible.
+fn run(state: &SquadState, choices: &Choices, fallback: &Choices) -> Outcome {
+    let mut alive: Vec<Member> = state.members.iter().map(|state| state.member).collect();
+    let mut deaths = Vec::new();
+    let kill = |alive: &mut Vec<Member>, deaths: &mut Vec<Death>, member, reason| {
+        if let Some(idx) = alive.iter().position(|&other| other == member) {
+            alive.remove(idx);
+            deaths.push(Death { member, reason });
+        }
+    };
+
+    // Omega 4 relay
+    let in_squad =
+        |member: Member| state.get(member).map(|state| state.in_squad).unwrap_or_default();
+    for (upgrade, victims, reason) in [
+        (state.heavy_ship_armor, ARMOR_VICTIMS, "No Heavy Ship Armor"),
+        (state.multicore_shielding, SHIELDING_VICTIMS, "No Multicore Shielding"),
+        (state.thanix_cannon, CANNON_VICTIMS, "No Thanix Cannon"),
+    ] {
+        if !upgrade {
+            let victim =
+                victims.iter().find(|&&member| alive.contains(&member) && !in_squad(member));
+            if let Some(&victim) = victim {
+                kill(&mut alive, &mut deaths, victim, reason);
+            }
+        }
+    }
+
+    // Members the user placed somewhere are kept for that decision
+    let reserved = choices.assigned();
+    // Without a choice, loyal experts come first then the weakest defenders
+    let pick = |alive: &[Member],
+                choice: Option<Member>,
+                fallback: Option<Member>,
+                taken: &[Member],
+                is_expert: fn(Member) -> bool| {
+        let free = |member: &Member| alive.contains(member) && !taken.contains(member);
+        let mut ranked = alive.to_vec();
+        ranked.sort_by_key(|&member| {
+            (!(is_expert(member) && state.is_loyal(member)), member.defense())
+        });
+
+        choice.filter(free).or_else(|| {
+            fallback
+                .into_iter()
+                .chain(ranked)
+                .find(|member| free(member) && !reserved.contains(member))
+        })
+    };
+
+    // The Infiltration
+    let tech_specialist = pick(
+        &alive,
+        choices.tech_specialist,
+        fallback.tech_specialist,
+        &[],
+        Member::is_tech_expert,
+    );
+    let first_leader = pick(
+        &alive,
+        choices.first_leader,
+        fallback.first_leader,
+        &tech_specialist.into_iter().collect::<Vec<_>>(),
+        Member::is_leader,
+    );
+    if let Some(tech_specialist) = tech_specialist {
+        if !(tech_specialist.is_tech_expert() && state.is_loyal(tech_specialist)) {
+            kill(
+                &mut alive,
+                &mut deaths,
+                tech_specialist,
+                "Tech specialist not an expert or not loyal",
+            );
+        } else if !first_leader
+            .map(|leader| leader.is_leader() && state.is_loyal(leader))
+            .unwrap_or_default()
+        {
+            kill(
+                &mut alive,
+                &mut deaths,
+                tech_specialist,
+                "First fireteam leader not a leader or not loyal",
+            );
+        }
+    }
+
+    // The Long Walk
+    let biotic_specialist = pick(
+        &alive,
+        choices.biotic_specialist,
+        fallback.biotic_specialist,
+        &[],
+        Member::is_biotic_expert,
+    );
+    let second_leader = pick(
+        &alive,
+        choices.second_leader,
+        fallback.second_leader,
+        &biotic_specialist.into_iter().collect::<Vec<_>>(),
+        Member::is_leader,
+    );
+    let mut taken: Vec<_> = [biotic_specialist, second_leader].into_iter().flatten().collect();
+    let crew_escort = match (choices.crew_escort, fallback.crew_escort) {
+        (Some(None), _) => None,
+        (Some(Some(choice)), _) if alive.contains(&choice) && !taken.contains(&choice) => {
+            Some(choice)
+        }
+        (_, Some(fallback)) => fallback.filter(|member| {
+            alive.contains(member) && !taken.contains(member) && !reserved.contains(member)
+        }),
+        _ => None,
+    };
+    taken.extend(crew_escort);
+    let first_walker = pick(
+        &alive,
+        choices.long_walk_squad.map(|squad| squad.0),
+        fallback.long_walk_squad.map(|squad| squad.0),
+        &taken,
+        |_| true,
+    );
+    taken.extend(first_walker);
+    let second_walker = pick(
+        &alive,
+        choices.long_walk_squad.map(|squad| squad.1),
+        fallback.long_walk_squad.map(|squad| squad.1),
+        &taken,
+        |_| true,
+    );
+    let long_walk_squad: Vec<_> = first_walker.into_iter().chain(second_walker).collect();
+
+    if let Some(biotic_specialist) = biotic_specialist {
+        if !(biotic_specialist.is_biotic_expert() && state.is_loyal(biotic_specialist)) {
+            // One of the 2 members following Shepard is taken
+            let victim = DEFENSE_VICTIMS.iter().find(|&&member| long_walk_squad.contains(&member));
+            if let Some(&victim) = victim {
+                kill(
+                    &mut alive,
+                    &mut deaths,
+                    victim,
+                    "Biotic specialist not an expert or not loyal",
+                );
+            }
+        }
+    }
+    if let Some(second_leader) = second_leader {
+        if !(second_leader.is_leader() && state.is_loyal(second_leader)) {
+            kill(
+                &mut alive,
+                &mut deaths,
+                second_leader,
+                "Second fireteam leader not a leader or not loyal",
+            );
+        }
+    }
+    if let Some(crew_escort) = crew_escort {
+        if !state.is_loyal(crew_escort) {
+            kill(&mut alive, &mut deaths, crew_escort, "Crew escort not loyal");
+        }
+    }
+
+    // The final battle
+    let escort: Vec<_> = crew_escort.into_iter().collect();
+    let first = pick(
+        &alive,
+        choices.final_squad.map(|squad| squad.0),
+        fallback.final_squad.map(|squad| squad.0),
+        &escort,
+        |_| true,
+    );
+    let mut taken = escort.clone();
+    taken.extend(first);
+    let second = pick(
+        &alive,
+        choices.final_squad.map(|squad| squad.1),
+        fallback.final_squad.map(|squad| squad.1),
+        &taken,
+        |_| true,
+    );
+    let final_squad: Vec<_> = first.into_iter().chain(second).collect();
+
+    let defenders: Vec<_> = alive
+        .iter()
+        .copied()
+        .filter(|member| !final_squad.contains(member) && !escort.contains(member))
+        .collect();
+    let defense_deaths = defense_deaths(state, &defenders);
+
+    let mut victims: Vec<_> =
+        DEFENSE_VICTIMS.iter().copied().filter(|member| defenders.contains(member)).collect();
+    // Disloyal members go first
+    victims.sort_by_key(|&member| state.is_loyal(member));
+    for victim in victims.into_iter().take(defense_deaths) {
+        kill(&mut alive, &mut deaths, victim, "Fell holding the line");
+    }
+
+    for &member in &final_squad {
+        if !state.is_loyal(member) {
+            kill(&mut alive, &mut deaths, member, "Final squad member not loyal");
+        }
+    }
+
+    Outcome {
+        tech_specialist,
+        first_leader,
+        biotic_specialist,
+        second_leader,
+        crew_escort,
+        long_walk_squad,
+        final_squad,
+        deaths,
+    }
+}
+
+/// Number of defenders dying while holding the line.
+fn defense_deaths(state: &SquadState, defenders: &[Member]) -> usize {
+    if defenders.is_empty() {
+        return 0;
+    }
+
+    let total: i32 =
+        defenders.iter().map(|&member| member.defense() + state.is_loyal(member) as i32).sum();
+    let score = total as f32 / defenders.len() as f32;
+
+    let thresholds: &[f32] = match defenders.len() {
+        1 => &[2.0],
+        2 | 3 => &[2.0, 1.0],
+        4 => &[2.0, 1.0, 0.5],
+        _ => &[2.0, 1.5, 0.5],
+    };
+    let deaths = thresholds.iter().position(|&min| score >= min).unwrap_or(thresholds.len());
+    deaths.min(defenders.len())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2LeSaveGame, unreal};
+
+    fn everyone(loyal: bool) -> SquadState {
+        let members = Member::ALL
+            .into_iter()
+            .filter(|&member| member != Member::Morinth)
+            .map(|member| MemberState { member, loyal, in_squad: false })
+            .collect();
+        SquadState {
+            members,
+            heavy_ship_armor: true,
+            multicore_shielding: true,
+            thanix_cannon: true,
+        }
+    }
+
+    #[test]
+    fn everyone_survives() {
+        let outcome = simulate(&everyone(true), &Choices::default());
+        assert_eq!(outcome.deaths, Vec::new());
+        assert!(outcome.tech_specialist.unwrap().is_tech_expert());
+        assert!(outcome.first_leader.unwrap().is_leader());
+        assert!(outcome.biotic_specialist.unwrap().is_biotic_expert());
+        assert!(outcome.second_leader.unwrap().is_leader());
+    }
+
+    #[test]
+    fn ship_upgrades() {
+        let mut state = everyone(true);
+        state.heavy_ship_armor = false;
+        state.multicore_shielding = false;
+        state.thanix_cannon = false;
+
+        let outcome = simulate(&state, &Choices::default());
+        let victims: Vec<_> = outcome.deaths.iter().map(|death| death.member).collect();
+        assert_eq!(victims, [Member::Jack, Member::Kasumi, Member::Thane]);
+
+        // Shepard's squad is never the victim
+        state.members.iter_mut().find(|member| member.member == Member::Kasumi).unwrap().in_squad =
+            true;
+        let outcome = simulate(&state, &Choices::default());
+        assert!(outcome.survives(Member::Kasumi));
+        assert!(!outcome.survives(Member::Legion));
+    }
+
+    #[test]
+    fn bad_choices() {
+        let state = everyone(false);
+        let choices = Choices {
+            tech_specialist: Some(Member::Mordin),
+            crew_escort: Some(Some(Member::Thane)),
+            ..Default::default()
+        };
+
+        let outcome = simulate(&state, &choices);
+        assert!(!outcome.survives(Member::Mordin));
+        assert!(!outcome.survives(Member::Thane));
+    }
+
+    #[test]
+    fn long_walk_victim() {
+        let mut state = everyone(true);
+        state.members.iter_mut().find(|member| member.member == Member::Jack).unwrap().loyal =
+            false;
+        let choices = Choices {
+            biotic_specialist: Some(Member::Jack),
+            long_walk_squad: Some((Member::Grunt, Member::Zaeed)),
+            ..Default::default()
+        };
+
+        // The victim is one of the 2 members following Shepard
+        let outcome = simulate(&state, &choices);
+        assert_eq!(outcome.long_walk_squad, [Member::Grunt, Member::Zaeed]);
+        let death = outcome
+            .deaths
+            .iter()
+            .find(|death| death.reason == "Biotic specialist not an expert or not loyal")
+            .unwrap();
+        assert!([Member::Grunt, Member::Zaeed].contains(&death.member));
+        assert!(outcome.survives(Member::Mordin));
+    }
+
+    #[test]
+    fn propose_edits_for_everyone() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let mut me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let mut plot = me2_save_game.plot_mut();
+
+        if let Some(mut armor) = plot.booleans_mut().get_mut(HEAVY_SHIP_ARMOR.0) {
+            *armor = false;
+        }
+        let state = SquadState::from_plot(&plot);
+        let survivors: Vec<_> = state.members.iter().map(|member| member.member).collect();
+        assert!(!simulate(&state, &Choices::default()).deaths.is_empty());
+
+        let edits = propose_edits(&state, &Choices::default(), &survivors);
+        assert!(!edits.is_empty());
+        for edit in &edits {
+            edit.apply(&mut plot);
+        }
+
+        let state = SquadState::from_plot(&plot);
+        let outcome = simulate(&state, &Choices::default());
+        assert!(survivors.iter().all(|&member| outcome.survives(member)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,290 @@
+use anyhow::{bail, Result};
+use serde::Deserialize;
+
+use super::{player::Player, squad::Henchman};
+use crate::save_data::{shared::player::WeaponLoadout, RcRef};
+
+/// Empty slots are blank in vanilla saves and `None` in Legendary Edition ones.
+pub fn is_empty_slot(class_name: &str) -> bool {
+    class_name.is_empty() || class_name == "None"
+}
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq)]
+pub enum Me2WeaponType {
+    AssaultRifle,
+    Shotgun,
+    SniperRifle,
+    SubmachineGun,
+    Pistol,
+    HeavyWeapon,
+}
+
+impl Me2WeaponType {
+    pub fn loadout_slot(self, loadout: &WeaponLoadout) -> &RcRef<String> {
+        match self {
+            Me2WeaponType::AssaultRifle => &loadout.assault_rifle,
+            Me2WeaponType::Shotgun => &loadout.shotgun,
+            Me2WeaponType::SniperRifle => &loadout.sniper_rifle,
+            Me2WeaponType::SubmachineGun => &loadout.submachine_gun,
+            Me2WeaponType::Pistol => &loadout.pistol,
+            Me2WeaponType::HeavyWeapon => &loadout.heavy_weapon,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Me2Weapon {
+    pub class_name: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct Me2WeaponCategory {
+    pub weapon_type: Me2WeaponType,
+    pub name: String,
+    pub weapons: Vec<Me2Weapon>,
+}
+
+#[derive(Deserialize)]
+pub struct Me2ClassWeapons {
+    pub class_name: String,
+    pub slots: Vec<Me2WeaponType>,
+}
+
+#[derive(Deserialize)]
+pub struct Me2HenchmanWeapons {
+    pub tag: String,
+    pub slots: Vec<Me2WeaponType>,
+}
+
+#[derive(Deserialize)]
+pub struct Me2WeaponDb {
+    types: Vec<Me2WeaponCategory>,
+    classes: Vec<Me2ClassWeapons>,
+    bonus_training: Vec<Me2WeaponType>,
+    henchmen: Vec<Me2HenchmanWeapons>,
+}
+
+impl Me2WeaponDb {
+    pub fn types(&self) -> &[Me2WeaponCategory] {
+        &self.types
+    }
+
+    pub fn weapon(&self, class_name: &str) -> Option<(&Me2WeaponCategory, &Me2Weapon)> {
+        self.types.iter().find_map(|category| {
+            category
+                .weapons
+                .iter()
+                .find(|weapon| weapon.class_name.eq_ignore_ascii_case(class_name))
+                .map(|weapon| (category, weapon))
+        })
+    }
+
+    fn all_slots(&self) -> Vec<Me2WeaponType> {
+        self.types.iter().map(|category| category.weapon_type).collect()
+    }
+
+    /// Weapon types a player class is trained with, all of them if the class is unknown.
+    pub fn class_slots(&self, class_name: &str) -> Vec<Me2WeaponType> {
+        self.classes
+            .iter()
+            .find(|class| class.class_name.eq_ignore_ascii_case(class_name))
+            .map(|class| class.slots.clone())
+            .unwrap_or_else(|| self.all_slots())
+    }
+
+    /// Weapon types of the Collector ship training the class isn't already trained with.
+    pub fn bonus_slots(&self, class_name: &str) -> Vec<Me2WeaponType> {
+        let class_slots = self.class_slots(class_name);
+        self.bonus_training
+            .iter()
+            .filter(|weapon_type| !class_slots.contains(weapon_type))
+            .copied()
+            .collect()
+    }
+
+    /// Weapon types a squadmate uses, all of them if the squadmate is unknown.
+    pub fn henchman_slots(&self, tag: &str) -> Vec<Me2WeaponType> {
+        self.henchmen
+            .iter()
+            .find(|henchman| henchman.tag.eq_ignore_ascii_case(tag))
+            .map(|henchman| henchman.slots.clone())
+            .unwrap_or_else(|| self.all_slots())
+    }
+
+    fn player_slots(&self, player: &Player) -> Vec<Me2WeaponType> {
+        let class_name = player.class_name();
+        let mut slots = self.class_slots(&class_name);
+        slots.extend(self.bonus_slots(&class_name));
+        slots
+    }
+
+    /// Puts the weapon in the loadout slot of its type.
+    pub fn equip(&self, loadout: &WeaponLoadout, weapon: &str) -> Result<()> {
+        let weapon_type = match self.weapon(weapon) {
+            Some((category, _)) => category.weapon_type,
+            None => bail!("Unknown weapon: {}", weapon),
+        };
+
+        *weapon_type.loadout_slot(loadout).borrow_mut() = weapon.to_owned();
+        Ok(())
+    }
+
+    fn equip_in_slots(
+        &self, loadout: &WeaponLoadout, weapon: &str, slots: &[Me2WeaponType],
+    ) -> Result<()> {
+        if let Some((category, _)) = self.weapon(weapon) {
+            if !slots.contains(&category.weapon_type) {
+                bail!("{} can't be used by this character", category.name);
+            }
+        }
+        self.equip(loadout, weapon)
+    }
+
+    /// Equips the player with a weapon of their class or of the Collector ship training.
+    pub fn equip_player(&self, player: &Player, weapon: &str) -> Result<()> {
+        self.equip_in_slots(&player.weapons_loadout(), weapon, &self.player_slots(player))
+    }
+
+    /// Equips a squadmate with a weapon type they use.
+    pub fn equip_henchman(&self, henchman: &Henchman, weapon: &str) -> Result<()> {
+        self.equip_in_slots(
+            &henchman.weapon_loadout(),
+            weapon,
+            &self.henchman_slots(&henchman.tag()),
+        )
+    }
+
+    /// Weapons of the loadout that don't fit their slot, unknown classes can't be checked.
+    pub fn loadout_issues(&self, loadout: &WeaponLoadout) -> Vec<String> {
+        let mut issues = Vec::new();
+        for category in &self.types {
+            let equipped = category.weapon_type.loadout_slot(loadout).borrow();
+            match self.weapon(&equipped) {
+                Some((other, weapon)) if other.weapon_type != category.weapon_type => {
+                    issues.push(format!("{} is in the {} slot", weapon.name, category.name))
+                }
+                _ => {}
+            }
+        }
+        issues
+    }
+
+    /// Misplaced weapons and unknown classes in the slots that are used, the game leaves the
+    /// character unarmed for those.
+    fn slots_issues(&self, loadout: &WeaponLoadout, slots: &[Me2WeaponType]) -> Vec<String> {
+        let mut issues = self.loadout_issues(loadout);
+        for category in self.types.iter().filter(|category| slots.contains(&category.weapon_type)) {
+            let equipped = category.weapon_type.loadout_slot(loadout).borrow();
+            if !is_empty_slot(&equipped) && self.weapon(&equipped).is_none() {
+                issues.push(format!("Unknown weapon: {}", equipped));
+            }
+        }
+        issues
+    }
+
+    pub fn player_issues(&self, player: &Player) -> Vec<String> {
+        self.slots_issues(&player.weapons_loadout(), &self.player_slots(player))
+    }
+
+    pub fn henchman_issues(&self, henchman: &Henchman) -> Vec<String> {
+        self.slots_issues(&henchman.weapon_loadout(), &self.henchman_slots(&henchman.tag()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::mass_effect_2::{Me2LeSaveGame, Me2SaveGame},
+        unreal,
+    };
+
+    fn weapon_db() -> Result<Me2WeaponDb> {
+        let input = fs::read_to_string("databases/me2_weapon_db.ron")?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn weapons_in_saves() -> Result<()> {
+        let weapon_db = weapon_db()?;
+
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let mut loadouts = vec![me2_save_game.player().weapons_loadout().clone()];
+        loadouts.extend(
+            me2_save_game.squad().iter().map(|henchman| henchman.borrow().weapon_loadout().clone()),
+        );
+        assert!(weapon_db.player_issues(&me2_save_game.player()).is_empty());
+        for henchman in me2_save_game.squad().iter() {
+            assert!(weapon_db.henchman_issues(&henchman.borrow()).is_empty());
+        }
+
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        loadouts.push(me2_save_game.player().weapons_loadout().clone());
+        loadouts.extend(
+            me2_save_game.squad().iter().map(|henchman| henchman.borrow().weapon_loadout().clone()),
+        );
+
+        // Every equipped weapon is known and in its slot
+        for loadout in &loadouts {
+            for category in weapon_db.types() {
+                let equipped = category.weapon_type.loadout_slot(loadout).borrow();
+                if !equipped.is_empty() && *equipped != "None" {
+                    assert!(weapon_db.weapon(&equipped).is_some(), "{}", equipped);
+                }
+            }
+            assert!(weapon_db.loadout_issues(loadout).is_empty());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn equip_weapons() -> Result<()> {
+        let weapon_db = weapon_db()?;
+        let (geth_pulse_rifle, cain) = (
+            "SFXGameContent_Inventory.SFXWeapon_GethPulseRifle",
+            "SFXGameContent_Inventory.SFXHeavyWeapon_NukeLauncher",
+        );
+
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        {
+            let squad = me2_save_game.squad();
+            let garrus = squad.iter().find(|henchman| *henchman.borrow().tag() == "hench_garrus");
+            let garrus = garrus.unwrap().borrow();
+            let loadout = garrus.weapon_loadout();
+            weapon_db.equip_henchman(&garrus, geth_pulse_rifle)?;
+            weapon_db.equip(&loadout, cain)?;
+            assert!(weapon_db.equip(&loadout, "SFXWeapon_Unknown").is_err());
+
+            // Squadmates don't carry heavy weapons, assault rifles are a Collector ship training for the Adept
+            assert!(weapon_db.equip_henchman(&garrus, cain).is_err());
+            let player = me2_save_game.player();
+            let class_name = player.class_name();
+            assert!(!weapon_db.class_slots(&class_name).contains(&Me2WeaponType::AssaultRifle));
+            assert!(weapon_db.bonus_slots(&class_name).contains(&Me2WeaponType::AssaultRifle));
+            weapon_db.equip_player(&player, geth_pulse_rifle)?;
+        }
+
+        let output = unreal::Serializer::to_vec(&me2_save_game)?;
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let squad = me2_save_game.squad();
+        let garrus = squad.iter().find(|henchman| *henchman.borrow().tag() == "hench_garrus");
+        let garrus = garrus.unwrap().borrow();
+        let loadout = garrus.weapon_loadout();
+        assert_eq!(*loadout.assault_rifle(), geth_pulse_rifle);
+        assert_eq!(*loadout.heavy_weapon(), cain);
+        assert!(weapon_db.loadout_issues(&loadout).is_empty());
+        assert!(weapon_db.henchman_issues(&garrus).is_empty());
+        assert_eq!(*me2_save_game.player().weapons_loadout().assault_rifle(), geth_pulse_rifle);
+
+        Ok(())
+    }
+}
@@ -1,29 +1,109 @@
 use serde::{Deserialize, Serialize};
 
-use crate::save_data::shared::Vector2d;
+use crate::save_data::shared::{galaxy_db::GalaxyDb, Vector2d};
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
 pub struct GalaxyMap {
-    planets: Vec<Planet>,
-    systems: Vec<System>,
+    pub planets: Vec<Planet>,
+    pub systems: Vec<System>,
+}
+
+impl GalaxyMap {
+    /// Marks every planet visited and scanned, the planets of the database the save doesn't know yet are added.
+    pub fn mark_all_scanned(&mut self, galaxy_db: &GalaxyDb) {
+        for galaxy_planet in galaxy_db.planets() {
+            if self.planets().iter().all(|planet| planet.borrow().id() != galaxy_planet.id) {
+                let planet = Planet::default();
+                planet.id.set(galaxy_planet.id);
+                self.planets_mut().push(planet.into());
+            }
+        }
+
+        for planet in self.planets().iter() {
+            let planet = planet.borrow();
+            planet.visited.set(true);
+            planet.show_as_scanned.set(true);
+        }
+    }
+
+    /// Clears the probe markers of a planet or every planet if `id` is `None`.
+    pub fn clear_probes(&self, id: Option<i32>) {
+        let planets = self.planets();
+        let planets =
+            planets.iter().filter(|planet| id.map_or(true, |id| planet.borrow().id() == id));
+        for planet in planets {
+            planet.borrow_mut().probes_mut().clear();
+        }
+    }
+
+    pub fn reset_reaper_alerts(&self) {
+        for system in self.systems().iter() {
+            system.borrow().reset_reaper_alert();
+        }
+    }
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", id)]
 pub struct Planet {
-    id: i32,
-    visited: bool,
-    probes: Vec<Vector2d>,
-    show_as_scanned: bool,
+    pub id: i32,
+    pub visited: bool,
+    pub probes: Vec<Vector2d>,
+    pub show_as_scanned: bool,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", id)]
 pub struct System {
-    id: i32,
-    reaper_alert_level: f32,
-    reaper_detected: bool,
+    pub id: i32,
+    pub reaper_alert_level: f32,
+    pub reaper_detected: bool,
+}
+
+impl System {
+    pub fn reset_reaper_alert(&self) {
+        self.reaper_alert_level.set(0.0);
+        self.reaper_detected.set(false);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    #[test]
+    fn mark_all_scanned() -> Result<()> {
+        let galaxy_db = ron::from_str(
+            r#"(clusters: [(id: 99, systems: [(id: 990100, planets: [(id: 990101)])])])"#,
+        )?;
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        {
+            let mut galaxy_map = me3_save_game.galaxy_map.borrow_mut();
+            galaxy_map.mark_all_scanned(&galaxy_db);
+            galaxy_map.clear_probes(None);
+            galaxy_map.reset_reaper_alerts();
+        }
+
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let galaxy_map = me3_save_game.galaxy_map.borrow();
+
+        assert!(galaxy_map.planets().iter().any(|planet| planet.borrow().id() == 990101));
+        assert!(galaxy_map.planets().iter().all(|planet| {
+            let planet = planet.borrow();
+            planet.visited() && planet.show_as_scanned() && planet.probes().is_empty()
+        }));
+        assert!(galaxy_map.systems().iter().all(|system| !system.borrow().reaper_detected()));
+
+        Ok(())
+    }
 }
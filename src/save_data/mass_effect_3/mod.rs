@@ -1,8 +1,13 @@
-mod galaxy_map;
+pub mod galaxy_map;
 pub mod player;
+pub mod player_class_db;
 pub mod plot;
 pub mod plot_db;
-mod squad;
+pub mod power_db;
+pub mod squad;
+pub mod squad_db;
+pub mod war_assets_db;
+pub mod weapon_db;
 
 use self::{galaxy_map::*, player::*, plot::*, squad::*};
 
@@ -11,8 +16,8 @@ use indexmap::IndexMap;
 use serde::{de, Deserialize, Deserializer, Serialize};
 
 use super::shared::{
-    plot::PlotTable as Me1PlotTable, Door, EndGameState, Kismet, Level, Rotator, SaveTimeStamp,
-    StreamingState, Vector,
+    location_db::SafeLocation, plot::PlotTable as Me1PlotTable, Door, EndGameState, Kismet, Level,
+    Rotator, SaveTimeStamp, StreamingState, Vector,
 };
 use super::Guid;
 
@@ -23,28 +28,28 @@ pub struct Me3SaveGame {
     debug_name: String,
     seconds_played: f32,
     disc: i32,
-    base_level_name: String,
+    pub base_level_name: String,
     base_level_name_display_override: String,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
     timestamp: SaveTimeStamp,
-    location: Vector,
+    pub location: Vector,
     rotation: Rotator,
     current_loading_tip: i32,
-    levels: Vec<Level>,
-    streaming_states: Vec<StreamingState>,
-    kismet_records: Vec<Kismet>,
-    doors: Vec<Door>,
-    placeables: Vec<Placeable>,
-    pawns: Vec<Guid>,
+    pub levels: Vec<Level>,
+    pub streaming_states: Vec<StreamingState>,
+    pub kismet_records: Vec<Kismet>,
+    pub doors: Vec<Door>,
+    pub placeables: Vec<Placeable>,
+    pub pawns: Vec<Guid>,
     pub player: Player,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
-    journal: Journal,
-    codex: Codex,
+    pub journal: Journal,
+    pub codex: Codex,
     _me1_plot: Me1PlotTable,
     pub player_variables: IndexMap<String, i32>,
-    galaxy_map: GalaxyMap,
+    pub galaxy_map: GalaxyMap,
     dependant_dlcs: Vec<DependentDlc>,
     treasures: Vec<LevelTreasure>,
     use_modules: Vec<Guid>,
@@ -53,6 +58,33 @@ pub struct Me3SaveGame {
     saved_objective_text: i32,
 }
 
+impl Me3SaveGame {
+    /// Moves the player to a known location, the streamed levels are replaced along.
+    pub fn relocate(&mut self, spot: &SafeLocation) {
+        *self.base_level_name.borrow_mut() = spot.base_level_name.clone();
+        // A spot without an override mustn't keep the name of the level the save was made in
+        *self.base_level_name_display_override.borrow_mut() =
+            spot.base_level_name_display_override.clone().unwrap_or_else(|| String::from("None"));
+        *self.location.borrow_mut() = spot.location();
+        *self.rotation.borrow_mut() = spot.rotation();
+        *self.levels.borrow_mut() = spot.levels();
+        *self.streaming_states.borrow_mut() = spot.streaming_states();
+    }
+
+    /// The current location, named after its level.
+    pub fn current_spot(&self) -> SafeLocation {
+        SafeLocation::capture(
+            self.base_level_name().clone(),
+            self.base_level_name().clone(),
+            Some(self.base_level_name_display_override.borrow().clone()),
+            &self.location(),
+            &self.rotation.borrow(),
+            &self.levels(),
+            &self.streaming_states(),
+        )
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me3Version {
     version: i32,
@@ -96,9 +128,23 @@ pub enum Difficulty {
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
 pub struct Placeable {
-    guid: Guid,
-    is_destroyed: PlaceableState,
-    is_deactivated: PlaceableState,
+    pub guid: Guid,
+    pub is_destroyed: PlaceableState,
+    pub is_deactivated: PlaceableState,
+}
+
+impl Placeable {
+    pub fn is_disabled(&self) -> bool {
+        [&self.is_destroyed, &self.is_deactivated]
+            .iter()
+            .any(|state| matches!(*state.borrow(), PlaceableState::Yes))
+    }
+
+    /// Brings back a destroyed or deactivated placeable, like a crate or a terminal.
+    pub fn reenable(&mut self) {
+        *self.is_destroyed.borrow_mut() = PlaceableState::No;
+        *self.is_deactivated.borrow_mut() = PlaceableState::No;
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone, RawUi)]
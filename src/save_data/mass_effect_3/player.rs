@@ -1,12 +1,14 @@
+use anyhow::{bail, Result};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
+use super::war_assets_db::is_multiplier;
 use crate::save_data::{
     shared::{
         appearance::Appearance,
         player::{Notoriety, Origin, WeaponLoadout},
     },
-    Guid,
+    Guid, RcCell, RcRef,
 };
 
 #[rcize_fields]
@@ -24,18 +26,18 @@ pub struct Player {
     pub origin: Origin,
     pub notoriety: Notoriety,
     pub talent_points: i32,
-    mapped_power_1: String,
-    mapped_power_2: String,
-    mapped_power_3: String,
+    pub mapped_power_1: String,
+    pub mapped_power_2: String,
+    pub mapped_power_3: String,
     pub appearance: Appearance,
     emissive_id: i32,
     pub powers: Vec<Power>,
-    war_assets: IndexMap<i32, i32>,
+    pub war_assets: IndexMap<i32, i32>,
     weapons: Vec<Weapon>,
-    weapons_mods: Vec<WeaponMod>,
-    weapons_loadout: WeaponLoadout,
-    primary_weapon: String,
-    secondary_weapon: String,
+    pub weapons_mods: Vec<WeaponMod>,
+    pub weapons_loadout: WeaponLoadout,
+    pub primary_weapon: String,
+    pub secondary_weapon: String,
     loadout_weapon_group: Vec<i32>,
     hotkeys: Vec<Hotkey>,
     health: f32,
@@ -49,10 +51,30 @@ pub struct Player {
     pub current_fuel: f32,
     pub grenades: i32,
     pub face_code: String,
-    localized_class_name: i32,
+    pub localized_class_name: i32,
     character_guid: Guid,
 }
 
+impl Player {
+    /// Sum of the war assets strength, multipliers aside.
+    pub fn total_military_strength(&self) -> i32 {
+        self.war_assets().values().map(RcCell::get).filter(|&value| !is_multiplier(value)).sum()
+    }
+
+    pub fn remove_war_asset(&mut self, id: i32) {
+        self.war_assets_mut().shift_remove(&id);
+    }
+
+    pub fn spent_talent_points(&self) -> i32 {
+        Power::spent_talent_points(&self.powers())
+    }
+
+    /// Resets all the powers and refunds their talent points.
+    pub fn respec(&mut self) {
+        Power::respec(&self.talent_points, &self.powers());
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", name)]
@@ -66,7 +88,105 @@ pub struct Power {
     evolved_choice_4: i32,
     evolved_choice_5: i32,
     pub power_class_name: String,
-    wheel_display_index: i32,
+    pub wheel_display_index: i32,
+}
+
+impl Power {
+    pub const MAX_RANK: i32 = 6;
+
+    // Ranks 4, 5 and 6 have a pair of choices each, the chosen one holds `rank - 3`
+    fn evolved_choices(&self) -> [&RcCell<i32>; 6] {
+        [
+            &self.evolved_choice_0,
+            &self.evolved_choice_1,
+            &self.evolved_choice_2,
+            &self.evolved_choice_3,
+            &self.evolved_choice_4,
+            &self.evolved_choice_5,
+        ]
+    }
+
+    fn evolution_pair(&self, rank: i32) -> [&RcCell<i32>; 2] {
+        let [a0, b0, a1, b1, a2, b2] = self.evolved_choices();
+        match rank {
+            4 => [a0, b0],
+            5 => [a1, b1],
+            _ => [a2, b2],
+        }
+    }
+
+    pub fn current_rank(&self) -> i32 {
+        self.rank.get() as i32
+    }
+
+    /// Talent points needed to reach `rank`, each rank costs its number of points.
+    pub fn rank_cost(rank: i32) -> i32 {
+        rank * (rank + 1) / 2
+    }
+
+    /// Evolution chosen at a rank from 4 to 6, `0` or `1`.
+    pub fn evolution(&self, rank: i32) -> Option<usize> {
+        self.evolution_pair(rank).iter().position(|choice| choice.get() == rank - 3)
+    }
+
+    /// Ranks from 4 should have exactly one evolution chosen, the ranks above none.
+    pub fn has_valid_evolutions(&self) -> bool {
+        let current_rank = self.current_rank();
+        (4..=Self::MAX_RANK).all(|rank| {
+            let [a, b] = self.evolution_pair(rank).map(RcCell::get);
+            if rank <= current_rank {
+                (a, b) == (rank - 3, 0) || (a, b) == (0, rank - 3)
+            } else {
+                (a, b) == (0, 0)
+            }
+        })
+    }
+
+    /// Sets the rank and its evolution, the ranks below without one get the first evolution.
+    pub fn set_rank(&self, rank: i32, evolution: usize) {
+        let rank = rank.clamp(0, Self::MAX_RANK);
+        self.rank.set(rank as f32);
+
+        for other in 4..=Self::MAX_RANK {
+            let pair = self.evolution_pair(other);
+            let chosen = if other > rank {
+                None
+            } else if other == rank {
+                Some(evolution.min(1))
+            } else {
+                Some(self.evolution(other).unwrap_or_default())
+            };
+            for (option, choice) in pair.into_iter().enumerate() {
+                choice.set(if chosen == Some(option) { other - 3 } else { 0 });
+            }
+        }
+    }
+
+    /// Sets the rank like `set_rank`, paying the difference with the talent points.
+    pub fn rank_up(&self, talent_points: &RcCell<i32>, rank: i32, evolution: usize) -> Result<()> {
+        let rank = rank.clamp(0, Self::MAX_RANK);
+        let cost = Self::rank_cost(rank) - Self::rank_cost(self.current_rank());
+        let available = talent_points.get();
+        if cost > available {
+            bail!("Not enough talent points: {} needed, {} available", cost, available);
+        }
+
+        talent_points.update(|tp| tp - cost);
+        self.set_rank(rank, evolution);
+        Ok(())
+    }
+
+    pub fn spent_talent_points(powers: &[RcRef<Power>]) -> i32 {
+        powers.iter().map(|power| Self::rank_cost(power.borrow().current_rank())).sum()
+    }
+
+    pub fn respec(talent_points: &RcCell<i32>, powers: &[RcRef<Power>]) {
+        for power in powers {
+            let power = power.borrow();
+            talent_points.update(|tp| tp + Self::rank_cost(power.current_rank()));
+            power.set_rank(0, 0);
+        }
+    }
 }
 
 #[rcize_fields]
@@ -86,8 +206,8 @@ pub struct Weapon {
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", weapon_class_name)]
 pub struct WeaponMod {
-    weapon_class_name: String,
-    weapon_mod_class_names: Vec<String>,
+    pub weapon_class_name: String,
+    pub weapon_mod_class_names: Vec<String>,
 }
 
 #[rcize_fields]
@@ -0,0 +1,185 @@
+use anyhow::{bail, Result};
+use serde::Deserialize;
+
+use super::player::{Player, Power};
+
+#[derive(Deserialize)]
+pub struct Me3ClassPower {
+    pub name: String,
+    pub power_class_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct Me3PlayerClass {
+    pub class_name: String,
+    pub localized_class_name: i32,
+    #[serde(default)]
+    pub powers: Vec<Me3ClassPower>,
+}
+
+impl Me3PlayerClass {
+    fn owns(&self, power: &Power) -> bool {
+        self.powers.iter().any(|class_power| {
+            class_power.power_class_name.eq_ignore_ascii_case(&power.power_class_name())
+        })
+    }
+}
+
+#[derive(Deserialize, Deref)]
+pub struct Me3PlayerClassDb(Vec<Me3PlayerClass>);
+
+impl Me3PlayerClassDb {
+    /// Class by its class name, non combat classes share the powers of their class.
+    pub fn class(&self, class_name: &str) -> Option<&Me3PlayerClass> {
+        let class_name = class_name.trim_end_matches("NonCombat");
+        self.iter().find(|class| class.class_name.eq_ignore_ascii_case(class_name))
+    }
+
+    /// Changes the class of the player, replacing the old class powers by the new ones at rank 0.
+    /// Their talent points are refunded, bonus powers are kept.
+    /// If the powers of one of the classes aren't known nothing is changed and an error is returned.
+    pub fn change_class(&self, player: &mut Player, class_name: &str) -> Result<()> {
+        let new_class = match self.class(class_name) {
+            Some(new_class) => new_class,
+            None => bail!("Unknown class: {}", class_name),
+        };
+        let old_class = match self.class(&player.class_name()) {
+            Some(old_class) if !old_class.powers.is_empty() && !new_class.powers.is_empty() => {
+                old_class
+            }
+            _ => bail!("The class powers aren't in the database, the class wasn't changed"),
+        };
+
+        *player.class_name_mut() = class_name.to_owned();
+        player.set_localized_class_name(new_class.localized_class_name);
+
+        // Old class powers
+        let mut refund = 0;
+        let mut removed = Vec::new();
+        player.powers_mut().retain(|power| {
+            let power = power.borrow();
+            let is_class_power = old_class.owns(&power);
+            if is_class_power {
+                refund += Power::rank_cost(power.current_rank());
+                removed.push(power.power_class_name().clone());
+            }
+            !is_class_power
+        });
+        player.talent_points.update(|tp| tp + refund);
+
+        // New class powers, unless the player already has them as bonus powers
+        for (idx, class_power) in new_class.powers.iter().enumerate() {
+            let has_power = player.powers().iter().any(|power| {
+                power
+                    .borrow()
+                    .power_class_name()
+                    .eq_ignore_ascii_case(&class_power.power_class_name)
+            });
+            if has_power {
+                continue;
+            }
+
+            let power = Power::default();
+            *power.name.borrow_mut() = class_power.name.clone();
+            *power.power_class_name.borrow_mut() = class_power.power_class_name.clone();
+            power.wheel_display_index.set(idx as i32);
+            player.powers_mut().push(power.into());
+        }
+
+        // Mapped powers are stored without their package
+        for mapped_power in [&player.mapped_power_1, &player.mapped_power_2, &player.mapped_power_3]
+        {
+            let mut mapped_power = mapped_power.borrow_mut();
+            let is_removed = removed.iter().any(|class_name| {
+                let class_name = class_name.rsplit('.').next().unwrap_or_default();
+                class_name.eq_ignore_ascii_case(&mapped_power)
+            });
+            if is_removed {
+                *mapped_power = "None".to_owned();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::mass_effect_3::{power_db::Me3PowerDb, Me3SaveGame},
+        unreal,
+    };
+
+    fn player_class_db() -> Result<Me3PlayerClassDb> {
+        let input = fs::read_to_string("databases/me3_player_class_db.ron")?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn class_powers_in_save() -> Result<()> {
+        let player_class_db = player_class_db()?;
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let player = me3_save_game.player();
+
+        // Every Adept power of the database is in the save
+        let adept = player_class_db.class(&player.class_name()).unwrap();
+        assert_eq!(adept.localized_class_name, player.localized_class_name());
+        let class_powers =
+            player.powers().iter().filter(|power| adept.owns(&power.borrow())).count();
+        assert_eq!(class_powers, adept.powers.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn change_class() -> Result<()> {
+        let player_class_db = player_class_db()?;
+        let input = fs::read_to_string("databases/me3_power_db.ron")?;
+        let me3_power_db: Me3PowerDb = ron::from_str(&input)?;
+        let adept = "SFXGame.SFXPawn_PlayerAdept";
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let powers = {
+            let mut player = me3_save_game.player.borrow_mut();
+            let powers = player.powers().len();
+
+            // The Vanguard powers aren't known, nothing is changed
+            let class_name = player.class_name().clone();
+            let vanguard = "SFXGame.SFXPawn_PlayerVanguardNonCombat";
+            assert!(player_class_db.change_class(&mut player, vanguard).is_err());
+            assert!(player_class_db.change_class(&mut player, "SFXGame.SFXPawn_Unknown").is_err());
+            assert_eq!(*player.class_name(), class_name);
+
+            // From the non combat Adept to the Adept
+            player_class_db.change_class(&mut player, adept)?;
+            powers
+        };
+
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let player = me3_save_game.player();
+        assert_eq!(*player.class_name(), adept);
+        assert_eq!(player.localized_class_name(), 93954);
+
+        // Spent points are refunded, Unity and the bonus power are kept
+        assert!(me3_power_db.check_player(&player).is_none());
+        assert_eq!(player.powers().len(), powers);
+        assert!(player.powers().iter().any(|power| *power.borrow().name() == "Unity"));
+        assert!(player.powers().iter().any(|power| *power.borrow().name() == "AriaLash"));
+        let adept = player_class_db.class(adept).unwrap();
+        let class_powers: Vec<_> =
+            player.powers().iter().filter(|power| adept.owns(&power.borrow())).cloned().collect();
+        assert_eq!(class_powers.len(), adept.powers.len());
+        assert!(class_powers.iter().all(|power| power.borrow().current_rank() == 0));
+
+        Ok(())
+    }
+}
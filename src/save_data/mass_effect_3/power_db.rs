@@ -0,0 +1,189 @@
+use serde::Deserialize;
+
+use super::{player::Player, squad::Henchman};
+
+#[derive(Deserialize)]
+pub struct Me3PowerInfo {
+    pub name: String,
+    pub label: String,
+    #[serde(default)]
+    pub evolutions: Vec<[String; 2]>,
+}
+
+#[derive(Deserialize)]
+pub struct Me3PowerDb {
+    powers: Vec<Me3PowerInfo>,
+    player_talent_points: Vec<(i32, i32)>,
+    henchman_talent_points: Vec<(i32, i32)>,
+}
+
+impl Me3PowerDb {
+    pub fn power(&self, name: &str) -> Option<&Me3PowerInfo> {
+        self.powers.iter().find(|power| power.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn label(&self, name: &str) -> String {
+        self.power(name).map(|power| power.label.clone()).unwrap_or_else(|| name.to_owned())
+    }
+
+    /// Name of an evolution of a rank from 4 to 6.
+    pub fn evolution_name(&self, name: &str, rank: i32, evolution: usize) -> String {
+        self.power(name)
+            .and_then(|power| power.evolutions.get((rank - 4) as usize))
+            .map(|evolutions| evolutions[evolution].clone())
+            .unwrap_or_else(|| format!("Evolution {}", if evolution == 0 { 'A' } else { 'B' }))
+    }
+
+    fn check_talent_points(
+        talent_points: &[(i32, i32)], level: i32, spent: i32, unspent: i32,
+    ) -> Option<String> {
+        // Levels never take points back, the next known level is a cap for the ones in between
+        let &(known, granted) = talent_points
+            .iter()
+            .filter(|&&(other, _)| other >= level)
+            .min_by_key(|&&(other, _)| other)?;
+        if known == level {
+            (spent + unspent != granted).then(|| {
+                format!(
+                    "{} talent points spent and {} unspent, level {} grants {}",
+                    spent, unspent, level, granted
+                )
+            })
+        } else {
+            (spent + unspent > granted).then(|| {
+                format!(
+                    "{} talent points spent and {} unspent, even level {} grants only {}",
+                    spent, unspent, known, granted
+                )
+            })
+        }
+    }
+
+    /// Spent and unspent talent points that don't add up to what the level grants if it's known,
+    /// or that are more than a higher level grants.
+    pub fn check_player(&self, player: &Player) -> Option<String> {
+        Self::check_talent_points(
+            &self.player_talent_points,
+            player.level(),
+            player.spent_talent_points(),
+            player.talent_points(),
+        )
+    }
+
+    pub fn check_henchman(&self, henchman: &Henchman) -> Option<String> {
+        Self::check_talent_points(
+            &self.henchman_talent_points,
+            henchman.character_level(),
+            henchman.spent_talent_points(),
+            henchman.talent_points(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::mass_effect_3::{player::Power, Me3SaveGame},
+        unreal,
+    };
+
+    #[test]
+    fn deserialize_power_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_power_db.ron")?;
+        let me3_power_db: Me3PowerDb = ron::from_str(&input)?;
+
+        for power in &me3_power_db.powers {
+            assert!(power.evolutions.len() <= 3);
+            assert!(std::ptr::eq(me3_power_db.power(&power.name).unwrap(), power));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn talent_points_match_save() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_power_db.ron")?;
+        let me3_power_db: Me3PowerDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let player = me3_save_game.player();
+        assert!(me3_power_db.check_player(&player).is_none());
+        assert!(player.powers().iter().all(|power| power.borrow().has_valid_evolutions()));
+
+        // The Citadel squadmates have a fixed build missing a point
+        for henchman in me3_save_game.squad().iter() {
+            let henchman = henchman.borrow();
+            let citadel = ["hench_wrex", "hench_samara", "hench_jack"];
+            assert_eq!(
+                me3_power_db.check_henchman(&henchman).is_some(),
+                citadel.contains(&henchman.tag().as_str())
+            );
+            assert!(henchman.powers().iter().all(|power| power.borrow().has_valid_evolutions()));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn talent_points_cap() {
+        let talent_points = [(1, 1), (60, 90)];
+        assert!(Me3PowerDb::check_talent_points(&talent_points, 1, 1, 0).is_none());
+        assert!(Me3PowerDb::check_talent_points(&talent_points, 1, 0, 2).is_some());
+        assert!(Me3PowerDb::check_talent_points(&talent_points, 30, 40, 5).is_none());
+        assert!(Me3PowerDb::check_talent_points(&talent_points, 30, 80, 11).is_some());
+        assert!(Me3PowerDb::check_talent_points(&talent_points, 61, 100, 0).is_none());
+    }
+
+    #[test]
+    fn evolutions() -> Result<()> {
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        {
+            let mut player = me3_save_game.player.borrow_mut();
+            let talent_points = player.talent_points() + player.spent_talent_points();
+            let pull =
+                player.powers().iter().find(|power| *power.borrow().name() == "Pull").cloned();
+            let pull = pull.unwrap();
+            assert_eq!(
+                (4..=Power::MAX_RANK).map(|rank| pull.borrow().evolution(rank)).collect::<Vec<_>>(),
+                [Some(1), Some(1), Some(0)]
+            );
+
+            player.respec();
+            assert_eq!(player.talent_points(), talent_points);
+            assert_eq!(player.spent_talent_points(), 0);
+
+            let pull = pull.borrow();
+            assert!(pull.rank_up(&player.talent_points, 7, 1).is_ok());
+            assert!(pull.has_valid_evolutions());
+            assert_eq!(pull.current_rank(), Power::MAX_RANK);
+            assert_eq!(player.talent_points(), talent_points - 21);
+
+            player.set_talent_points(0);
+            assert!(pull.rank_up(&player.talent_points, 1, 0).is_ok());
+            assert_eq!(player.talent_points(), 20);
+        }
+
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let player = me3_save_game.player();
+        let pull = player.powers().iter().find(|power| *power.borrow().name() == "Pull").cloned();
+        let pull = pull.unwrap();
+        let pull = pull.borrow();
+        assert_eq!(pull.current_rank(), 1);
+        assert!(pull.has_valid_evolutions());
+
+        player.talent_points.set(10);
+        assert!(pull.rank_up(&player.talent_points, 5, 1).is_err());
+        assert_eq!(pull.current_rank(), 1);
+
+        Ok(())
+    }
+}
@@ -1,19 +1,136 @@
 use serde::{Deserialize, Serialize};
 
-use super::player::{Power, Weapon, WeaponMod};
+use super::{
+    player::{Player, Power, Weapon, WeaponMod},
+    weapon_db::EMPTY_SLOT,
+};
 use crate::save_data::shared::player::WeaponLoadout;
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", tag)]
 pub struct Henchman {
-    tag: String,
-    powers: Vec<Power>,
-    character_level: i32,
-    talent_points: i32,
-    weapon_loadout: WeaponLoadout,
+    pub tag: String,
+    pub powers: Vec<Power>,
+    pub character_level: i32,
+    pub talent_points: i32,
+    pub weapon_loadout: WeaponLoadout,
     mapped_power: String,
-    weapon_mods: Vec<WeaponMod>,
-    grenades: i32,
+    pub weapon_mods: Vec<WeaponMod>,
+    pub grenades: i32,
     weapons: Vec<Weapon>,
 }
+
+impl Henchman {
+    pub fn spent_talent_points(&self) -> i32 {
+        Power::spent_talent_points(&self.powers())
+    }
+
+    /// Resets all the powers and refunds their talent points.
+    pub fn respec(&mut self) {
+        Power::respec(&self.talent_points, &self.powers());
+    }
+
+    /// Copies the player's weapons and their mods into the loadout slots the squadmate uses.
+    /// Unused slots stay empty as squadmates can't carry every weapon type.
+    pub fn copy_loadout(&self, player: &Player) {
+        let (from, to) = (player.weapons_loadout(), self.weapon_loadout());
+        let slots = [
+            (&from.assault_rifle, &to.assault_rifle),
+            (&from.shotgun, &to.shotgun),
+            (&from.sniper_rifle, &to.sniper_rifle),
+            (&from.submachine_gun, &to.submachine_gun),
+            (&from.pistol, &to.pistol),
+        ];
+
+        let mut weapon_mods = self.weapon_mods.borrow_mut();
+        for (from, to) in slots {
+            let weapon = from.borrow().clone();
+            let is_used = |slot: &str| !slot.is_empty() && slot != EMPTY_SLOT;
+            if !is_used(&weapon) || !is_used(&to.borrow()) {
+                continue;
+            }
+
+            *to.borrow_mut() = weapon.clone();
+            weapon_mods
+                .retain(|other| !other.borrow().weapon_class_name().eq_ignore_ascii_case(&weapon));
+
+            let player_mods = player.weapons_mods();
+            let player_mods = player_mods
+                .iter()
+                .find(|other| other.borrow().weapon_class_name().eq_ignore_ascii_case(&weapon));
+            if let Some(player_mods) = player_mods {
+                let player_mods = player_mods.borrow();
+                let copy = WeaponMod::default();
+                *copy.weapon_class_name.borrow_mut() = weapon;
+                *copy.weapon_mod_class_names.borrow_mut() = player_mods
+                    .weapon_mod_class_names()
+                    .iter()
+                    .map(|class_name| class_name.borrow().clone().into())
+                    .collect();
+                weapon_mods.push(copy.into());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::mass_effect_3::{weapon_db::Me3WeaponDb, Me3SaveGame},
+        unreal,
+    };
+
+    #[test]
+    fn copy_loadout() -> Result<()> {
+        let (locust, talon, katana) = (
+            "SFXGameContent.SFXWeapon_SMG_Locust",
+            "SFXGameContent.SFXWeapon_Pistol_Talon",
+            "SFXGameContent.SFXWeapon_Shotgun_Katana",
+        );
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        {
+            let player = me3_save_game.player();
+            // The player carries a Locust and a Talon
+            assert_eq!(*player.weapons_loadout().submachine_gun(), locust);
+            assert_eq!(*player.weapons_loadout().pistol(), talon);
+            for henchman in me3_save_game.squad().iter() {
+                henchman.borrow().copy_loadout(&player);
+            }
+        }
+
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let player_mods =
+            |weapon: &str| Me3WeaponDb::mods(&me3_save_game.player().weapons_mods, weapon);
+        let squad = me3_save_game.squad();
+        let henchman = |tag: &str| {
+            squad.iter().find(|henchman| *henchman.borrow().tag() == tag).cloned().unwrap()
+        };
+
+        // EDI uses an SMG and a pistol, both are replaced with their mods
+        let edi = henchman("hench_edi");
+        let edi = edi.borrow();
+        assert_eq!(*edi.weapon_loadout().submachine_gun(), locust);
+        assert_eq!(*edi.weapon_loadout().pistol(), talon);
+        assert_eq!(*edi.weapon_loadout().shotgun(), EMPTY_SLOT);
+        assert_eq!(Me3WeaponDb::mods(&edi.weapon_mods, locust), player_mods(locust));
+        assert_eq!(Me3WeaponDb::mods(&edi.weapon_mods, talon), player_mods(talon));
+
+        // Wrex has no SMG slot and the player no shotgun
+        let wrex = henchman("hench_wrex");
+        let wrex = wrex.borrow();
+        assert_eq!(*wrex.weapon_loadout().submachine_gun(), EMPTY_SLOT);
+        assert_eq!(*wrex.weapon_loadout().shotgun(), katana);
+        assert_eq!(*wrex.weapon_loadout().pistol(), talon);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct Me3SquadMember {
+    pub tag: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct Me3SquadDb {
+    henchmen: Vec<Me3SquadMember>,
+}
+
+impl Me3SquadDb {
+    pub fn henchmen(&self) -> &[Me3SquadMember] {
+        &self.henchmen
+    }
+
+    /// Name of a squadmate, the tag if it's unknown.
+    pub fn name(&self, tag: &str) -> String {
+        self.henchmen
+            .iter()
+            .find(|henchman| henchman.tag.eq_ignore_ascii_case(tag))
+            .map(|henchman| henchman.name.clone())
+            .unwrap_or_else(|| tag.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    #[test]
+    fn deserialize_squad_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_squad_db.ron")?;
+        let squad_db: Me3SquadDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let squad = me3_save_game.squad();
+
+        // Every squadmate of the save is known, Ashley died on Virmire in that one
+        for henchman in squad.iter() {
+            let tag = henchman.borrow().tag().clone();
+            if tag.starts_with("hench_") {
+                assert!(squad_db.henchmen().iter().any(|other| other.tag == tag));
+            }
+        }
+        assert_eq!(squad_db.name("hench_prothean"), "Javik");
+        assert_eq!(squad_db.name("hench_ashley"), "Ashley Williams");
+        assert_eq!(squad_db.name("global_anderson"), "global_anderson");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use serde::Deserialize;
+
+use super::{player::Player, plot::PlotTable};
+
+/// Values from 10000 are multipliers (10000 = x1.0, 15000 = x1.5) rather than a strength.
+pub fn is_multiplier(value: i32) -> bool {
+    value >= 10_000
+}
+
+/// `readiness` is the galactic readiness in percent.
+pub fn effective_military_strength(total_military_strength: i32, readiness: i32) -> i32 {
+    total_military_strength * readiness / 100
+}
+
+#[derive(Deserialize)]
+pub struct Me3WarAsset {
+    pub id: i32,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub category: String,
+    #[serde(default)]
+    pub strength: i32,
+    #[serde(default)]
+    pub plots: Vec<usize>,
+}
+
+impl Me3WarAsset {
+    pub fn label(&self) -> String {
+        match self.name {
+            Some(ref name) => format!("{} ({})", name, self.category),
+            None => format!("Asset {} ({})", self.id, self.category),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Me3Ending {
+    pub name: String,
+    pub min_ems: i32,
+    #[serde(default)]
+    pub plot: Option<usize>,
+}
+
+impl Me3Ending {
+    pub fn is_available(&self, ems: i32, plot: &PlotTable) -> bool {
+        ems >= self.min_ems
+            || self.plot.map_or(false, |idx| plot.booleans().get(idx).map_or(false, |b| *b))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Me3WarAssetsDb {
+    assets: Vec<Me3WarAsset>,
+    endings: Vec<Me3Ending>,
+}
+
+impl Me3WarAssetsDb {
+    pub fn assets(&self) -> &[Me3WarAsset] {
+        &self.assets
+    }
+
+    pub fn endings(&self) -> &[Me3Ending] {
+        &self.endings
+    }
+
+    pub fn asset(&self, id: i32) -> Option<&Me3WarAsset> {
+        self.assets.iter().find(|asset| asset.id == id)
+    }
+
+    /// Adds the asset to the player, along with the plots granting it if it's known.
+    pub fn add_asset(&self, player: &mut Player, plot: &mut PlotTable, id: i32, value: i32) {
+        player.war_assets_mut().insert(id, value.into());
+
+        if let Some(asset) = self.asset(id) {
+            let mut booleans = plot.booleans_mut();
+            for &idx in &asset.plots {
+                if idx >= booleans.len() {
+                    booleans.resize(idx + 1, false);
+                }
+                booleans.set(idx, true);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    #[test]
+    fn deserialize_war_assets_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_war_assets_db.ron")?;
+        let me3_war_assets_db: Me3WarAssetsDb = ron::from_str(&input)?;
+
+        let mut ids: Vec<_> = me3_war_assets_db.assets().iter().map(|asset| asset.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), me3_war_assets_db.assets().len());
+
+        // The strengths come from this save
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let player = me3_save_game.player();
+        for asset in me3_war_assets_db.assets() {
+            assert_eq!(
+                player.war_assets().get(&asset.id).map(|value| value.get()),
+                Some(asset.strength)
+            );
+            assert_eq!(is_multiplier(asset.strength), asset.category == "Multiplier");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn military_strength() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_war_assets_db.ron")?;
+        let me3_war_assets_db: Me3WarAssetsDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let tms = me3_save_game.player().total_military_strength();
+        assert_eq!(tms, 7969);
+
+        // The Collector Base was saved in this save
+        let plot = me3_save_game.plot();
+        let available = |ems| -> Vec<_> {
+            me3_war_assets_db
+                .endings()
+                .iter()
+                .filter(|ending| ending.is_available(ems, &plot))
+                .map(|ending| ending.name.as_str())
+                .collect()
+        };
+        assert_eq!(available(effective_military_strength(tms, 10)), ["Refuse", "Control"]);
+        assert_eq!(available(effective_military_strength(tms, 50)).len(), 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_remove_asset() -> Result<()> {
+        let input = r#"(assets: [(id: 9999, category: "", plots: [31000])], endings: [])"#;
+        let me3_war_assets_db: Me3WarAssetsDb = ron::from_str(input)?;
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let tms = me3_save_game.player().total_military_strength();
+        {
+            let mut player = me3_save_game.player.borrow_mut();
+            let mut plot = me3_save_game.plot.borrow_mut();
+            me3_war_assets_db.add_asset(&mut player, &mut plot, 9999, 100);
+            player.remove_war_asset(0);
+        }
+
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        assert_eq!(me3_save_game.player().total_military_strength(), tms + 100 - 290);
+        assert!(me3_save_game.plot().booleans()[31000]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,490 @@
+use std::mem;
+
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use super::{
+    player::{Player, WeaponMod},
+    plot::PlotTable,
+    squad::Henchman,
+};
+use crate::save_data::{shared::player::WeaponLoadout, RcCell, RcRef};
+
+/// What the game stores in an empty loadout slot.
+pub const EMPTY_SLOT: &str = "None";
+
+pub type PlayerVariables = IndexMap<String, RcCell<i32>>;
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq)]
+pub enum Me3WeaponType {
+    AssaultRifle,
+    Shotgun,
+    SniperRifle,
+    SubmachineGun,
+    Pistol,
+}
+
+impl Me3WeaponType {
+    pub fn loadout_slot(self, loadout: &WeaponLoadout) -> &RcRef<String> {
+        match self {
+            Me3WeaponType::AssaultRifle => &loadout.assault_rifle,
+            Me3WeaponType::Shotgun => &loadout.shotgun,
+            Me3WeaponType::SniperRifle => &loadout.sniper_rifle,
+            Me3WeaponType::SubmachineGun => &loadout.submachine_gun,
+            Me3WeaponType::Pistol => &loadout.pistol,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Me3Weapon {
+    pub class_name: String,
+    pub name: String,
+    #[serde(default)]
+    pub unlock: Option<usize>,
+}
+
+impl Me3Weapon {
+    // Weapons without a known boolean can't be checked
+    pub fn is_unlocked(&self, plot: &PlotTable) -> bool {
+        self.unlock.map_or(true, |idx| plot.booleans().get(idx).map_or(false, |b| *b))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Me3WeaponModInfo {
+    pub class_name: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct Me3WeaponCategory {
+    pub weapon_type: Me3WeaponType,
+    pub name: String,
+    pub weapons: Vec<Me3Weapon>,
+    pub mods: Vec<Me3WeaponModInfo>,
+}
+
+#[derive(Deserialize)]
+pub struct Me3HenchmanWeapons {
+    pub tag: String,
+    pub slots: Vec<Me3WeaponType>,
+}
+
+#[derive(Deserialize)]
+pub struct Me3WeaponDb {
+    types: Vec<Me3WeaponCategory>,
+    pub max_weapon_level: i32,
+    pub max_mod_level: i32,
+    henchmen: Vec<Me3HenchmanWeapons>,
+}
+
+impl Me3WeaponDb {
+    pub fn types(&self) -> &[Me3WeaponCategory] {
+        &self.types
+    }
+
+    pub fn weapon(&self, class_name: &str) -> Option<(&Me3WeaponCategory, &Me3Weapon)> {
+        self.types.iter().find_map(|category| {
+            let weapon = category
+                .weapons
+                .iter()
+                .find(|weapon| weapon.class_name.eq_ignore_ascii_case(class_name))?;
+            Some((category, weapon))
+        })
+    }
+
+    pub fn weapon_mod(&self, class_name: &str) -> Option<(&Me3WeaponCategory, &Me3WeaponModInfo)> {
+        self.types.iter().find_map(|category| {
+            let weapon_mod = category
+                .mods
+                .iter()
+                .find(|weapon_mod| weapon_mod.class_name.eq_ignore_ascii_case(class_name))?;
+            Some((category, weapon_mod))
+        })
+    }
+
+    /// Name of a weapon or a mod, the class name if it's unknown.
+    pub fn name(&self, class_name: &str) -> String {
+        self.weapon(class_name)
+            .map(|(_, weapon)| weapon.name.clone())
+            .or_else(|| self.weapon_mod(class_name).map(|(_, weapon_mod)| weapon_mod.name.clone()))
+            .unwrap_or_else(|| class_name.to_owned())
+    }
+
+    // The game doesn't always use the same case for the class names
+    fn variable<'a>(variables: &'a PlayerVariables, class_name: &str) -> Option<&'a RcCell<i32>> {
+        variables.iter().find(|(name, _)| name.eq_ignore_ascii_case(class_name)).map(|(_, v)| v)
+    }
+
+    /// Upgrade level of a weapon or a mod, `0` if it has none.
+    pub fn upgrade_level(variables: &PlayerVariables, class_name: &str) -> i32 {
+        Self::variable(variables, class_name).map_or(0, RcCell::get)
+    }
+
+    pub fn set_upgrade_level(&self, variables: &mut PlayerVariables, class_name: &str, level: i32) {
+        let max_level = if self.weapon_mod(class_name).is_some() {
+            self.max_mod_level
+        } else {
+            self.max_weapon_level
+        };
+        let level = level.clamp(0, max_level);
+
+        match Self::variable(variables, class_name) {
+            Some(variable) => variable.set(level),
+            None => {
+                variables.insert(class_name.to_owned(), level.into());
+            }
+        }
+    }
+
+    /// Sets the boolean granting the weapon, an unlocked weapon gets at least the first upgrade level.
+    pub fn set_unlocked(
+        &self, plot: &mut PlotTable, variables: &mut PlayerVariables, class_name: &str,
+        unlocked: bool,
+    ) {
+        if let Some(idx) = self.weapon(class_name).and_then(|(_, weapon)| weapon.unlock) {
+            let mut booleans = plot.booleans_mut();
+            if idx >= booleans.len() {
+                booleans.resize(idx + 1, false);
+            }
+            booleans.set(idx, unlocked);
+        }
+
+        if unlocked && Self::upgrade_level(variables, class_name) == 0 {
+            self.set_upgrade_level(variables, class_name, 1);
+        }
+    }
+
+    /// Mods attached to a weapon, `weapons_mods` is the list of the player or of a squadmate.
+    pub fn mods(weapons_mods: &RcRef<Vec<RcRef<WeaponMod>>>, weapon: &str) -> Vec<String> {
+        weapons_mods
+            .borrow()
+            .iter()
+            .find(|weapon_mods| {
+                weapon_mods.borrow().weapon_class_name().eq_ignore_ascii_case(weapon)
+            })
+            .map(|weapon_mods| {
+                weapon_mods
+                    .borrow()
+                    .weapon_mod_class_names()
+                    .iter()
+                    .map(|class_name| class_name.borrow().clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Attaches a mod to a weapon of its type, a weapon holds two mods at most.
+    pub fn attach_mod(
+        &self, weapons_mods: &RcRef<Vec<RcRef<WeaponMod>>>, weapon: &str, weapon_mod: &str,
+    ) -> Result<()> {
+        let weapon_type = self.weapon(weapon).map(|(category, _)| category.weapon_type);
+        let mod_type = self.weapon_mod(weapon_mod).map(|(category, _)| category.weapon_type);
+        if weapon_type != mod_type {
+            bail!("{} can't be attached to {}", self.name(weapon_mod), self.name(weapon));
+        }
+
+        let mods = Self::mods(weapons_mods, weapon);
+        if mods.iter().any(|other| other.eq_ignore_ascii_case(weapon_mod)) {
+            bail!("{} is already attached to {}", self.name(weapon_mod), self.name(weapon));
+        }
+        if mods.len() >= 2 {
+            bail!("{} already has two mods", self.name(weapon));
+        }
+
+        let mut weapons_mods = weapons_mods.borrow_mut();
+        let existing = weapons_mods
+            .iter()
+            .find(|weapon_mods| {
+                weapon_mods.borrow().weapon_class_name().eq_ignore_ascii_case(weapon)
+            })
+            .cloned();
+        let weapon_mods = existing.unwrap_or_else(|| {
+            let weapon_mods = WeaponMod::default();
+            *weapon_mods.weapon_class_name.borrow_mut() = weapon.to_owned();
+            let weapon_mods = RcRef::new(weapon_mods);
+            weapons_mods.push(RcRef::clone(&weapon_mods));
+            weapon_mods
+        });
+        weapon_mods.borrow_mut().weapon_mod_class_names_mut().push(weapon_mod.to_owned().into());
+        Ok(())
+    }
+
+    pub fn detach_mod(weapons_mods: &RcRef<Vec<RcRef<WeaponMod>>>, weapon: &str, weapon_mod: &str) {
+        for weapon_mods in weapons_mods.borrow().iter() {
+            let mut weapon_mods = weapon_mods.borrow_mut();
+            if weapon_mods.weapon_class_name().eq_ignore_ascii_case(weapon) {
+                weapon_mods
+                    .weapon_mod_class_names_mut()
+                    .retain(|other| !other.borrow().eq_ignore_ascii_case(weapon_mod));
+            }
+        }
+    }
+
+    /// Puts the weapon in the loadout slot of its type and returns the weapon it replaces.
+    pub fn equip_in_loadout(&self, loadout: &WeaponLoadout, weapon: &str) -> Result<String> {
+        let weapon_type = match self.weapon(weapon) {
+            Some((category, _)) => category.weapon_type,
+            None => bail!("Unknown weapon: {}", weapon),
+        };
+
+        let mut slot = weapon_type.loadout_slot(loadout).borrow_mut();
+        Ok(mem::replace(&mut *slot, weapon.to_owned()))
+    }
+
+    /// Puts the weapon in the loadout slot of its type, the primary or secondary weapon it replaces follows.
+    pub fn equip(&self, player: &Player, weapon: &str) -> Result<()> {
+        let replaced = self.equip_in_loadout(&player.weapons_loadout(), weapon)?;
+        for equipped in [&player.primary_weapon, &player.secondary_weapon] {
+            let mut equipped = equipped.borrow_mut();
+            if equipped.eq_ignore_ascii_case(&replaced) {
+                *equipped = weapon.to_owned();
+            }
+        }
+        Ok(())
+    }
+
+    /// Weapon types a squadmate uses, all of them if the squadmate is unknown.
+    pub fn henchman_slots(&self, tag: &str) -> Vec<Me3WeaponType> {
+        self.henchmen
+            .iter()
+            .find(|henchman| henchman.tag.eq_ignore_ascii_case(tag))
+            .map(|henchman| henchman.slots.clone())
+            .unwrap_or_else(|| self.types.iter().map(|category| category.weapon_type).collect())
+    }
+
+    /// Puts the weapon in the loadout slot of its type if the squadmate uses it.
+    pub fn equip_henchman(&self, henchman: &Henchman, weapon: &str) -> Result<()> {
+        if let Some((category, _)) = self.weapon(weapon) {
+            if !self.henchman_slots(&henchman.tag()).contains(&category.weapon_type) {
+                bail!("{} can't be used by this squadmate", category.name);
+            }
+        }
+        self.equip_in_loadout(&henchman.weapon_loadout(), weapon).map(|_| ())
+    }
+
+    pub fn unequip(loadout: &WeaponLoadout, weapon_type: Me3WeaponType) {
+        *weapon_type.loadout_slot(loadout).borrow_mut() = EMPTY_SLOT.to_owned();
+    }
+
+    /// Weapons of the loadout that don't fit their slot.
+    /// The unlocks are only checked with a plot, squadmates come with their own weapons.
+    pub fn loadout_issues(&self, loadout: &WeaponLoadout, plot: Option<&PlotTable>) -> Vec<String> {
+        let mut issues = Vec::new();
+        for category in &self.types {
+            let equipped = category.weapon_type.loadout_slot(loadout).borrow();
+            if equipped.is_empty() || *equipped == EMPTY_SLOT {
+                continue;
+            }
+
+            match self.weapon(&equipped) {
+                Some((other, _)) if other.weapon_type != category.weapon_type => issues
+                    .push(format!("{} is in the {} slot", self.name(&equipped), category.name)),
+                Some((_, weapon)) if plot.map_or(false, |plot| !weapon.is_unlocked(plot)) => {
+                    issues.push(format!("{} is equipped but not unlocked", weapon.name))
+                }
+                Some(_) => {}
+                None => issues.push(format!("Unknown weapon: {}", equipped)),
+            }
+        }
+        issues
+    }
+
+    /// Loadout issues, primary and secondary weapons missing from the loadout and invalid mods.
+    pub fn player_issues(&self, player: &Player, plot: &PlotTable) -> Vec<String> {
+        let loadout = player.weapons_loadout();
+        let mut issues = self.loadout_issues(&loadout, Some(plot));
+
+        let equipped: Vec<_> = self
+            .types
+            .iter()
+            .map(|category| category.weapon_type.loadout_slot(&loadout).borrow().clone())
+            .collect();
+        for (label, weapon) in
+            [("Primary", player.primary_weapon()), ("Secondary", player.secondary_weapon())]
+        {
+            let is_empty = weapon.is_empty() || *weapon == EMPTY_SLOT;
+            if !is_empty && !equipped.iter().any(|other| other.eq_ignore_ascii_case(&weapon)) {
+                issues.push(format!(
+                    "{} weapon {} isn't in the loadout",
+                    label,
+                    self.name(&weapon)
+                ));
+            }
+        }
+
+        issues.extend(self.mods_issues(&player.weapons_mods));
+        issues
+    }
+
+    /// Loadout issues, weapons in slots the squadmate doesn't use and invalid mods.
+    pub fn henchman_issues(&self, henchman: &Henchman) -> Vec<String> {
+        let loadout = henchman.weapon_loadout();
+        let mut issues = self.loadout_issues(&loadout, None);
+
+        let slots = self.henchman_slots(&henchman.tag());
+        for category in self.types.iter().filter(|category| !slots.contains(&category.weapon_type))
+        {
+            let equipped = category.weapon_type.loadout_slot(&loadout).borrow();
+            if !equipped.is_empty() && *equipped != EMPTY_SLOT {
+                issues.push(format!("{} isn't used by this squadmate", category.name));
+            }
+        }
+
+        issues.extend(self.mods_issues(&henchman.weapon_mods));
+        issues
+    }
+
+    /// Mods that don't fit their weapon and weapons with too many mods.
+    pub fn mods_issues(&self, weapons_mods: &RcRef<Vec<RcRef<WeaponMod>>>) -> Vec<String> {
+        let mut issues = Vec::new();
+        for weapon_mods in weapons_mods.borrow().iter() {
+            let weapon_mods = weapon_mods.borrow();
+            let weapon = weapon_mods.weapon_class_name();
+            let weapon_type = self.weapon(&weapon).map(|(category, _)| category.weapon_type);
+            let mods = weapon_mods.weapon_mod_class_names();
+            if mods.len() > 2 {
+                issues.push(format!("{} has more than two mods", self.name(&weapon)));
+            }
+            for weapon_mod in mods.iter() {
+                let weapon_mod = weapon_mod.borrow();
+                let mod_type =
+                    self.weapon_mod(&weapon_mod).map(|(category, _)| category.weapon_type);
+                // Unknown classes can't be checked
+                if matches!((weapon_type, mod_type), (Some(a), Some(b)) if a != b) {
+                    issues.push(format!(
+                        "{} can't be attached to {}",
+                        self.name(&weapon_mod),
+                        self.name(&weapon)
+                    ));
+                }
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    fn weapon_db() -> Result<Me3WeaponDb> {
+        let input = fs::read_to_string("databases/me3_weapon_db.ron")?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn deserialize_weapon_db() -> Result<()> {
+        let weapon_db = weapon_db()?;
+
+        // Class names are unique across types
+        for category in weapon_db.types() {
+            for weapon in &category.weapons {
+                let (other, _) = weapon_db.weapon(&weapon.class_name).unwrap();
+                assert!(other.weapon_type == category.weapon_type);
+            }
+            for weapon_mod in &category.mods {
+                let (other, _) = weapon_db.weapon_mod(&weapon_mod.class_name).unwrap();
+                assert!(other.weapon_type == category.weapon_type);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn weapons_in_save() -> Result<()> {
+        let weapon_db = weapon_db()?;
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let plot = me3_save_game.plot();
+
+        assert!(weapon_db.player_issues(&me3_save_game.player(), &plot).is_empty());
+        for henchman in me3_save_game.squad().iter() {
+            assert!(weapon_db.henchman_issues(&henchman.borrow()).is_empty());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn edit_weapons() -> Result<()> {
+        let weapon_db = weapon_db()?;
+        let (talon, locust, shuriken) = (
+            "SFXGameContent.SFXWeapon_Pistol_Talon",
+            "SFXGameContent.SFXWeapon_SMG_Locust",
+            "SFXGameContent.SFXWeapon_SMG_Shuriken",
+        );
+        let (smg_mod, pistol_mod) = (
+            "SFXGameContent.SFXWeaponMod_SMGAccuracy",
+            "SFXGameContent.SFXWeaponMod_PistolReloadSpeed",
+        );
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        {
+            let player = me3_save_game.player();
+            let mut plot = me3_save_game.plot.borrow_mut();
+            let mut variables = me3_save_game.player_variables.borrow_mut();
+
+            // Talon already has two mods
+            assert!(weapon_db.attach_mod(&player.weapons_mods, talon, pistol_mod).is_err());
+            assert!(weapon_db.attach_mod(&player.weapons_mods, shuriken, pistol_mod).is_err());
+            weapon_db.attach_mod(&player.weapons_mods, shuriken, smg_mod)?;
+
+            weapon_db.set_unlocked(&mut plot, &mut variables, shuriken, true);
+            weapon_db.set_upgrade_level(&mut variables, shuriken, 42);
+            weapon_db.equip(&player, shuriken)?;
+            assert!(weapon_db.equip(&player, smg_mod).is_err());
+
+            Me3WeaponDb::detach_mod(&player.weapons_mods, talon, pistol_mod);
+        }
+
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let player = me3_save_game.player();
+
+        assert_eq!(*player.weapons_loadout().submachine_gun(), shuriken);
+        assert_eq!(*player.secondary_weapon(), shuriken);
+        assert_ne!(*player.primary_weapon(), locust);
+        assert_eq!(Me3WeaponDb::mods(&player.weapons_mods, shuriken), [smg_mod]);
+        assert_eq!(Me3WeaponDb::mods(&player.weapons_mods, talon).len(), 1);
+        let variables = me3_save_game.player_variables();
+        assert_eq!(Me3WeaponDb::upgrade_level(&variables, shuriken), weapon_db.max_weapon_level);
+        assert!(weapon_db.player_issues(&player, &me3_save_game.plot()).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn equip_henchman() -> Result<()> {
+        let weapon_db = weapon_db()?;
+        let (locust, katana) =
+            ("SFXGameContent.SFXWeapon_SMG_Locust", "SFXGameContent.SFXWeapon_Shotgun_Katana");
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let squad = me3_save_game.squad();
+        let liara = squad.iter().find(|henchman| *henchman.borrow().tag() == "hench_liara");
+        let liara = liara.unwrap().borrow();
+
+        // Liara uses SMGs and pistols only
+        weapon_db.equip_henchman(&liara, locust)?;
+        assert!(weapon_db.equip_henchman(&liara, katana).is_err());
+        assert_eq!(*liara.weapon_loadout().submachine_gun(), locust);
+        assert_eq!(*liara.weapon_loadout().shotgun(), EMPTY_SLOT);
+
+        weapon_db.equip_in_loadout(&liara.weapon_loadout(), katana)?;
+        assert_eq!(weapon_db.henchman_issues(&liara).len(), 1);
+
+        Ok(())
+    }
+}
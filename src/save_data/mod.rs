@@ -181,6 +181,17 @@ impl<const LEN: usize> serde::Serialize for Dummy<LEN> {
     }
 }
 
+// SeqAccessExt: visitors read their fields one by one
+pub trait SeqAccessExt<'de>: de::SeqAccess<'de> {
+    /// Reads the next field, a missing one means the save is truncated or corrupted.
+    fn next_field<T: Deserialize<'de>>(&mut self) -> Result<T, Self::Error> {
+        self.next_element()?
+            .ok_or_else(|| de::Error::custom("missing field, the save is corrupted"))
+    }
+}
+
+impl<'de, A: de::SeqAccess<'de>> SeqAccessExt<'de> for A {}
+
 // List<T> : Vec<T> qui se (dé)sérialise sans précision de longueur
 #[derive(Deref, DerefMut, From, Clone)]
 pub struct List<T>(Vec<T>)
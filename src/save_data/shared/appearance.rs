@@ -1,8 +1,9 @@
-use anyhow::Result;
+use anyhow::{ensure, Context, Result};
 use indexmap::IndexMap;
 use serde::{ser::SerializeTupleStruct, Deserialize, Deserializer, Serialize, Serializer};
 
 use super::Vector;
+use crate::save_data::RcRef;
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
@@ -46,6 +47,249 @@ pub struct HeadMorph {
     pub texture_parameters: IndexMap<String, String>,
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum HeadMorphPart {
+    HairMesh,
+    AccessoryMesh,
+    MorphFeatures,
+    OffsetBones,
+    LodVertices,
+    ScalarParameters,
+    VectorParameters,
+    TextureParameters,
+}
+
+impl HeadMorphPart {
+    pub const ALL: [HeadMorphPart; 8] = [
+        HeadMorphPart::HairMesh,
+        HeadMorphPart::AccessoryMesh,
+        HeadMorphPart::MorphFeatures,
+        HeadMorphPart::OffsetBones,
+        HeadMorphPart::LodVertices,
+        HeadMorphPart::ScalarParameters,
+        HeadMorphPart::VectorParameters,
+        HeadMorphPart::TextureParameters,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HeadMorphPart::HairMesh => "Hair mesh",
+            HeadMorphPart::AccessoryMesh => "Accessory mesh",
+            HeadMorphPart::MorphFeatures => "Morph features",
+            HeadMorphPart::OffsetBones => "Offset bones",
+            HeadMorphPart::LodVertices => "LOD vertices",
+            HeadMorphPart::ScalarParameters => "Scalar parameters",
+            HeadMorphPart::VectorParameters => "Vector parameters",
+            HeadMorphPart::TextureParameters => "Texture parameters",
+        }
+    }
+}
+
+#[derive(Default, Clone, PartialEq)]
+pub struct HeadMorphDiff {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+impl HeadMorphDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0 && self.changed == 0
+    }
+
+    fn from_maps<T, F>(current: &IndexMap<String, T>, imported: &IndexMap<String, T>, eq: F) -> Self
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut diff = HeadMorphDiff::default();
+        for (key, value) in imported {
+            match current.get(key) {
+                Some(current) if !eq(current, value) => diff.changed += 1,
+                Some(_) => {}
+                None => diff.added += 1,
+            }
+        }
+        diff.removed = current.keys().filter(|key| !imported.contains_key(*key)).count();
+        diff
+    }
+
+    fn from_vertices(current: &[RcRef<Vector>], imported: &[RcRef<Vector>]) -> Self {
+        let changed = current
+            .iter()
+            .zip(imported)
+            .filter(|(current, imported)| !vector_eq(current, imported))
+            .count();
+        HeadMorphDiff {
+            added: imported.len().saturating_sub(current.len()),
+            removed: current.len().saturating_sub(imported.len()),
+            changed,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        HeadMorphDiff {
+            added: self.added + other.added,
+            removed: self.removed + other.removed,
+            changed: self.changed + other.changed,
+        }
+    }
+}
+
+impl HeadMorph {
+    fn lods(&self) -> [&RcRef<Vec<RcRef<Vector>>>; 4] {
+        [&self.lod0_vertices, &self.lod1_vertices, &self.lod2_vertices, &self.lod3_vertices]
+    }
+
+    pub fn diff(&self, imported: &HeadMorph, part: HeadMorphPart) -> HeadMorphDiff {
+        match part {
+            HeadMorphPart::HairMesh => HeadMorphDiff {
+                changed: (*self.hair_mesh() != *imported.hair_mesh()) as usize,
+                ..Default::default()
+            },
+            HeadMorphPart::AccessoryMesh => {
+                let current = self.accessory_mesh();
+                let imported = imported.accessory_mesh();
+                let contains = |list: &[RcRef<String>], mesh: &RcRef<String>| {
+                    list.iter().any(|other| *other.borrow() == *mesh.borrow())
+                };
+                HeadMorphDiff {
+                    added: imported.iter().filter(|mesh| !contains(&current, mesh)).count(),
+                    removed: current.iter().filter(|mesh| !contains(&imported, mesh)).count(),
+                    changed: 0,
+                }
+            }
+            HeadMorphPart::MorphFeatures => HeadMorphDiff::from_maps(
+                &self.morph_features(),
+                &imported.morph_features(),
+                |a, b| a.get() == b.get(),
+            ),
+            HeadMorphPart::OffsetBones => {
+                HeadMorphDiff::from_maps(&self.offset_bones(), &imported.offset_bones(), vector_eq)
+            }
+            HeadMorphPart::LodVertices => self
+                .lods()
+                .iter()
+                .zip(imported.lods())
+                .map(|(current, imported)| {
+                    HeadMorphDiff::from_vertices(&current.borrow(), &imported.borrow())
+                })
+                .fold(HeadMorphDiff::default(), HeadMorphDiff::merge),
+            HeadMorphPart::ScalarParameters => HeadMorphDiff::from_maps(
+                &self.scalar_parameters(),
+                &imported.scalar_parameters(),
+                |a, b| a.get() == b.get(),
+            ),
+            HeadMorphPart::VectorParameters => HeadMorphDiff::from_maps(
+                &self.vector_parameters(),
+                &imported.vector_parameters(),
+                |a, b| {
+                    let (a, b) = (a.borrow(), b.borrow());
+                    a.r == b.r && a.g == b.g && a.b == b.b && a.a == b.a
+                },
+            ),
+            HeadMorphPart::TextureParameters => HeadMorphDiff::from_maps(
+                &self.texture_parameters(),
+                &imported.texture_parameters(),
+                |a, b| *a.borrow() == *b.borrow(),
+            ),
+        }
+    }
+
+    /// Builds a new head morph from `self` with the selected `parts` taken from `imported`.
+    pub fn merge(&self, imported: HeadMorph, parts: &[HeadMorphPart]) -> Result<HeadMorph> {
+        if parts.contains(&HeadMorphPart::LodVertices) {
+            for (i, (current, imported)) in self.lods().iter().zip(imported.lods()).enumerate() {
+                let (current, imported) = (current.borrow().len(), imported.borrow().len());
+                ensure!(
+                    current == imported,
+                    "LOD{} vertex count mismatch: current head has {} vertices, imported head has {}",
+                    i,
+                    current,
+                    imported
+                );
+            }
+        }
+
+        let mut merged = self.clone();
+        for part in parts {
+            match part {
+                HeadMorphPart::HairMesh => merged.hair_mesh = RcRef::clone(&imported.hair_mesh),
+                HeadMorphPart::AccessoryMesh => {
+                    merged.accessory_mesh = RcRef::clone(&imported.accessory_mesh)
+                }
+                HeadMorphPart::MorphFeatures => {
+                    merged.morph_features = RcRef::clone(&imported.morph_features)
+                }
+                HeadMorphPart::OffsetBones => {
+                    merged.offset_bones = RcRef::clone(&imported.offset_bones)
+                }
+                HeadMorphPart::LodVertices => {
+                    merged.lod0_vertices = RcRef::clone(&imported.lod0_vertices);
+                    merged.lod1_vertices = RcRef::clone(&imported.lod1_vertices);
+                    merged.lod2_vertices = RcRef::clone(&imported.lod2_vertices);
+                    merged.lod3_vertices = RcRef::clone(&imported.lod3_vertices);
+                }
+                HeadMorphPart::ScalarParameters => {
+                    merged.scalar_parameters = RcRef::clone(&imported.scalar_parameters)
+                }
+                HeadMorphPart::VectorParameters => {
+                    merged.vector_parameters = RcRef::clone(&imported.vector_parameters)
+                }
+                HeadMorphPart::TextureParameters => {
+                    merged.texture_parameters = RcRef::clone(&imported.texture_parameters)
+                }
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Other `vector_parameters` about the same subject as `name`,
+    /// e.g. `HED_Hair_Colour_Vector` and `HAIR_Tint_Vector`.
+    pub fn similar_vector_parameters(&self, name: &str) -> Vec<String> {
+        fn subjects(name: &str) -> Vec<String> {
+            const GENERIC: [&str; 7] = ["hed", "eye", "addn", "colour", "color", "tint", "vector"];
+            name.split('_')
+                .map(str::to_lowercase)
+                .filter(|token| !token.is_empty() && !GENERIC.contains(&token.as_str()))
+                .collect()
+        }
+
+        let name_subjects = subjects(name);
+        self.vector_parameters()
+            .keys()
+            .filter(|other| {
+                *other != name && subjects(other).iter().any(|s| name_subjects.contains(s))
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn copy_vector_parameter_to_similar(&mut self, name: &str) {
+        let color = match self.vector_parameters().get(name) {
+            Some(color) => color.borrow().clone(),
+            None => return,
+        };
+        let similar = self.similar_vector_parameters(name);
+
+        let parameters = self
+            .vector_parameters()
+            .iter()
+            .map(|(key, value)| {
+                let value =
+                    if similar.contains(key) { color.clone().into() } else { RcRef::clone(value) };
+                (key.clone(), value)
+            })
+            .collect();
+        // New `RcRef` so the views are refreshed
+        self.vector_parameters = RcRef::new(parameters);
+    }
+}
+
+fn vector_eq(a: &RcRef<Vector>, b: &RcRef<Vector>) -> bool {
+    let (a, b) = (a.borrow(), b.borrow());
+    a.x.get() == b.x.get() && a.y.get() == b.y.get() && a.z.get() == b.z.get()
+}
+
 #[derive(Default, Clone)]
 pub struct LinearColor {
     pub r: f32,
@@ -54,6 +298,75 @@ pub struct LinearColor {
     pub a: f32,
 }
 
+impl LinearColor {
+    /// HDR multiplier of an emissive color, `1.0` for regular colors.
+    pub fn intensity(&self) -> f32 {
+        self.r.max(self.g).max(self.b).max(1.0)
+    }
+
+    /// Scales the color so its brightest channel is `intensity`, black has nothing to scale.
+    pub fn set_intensity(&mut self, intensity: f32) {
+        let max = self.r.max(self.g).max(self.b);
+        if max <= 0.0 {
+            return;
+        }
+
+        let ratio = intensity.max(1.0) / max;
+        self.r *= ratio;
+        self.g *= ratio;
+        self.b *= ratio;
+    }
+
+    /// Returns the 8-bit sRGB color, without the HDR intensity.
+    pub fn to_srgb(&self) -> (u8, u8, u8) {
+        let intensity = self.intensity();
+        let to_srgb = |c: f32| (linear_to_srgb(c / intensity) * 255.0).round() as u8;
+        (to_srgb(self.r), to_srgb(self.g), to_srgb(self.b))
+    }
+
+    /// Sets the color from 8-bit sRGB values, keeping the HDR intensity.
+    pub fn set_srgb(&mut self, r: u8, g: u8, b: u8) {
+        let intensity = self.intensity();
+        let to_linear = |c: u8| srgb_to_linear(c as f32 / 255.0) * intensity;
+        self.r = to_linear(r);
+        self.g = to_linear(g);
+        self.b = to_linear(b);
+    }
+
+    pub fn to_hex(&self) -> String {
+        let (r, g, b) = self.to_srgb();
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    }
+
+    pub fn set_hex(&mut self, hex: &str) -> Result<()> {
+        let hex = hex.trim().trim_start_matches('#');
+        ensure!(hex.len() == 6 && hex.is_ascii(), "invalid color");
+
+        let r = u8::from_str_radix(&hex[0..2], 16).context("invalid color")?;
+        let g = u8::from_str_radix(&hex[2..4], 16).context("invalid color")?;
+        let b = u8::from_str_radix(&hex[4..6], 16).context("invalid color")?;
+        self.set_srgb(r, g, b);
+        Ok(())
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    let c = c.clamp(0.0, 1.0);
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
 impl<'de> Deserialize<'de> for LinearColor {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -101,4 +414,72 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn linear_color_srgb() -> Result<()> {
+        let mut color = LinearColor { r: 0.5, g: 0.0, b: 1.0, a: 1.0 };
+        assert_eq!(color.to_hex(), "#BC00FF");
+
+        color.set_hex("#FF8000")?;
+        assert_eq!(color.to_srgb(), (255, 128, 0));
+        assert!((color.g - 0.2158).abs() < 0.001);
+
+        // HDR intensity is kept
+        color.set_intensity(4.0);
+        assert_eq!(color.r, 4.0);
+        color.set_hex("#00FF80")?;
+        assert_eq!(color.g, 4.0);
+        assert_eq!(color.to_hex(), "#00FF80");
+
+        // Dim colors are scaled from their brightest channel
+        let mut color = LinearColor { r: 0.5, g: 0.25, b: 0.0, a: 1.0 };
+        color.set_intensity(2.0);
+        assert_eq!((color.r, color.g, color.b), (2.0, 1.0, 0.0));
+
+        let mut black = LinearColor::default();
+        black.set_intensity(2.0);
+        assert_eq!((black.r, black.g, black.b), (0.0, 0.0, 0.0));
+
+        assert!(color.set_hex("#12345").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge_head_morph() -> Result<()> {
+        let me2 = fs::read("test/GibbedME2.me2headmorph")?;
+        let current: HeadMorph = unreal::Deserializer::from_bytes(&me2[31..])?;
+        let imported: HeadMorph = unreal::Deserializer::from_bytes(&me2[31..])?;
+        imported.hair_mesh.borrow_mut().push_str("_imported");
+        imported.lod0_vertices.borrow_mut().pop();
+
+        assert_eq!(current.diff(&imported, HeadMorphPart::HairMesh).changed, 1);
+        assert!(current.diff(&imported, HeadMorphPart::MorphFeatures).is_empty());
+
+        // Mismatching LOD vertex counts
+        let parts = [HeadMorphPart::HairMesh, HeadMorphPart::LodVertices];
+        assert!(current.merge(imported.clone(), &parts).is_err());
+
+        let merged = current.merge(imported, &[HeadMorphPart::HairMesh])?;
+        assert!(merged.hair_mesh().ends_with("_imported"));
+        assert!(!current.hair_mesh().ends_with("_imported"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn copy_vector_parameter_to_similar() -> Result<()> {
+        let me2 = fs::read("test/GibbedME2.me2headmorph")?;
+        let mut head_morph: HeadMorph = unreal::Deserializer::from_bytes(&me2[31..])?;
+
+        let similar = head_morph.similar_vector_parameters("HED_Aniso_Colour01_Vector");
+        assert_eq!(similar, ["HED_Aniso_Colour02_Vector"]);
+
+        head_morph.vector_parameters()["HED_Aniso_Colour01_Vector"].borrow_mut().r = 42.0;
+        head_morph.copy_vector_parameter_to_similar("HED_Aniso_Colour01_Vector");
+        assert_eq!(head_morph.vector_parameters()["HED_Aniso_Colour02_Vector"].borrow().r, 42.0);
+        assert_ne!(head_morph.vector_parameters()["SkinTone"].borrow().r, 42.0);
+
+        Ok(())
+    }
 }
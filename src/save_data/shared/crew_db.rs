@@ -0,0 +1,218 @@
+use serde::Deserialize;
+
+use super::plot::BitVec;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CrewStatus {
+    Missing,
+    Alive,
+    Recruited,
+    Loyal,
+    Dead,
+}
+
+impl CrewStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CrewStatus::Missing => "Missing",
+            CrewStatus::Alive => "Alive",
+            CrewStatus::Recruited => "Recruited",
+            CrewStatus::Loyal => "Loyal",
+            CrewStatus::Dead => "Dead",
+        }
+    }
+}
+
+/// Flags of a character in one game, the current one or an import.
+#[derive(Deserialize)]
+pub struct CrewFlags {
+    pub game: String,
+    /// The flags are in the imported ME1 plot of a ME2 save.
+    #[serde(default)]
+    pub me1_plot: bool,
+    /// Any of them set means the character is dead.
+    #[serde(default)]
+    pub dead: Vec<usize>,
+    /// Set to kill the character, the game where the death happens.
+    #[serde(default)]
+    pub kill: Vec<usize>,
+    #[serde(default)]
+    pub alive: Vec<usize>,
+    #[serde(default)]
+    pub recruited: Vec<usize>,
+    #[serde(default)]
+    pub loyal: Vec<usize>,
+}
+
+impl CrewFlags {
+    pub fn status(&self, booleans: &BitVec) -> CrewStatus {
+        let any =
+            |flags: &[usize]| flags.iter().any(|&idx| booleans.get(idx).map_or(false, |b| *b));
+        if any(&self.dead) {
+            CrewStatus::Dead
+        } else if any(&self.loyal) {
+            CrewStatus::Loyal
+        } else if any(&self.recruited) {
+            CrewStatus::Recruited
+        } else if any(&self.alive) {
+            CrewStatus::Alive
+        } else {
+            CrewStatus::Missing
+        }
+    }
+
+    /// Clears the death flags and sets the survival ones.
+    pub fn revive(&self, booleans: &mut BitVec) {
+        Self::set(booleans, &self.dead, false);
+        Self::set(booleans, &self.alive, true);
+    }
+
+    pub fn kill(&self, booleans: &mut BitVec) {
+        Self::set(booleans, &self.alive, false);
+        Self::set(booleans, &self.kill, true);
+    }
+
+    pub fn make_loyal(&self, booleans: &mut BitVec) {
+        Self::set(booleans, &self.loyal, true);
+    }
+
+    fn set(booleans: &mut BitVec, flags: &[usize], value: bool) {
+        for &idx in flags {
+            if idx >= booleans.len() {
+                booleans.resize(idx + 1, false);
+            }
+            booleans.set(idx, value);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CrewMember {
+    pub name: String,
+    pub games: Vec<CrewFlags>,
+}
+
+impl CrewMember {
+    pub fn can_be_killed(&self) -> bool {
+        self.games.iter().any(|flags| !flags.kill.is_empty())
+    }
+
+    pub fn can_be_loyal(&self) -> bool {
+        self.games.iter().any(|flags| !flags.loyal.is_empty())
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CrewDb {
+    crew: Vec<CrewMember>,
+}
+
+impl CrewDb {
+    pub fn crew(&self) -> &[CrewMember] {
+        &self.crew
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{mass_effect_2::Me2LeSaveGame, mass_effect_3::Me3SaveGame},
+        unreal,
+    };
+
+    fn crew_db(file: &str) -> Result<CrewDb> {
+        let input = fs::read_to_string(file)?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn deserialize_crew_db() -> Result<()> {
+        for file in
+            ["databases/me1_crew_db.ron", "databases/me2_crew_db.ron", "databases/me3_crew_db.ron"]
+        {
+            let crew_db = crew_db(file)?;
+            for flags in crew_db.crew().iter().flat_map(|member| &member.games) {
+                // Killing must be seen as a death
+                assert!(flags.kill.iter().all(|idx| flags.dead.contains(idx)), "{}", flags.game);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn revive_and_kill() -> Result<()> {
+        let crew_db = crew_db("databases/me2_crew_db.ron")?;
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        {
+            let plot = me2_save_game.plot();
+            let mut booleans = plot.booleans.borrow_mut();
+            let current = crew_db.crew().iter().flat_map(|member| &member.games);
+            for flags in current.filter(|flags| !flags.me1_plot) {
+                flags.kill(&mut booleans);
+                if !flags.kill.is_empty() {
+                    assert_eq!(flags.status(&booleans), CrewStatus::Dead);
+                }
+                flags.revive(&mut booleans);
+                assert_ne!(flags.status(&booleans), CrewStatus::Dead);
+                flags.make_loyal(&mut booleans);
+                if !flags.loyal.is_empty() {
+                    assert_eq!(flags.status(&booleans), CrewStatus::Loyal);
+                }
+            }
+        }
+
+        let output = unreal::Serializer::to_vec(&me2_save_game)?;
+        let _: Me2LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn revive_imported() -> Result<()> {
+        let crew_db = crew_db("databases/me3_crew_db.ron")?;
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let plot = me3_save_game.plot();
+        let mut booleans = plot.booleans.borrow_mut();
+        let wrex = crew_db.crew().iter().find(|member| member.name == "Wrex").unwrap();
+
+        // Dead in ME1, every game carrying the import sees it
+        for flags in &wrex.games {
+            flags.kill(&mut booleans);
+        }
+        assert!(wrex.games.iter().all(|flags| flags.status(&booleans) == CrewStatus::Dead));
+
+        for flags in &wrex.games {
+            flags.revive(&mut booleans);
+        }
+        assert!(wrex.games.iter().all(|flags| flags.status(&booleans) != CrewStatus::Dead));
+
+        Ok(())
+    }
+
+    #[test]
+    fn me1_squad() -> Result<()> {
+        let crew_db = crew_db("databases/me3_crew_db.ron")?;
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let plot = me3_save_game.plot();
+        let booleans = plot.booleans.borrow();
+        for name in ["Garrus", "Tali", "Liara"] {
+            let member = crew_db.crew().iter().find(|member| member.name == name).unwrap();
+            let me1 = member.games.iter().find(|flags| flags.game == "Mass Effect 1").unwrap();
+            assert_eq!(me1.status(&booleans), CrewStatus::Recruited, "{}", name);
+        }
+
+        Ok(())
+    }
+}
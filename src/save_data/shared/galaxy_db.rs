@@ -0,0 +1,104 @@
+use serde::Deserialize;
+
+// Ids are built as `cluster * 10_000 + system * 100 + planet`
+pub fn cluster_id(id: i32) -> i32 {
+    id / 10_000
+}
+
+pub fn system_id(id: i32) -> i32 {
+    id / 100 * 100
+}
+
+#[derive(Deserialize)]
+pub struct GalaxyPlanet {
+    pub id: i32,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub anomaly: bool,
+}
+
+#[derive(Deserialize)]
+pub struct GalaxySystem {
+    pub id: i32,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub planets: Vec<GalaxyPlanet>,
+}
+
+#[derive(Deserialize)]
+pub struct GalaxyCluster {
+    pub id: i32,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub systems: Vec<GalaxySystem>,
+}
+
+#[derive(Deserialize)]
+pub struct GalaxyDb {
+    clusters: Vec<GalaxyCluster>,
+}
+
+impl GalaxyDb {
+    pub fn systems(&self) -> impl Iterator<Item = &GalaxySystem> {
+        self.clusters.iter().flat_map(|cluster| &cluster.systems)
+    }
+
+    pub fn planets(&self) -> impl Iterator<Item = &GalaxyPlanet> {
+        self.systems().flat_map(|system| &system.planets)
+    }
+
+    pub fn planet(&self, id: i32) -> Option<&GalaxyPlanet> {
+        self.planets().find(|planet| planet.id == id)
+    }
+
+    pub fn cluster_name(&self, id: i32) -> String {
+        self.clusters
+            .iter()
+            .find(|cluster| cluster.id == id)
+            .and_then(|cluster| cluster.name.clone())
+            .unwrap_or_else(|| format!("Cluster {}", id))
+    }
+
+    pub fn system_name(&self, id: i32) -> String {
+        self.systems()
+            .find(|system| system.id == id)
+            .and_then(|system| system.name.clone())
+            .unwrap_or_else(|| format!("System {}", id))
+    }
+
+    pub fn planet_name(&self, id: i32) -> String {
+        self.planet(id)
+            .and_then(|planet| planet.name.clone())
+            .unwrap_or_else(|| format!("Planet {}", id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_galaxy_db() -> Result<()> {
+        for file in ["databases/me2_galaxy_db.ron", "databases/me3_galaxy_db.ron"] {
+            let input = fs::read_to_string(file)?;
+            let galaxy_db: GalaxyDb = ron::from_str(&input)?;
+
+            // Planets are grouped by the ids
+            for cluster in &galaxy_db.clusters {
+                for system in &cluster.systems {
+                    assert_eq!(cluster_id(system.id), cluster.id);
+                    for planet in &system.planets {
+                        assert_eq!(system_id(planet.id), system.id);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
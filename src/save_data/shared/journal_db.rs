@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+
+use super::plot::{PlotCodex, PlotCodexPage};
+use crate::save_data::{RcCell, RcRef};
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct JournalQuest {
+    pub id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub end_goals: Vec<i32>,
+}
+
+impl JournalQuest {
+    /// Whether a goal ending the quest is in its history, `None` if the ending goals aren't known.
+    pub fn is_completed(&self, history: &[i32]) -> Option<bool> {
+        (!self.end_goals.is_empty())
+            .then(|| history.iter().any(|goal| self.end_goals.contains(goal)))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CodexPage {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct CodexSection {
+    pub id: i32,
+    #[serde(default)]
+    pub name: String,
+    pub pages: Vec<CodexPage>,
+}
+
+#[derive(Deserialize)]
+pub struct JournalDb {
+    quests: Vec<JournalQuest>,
+    codex: Vec<CodexSection>,
+}
+
+impl JournalDb {
+    pub fn quest(&self, id: i32) -> Option<&JournalQuest> {
+        self.quests.iter().find(|quest| quest.id == id)
+    }
+
+    /// A quest of the database, the name and ending goals of `overrides` replace its own.
+    pub fn quest_with(&self, id: i32, overrides: &[JournalQuest]) -> Option<JournalQuest> {
+        let mut quest = self.quest(id).cloned();
+        if let Some(other) = overrides.iter().find(|quest| quest.id == id) {
+            let quest = quest.get_or_insert_with(|| JournalQuest {
+                id,
+                name: String::new(),
+                end_goals: Vec::new(),
+            });
+            if !other.name.is_empty() {
+                quest.name = other.name.clone();
+            }
+            if !other.end_goals.is_empty() {
+                quest.end_goals = other.end_goals.clone();
+            }
+        }
+        quest
+    }
+
+    pub fn section(&self, id: i32) -> Option<&CodexSection> {
+        self.codex.iter().find(|section| section.id == id)
+    }
+
+    pub fn section_name(&self, id: i32) -> String {
+        self.section(id)
+            .filter(|section| !section.name.is_empty())
+            .map(|section| section.name.clone())
+            .unwrap_or_else(|| format!("Codex {}", id))
+    }
+
+    pub fn page_name(&self, id: i32) -> String {
+        self.codex
+            .iter()
+            .flat_map(|section| &section.pages)
+            .find(|page| page.id == id)
+            .map(|page| page.name.clone())
+            .unwrap_or_else(|| format!("Page {}", id))
+    }
+
+    /// Adds the missing pages of every known codex entry, they are flagged as new like the game does.
+    pub fn unlock_codex(
+        &self, codex_ids: &mut Vec<RcCell<i32>>, codex_entries: &mut Vec<RcRef<PlotCodex>>,
+    ) {
+        for section in &self.codex {
+            let entry = match codex_ids.iter().position(|id| id.get() == section.id) {
+                Some(idx) => match codex_entries.get(idx) {
+                    Some(entry) => RcRef::clone(entry),
+                    None => continue,
+                },
+                None => {
+                    let entry = RcRef::new(PlotCodex::default());
+                    codex_ids.push(section.id.into());
+                    codex_entries.push(RcRef::clone(&entry));
+                    entry
+                }
+            };
+
+            let mut entry = entry.borrow_mut();
+            for page in &section.pages {
+                let is_unlocked =
+                    entry.pages().iter().any(|other| other.borrow().page() == page.id);
+                if !is_unlocked {
+                    let new_page = PlotCodexPage::default();
+                    new_page.page.set(page.id);
+                    new_page.is_new.set(true);
+                    entry.pages_mut().push(new_page.into());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{mass_effect_2::Me2LeSaveGame, mass_effect_3::Me3SaveGame},
+        unreal,
+    };
+
+    fn journal_db(file: &str) -> Result<JournalDb> {
+        let input = fs::read_to_string(file)?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn codex_in_saves() -> Result<()> {
+        // Every codex entry and page of the saves is known
+        let me2_journal_db = journal_db("databases/me2_journal_db.ron")?;
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let codex = me2_save_game.codex();
+        for (id, entry) in codex.codex_ids().iter().zip(codex.codex_entries().iter()) {
+            let section = me2_journal_db.section(id.get()).unwrap();
+            for page in entry.borrow().pages().iter() {
+                let page = page.borrow().page();
+                assert!(section.pages.iter().any(|other| other.id == page));
+            }
+        }
+
+        let me3_journal_db = journal_db("databases/me3_journal_db.ron")?;
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let codex = me3_save_game.codex();
+        for (id, entry) in codex.codex_ids().iter().zip(codex.codex_entries().iter()) {
+            let section = me3_journal_db.section(id.get()).unwrap();
+            for page in entry.borrow().pages().iter() {
+                let page = page.borrow().page();
+                assert!(section.pages.iter().any(|other| other.id == page));
+            }
+        }
+
+        let me1_journal_db = journal_db("databases/me1_journal_db.ron")?;
+        assert!(!me1_journal_db.codex.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unlock_codex() -> Result<()> {
+        let journal_db = journal_db("databases/me2_journal_db.ron")?;
+        let pages: usize = journal_db.codex.iter().map(|section| section.pages.len()).sum();
+
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        {
+            let codex = me2_save_game.codex();
+            let (mut codex_ids, mut codex_entries) =
+                (codex.codex_ids.borrow_mut(), codex.codex_entries.borrow_mut());
+            let unlocked: usize =
+                codex_entries.iter().map(|entry| entry.borrow().pages().len()).sum();
+            assert!(unlocked < pages);
+            journal_db.unlock_codex(&mut codex_ids, &mut codex_entries);
+        }
+
+        let output = unreal::Serializer::to_vec(&me2_save_game)?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let codex = me2_save_game.codex();
+        assert_eq!(codex.codex_ids().len(), journal_db.codex.len());
+        let unlocked: usize =
+            codex.codex_entries().iter().map(|entry| entry.borrow().pages().len()).sum();
+        assert_eq!(unlocked, pages);
+
+        Ok(())
+    }
+
+    #[test]
+    fn edit_quests() -> Result<()> {
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let (id, counter) = {
+            let mut journal = me3_save_game.journal.borrow_mut();
+            let id = journal.quest_ids().iter().map(|id| id.get()).max().unwrap_or_default() + 1;
+            let counter = journal.quest_progress_counter();
+            journal.add_goal(id, 3);
+            journal.add_goal(id, 5);
+            journal.add_goal(id, 3);
+            journal.remove_goal(id, 5);
+            (id, counter)
+        };
+
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let mut journal = me3_save_game.journal.borrow_mut();
+        assert_eq!(journal.quest_progress_counter(), counter + 3);
+        {
+            let quest = journal.quest(id).unwrap();
+            let quest = quest.borrow();
+            assert_eq!(quest.history().iter().map(|goal| goal.get()).collect::<Vec<_>>(), [3]);
+            assert_eq!(quest.quest_counter(), counter + 2);
+            assert!(quest.quest_updated());
+        }
+
+        journal.reset_quest(id);
+        let quest = journal.quest(id).unwrap();
+        let quest = quest.borrow();
+        assert!(quest.history().is_empty());
+        assert!(!quest.quest_updated());
+        assert_eq!(quest.active_goal(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn quest_completion() -> Result<()> {
+        // The quests named by the user are stored like the ones of the database
+        let quest = JournalQuest { id: 5246, name: "Named".to_owned(), end_goals: vec![4] };
+        let quest: JournalQuest = ron::from_str(&ron::to_string(&quest)?)?;
+        assert_eq!(quest.is_completed(&[1, 2]), Some(false));
+        assert_eq!(quest.is_completed(&[1, 4]), Some(true));
+
+        let quest: JournalQuest = ron::from_str(r#"(id: 5246, name: "")"#)?;
+        assert_eq!(quest.is_completed(&[1, 4]), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn override_quests() {
+        let quest = |id, name: &str, end_goals: Vec<i32>| JournalQuest {
+            id,
+            name: name.to_owned(),
+            end_goals,
+        };
+        let journal_db = JournalDb {
+            quests: vec![quest(1, "Known", vec![3]), quest(2, "Other", vec![4])],
+            codex: Vec::new(),
+        };
+        let overrides =
+            [quest(1, "Renamed", vec![]), quest(2, "", vec![5]), quest(6, "New", vec![])];
+
+        let renamed = journal_db.quest_with(1, &overrides).unwrap();
+        assert_eq!((renamed.name.as_str(), renamed.end_goals.as_slice()), ("Renamed", &[3][..]));
+        let ended = journal_db.quest_with(2, &overrides).unwrap();
+        assert_eq!((ended.name.as_str(), ended.end_goals.as_slice()), ("Other", &[5][..]));
+        assert_eq!(journal_db.quest_with(6, &overrides).unwrap().name, "New");
+        assert!(journal_db.quest_with(7, &overrides).is_none());
+    }
+}
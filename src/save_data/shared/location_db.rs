@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Level, Rotator, StreamingState, Vector};
+use crate::save_data::RcRef;
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct SafeLocation {
+    pub name: String,
+    pub base_level_name: String,
+    // ME3 only
+    #[serde(default)]
+    pub base_level_name_display_override: Option<String>,
+    pub location: (f32, f32, f32),
+    pub rotation: (i32, i32, i32),
+    /// Name, should be loaded, should be visible.
+    pub levels: Vec<(String, bool, bool)>,
+    /// Name, is active.
+    pub streaming_states: Vec<(String, bool)>,
+}
+
+impl SafeLocation {
+    /// Copies where a save currently is, so another save can be moved there.
+    pub fn capture(
+        name: String, base_level_name: String, base_level_name_display_override: Option<String>,
+        location: &Vector, rotation: &Rotator, levels: &[RcRef<Level>],
+        streaming_states: &[RcRef<StreamingState>],
+    ) -> Self {
+        SafeLocation {
+            name,
+            base_level_name,
+            base_level_name_display_override,
+            location: location.xyz(),
+            rotation: (rotation.pitch.get(), rotation.yaw.get(), rotation.roll.get()),
+            levels: levels
+                .iter()
+                .map(|level| {
+                    let level = level.borrow();
+                    let name = level.name.borrow().clone();
+                    (name, level.should_be_loaded.get(), level.should_be_visible.get())
+                })
+                .collect(),
+            streaming_states: streaming_states
+                .iter()
+                .map(|state| {
+                    let state = state.borrow();
+                    let name = state.name.borrow().clone();
+                    (name, state.is_active.get())
+                })
+                .collect(),
+        }
+    }
+
+    pub fn location(&self) -> Vector {
+        let (x, y, z) = self.location;
+        Vector::new(x, y, z)
+    }
+
+    pub fn rotation(&self) -> Rotator {
+        let (pitch, yaw, roll) = self.rotation;
+        Rotator::new(pitch, yaw, roll)
+    }
+
+    pub fn levels(&self) -> Vec<RcRef<Level>> {
+        self.levels
+            .iter()
+            .map(|(name, should_be_loaded, should_be_visible)| {
+                Level::new(name.clone(), *should_be_loaded, *should_be_visible).into()
+            })
+            .collect()
+    }
+
+    pub fn streaming_states(&self) -> Vec<RcRef<StreamingState>> {
+        self.streaming_states
+            .iter()
+            .map(|(name, is_active)| StreamingState::new(name.clone(), *is_active).into())
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct LocationDb {
+    locations: Vec<SafeLocation>,
+}
+
+impl LocationDb {
+    pub fn locations(&self) -> &[SafeLocation] {
+        &self.locations
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            mass_effect_2::{Me2LeSaveGame, Me2SaveGame},
+            mass_effect_3::Me3SaveGame,
+        },
+        unreal,
+    };
+
+    fn location_db(file: &str) -> Result<LocationDb> {
+        let input = fs::read_to_string(file)?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn relocate_me2() -> Result<()> {
+        let location_db = location_db("databases/me2_location_db.ron")?;
+        assert!(!location_db.locations().is_empty());
+
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let mut me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let mut me2_le_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        for spot in location_db.locations() {
+            me2_save_game.relocate(spot);
+            let output = unreal::Serializer::to_vec(&me2_save_game)?;
+            let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&output)?;
+            assert_eq!(*me2_save_game.base_level_name(), spot.base_level_name);
+            assert_eq!(me2_save_game.location().xyz(), spot.location);
+            assert_eq!(me2_save_game.levels().len(), spot.levels.len());
+            assert_eq!(me2_save_game.streaming_states().len(), spot.streaming_states.len());
+
+            me2_le_save_game.relocate(spot);
+            let output = unreal::Serializer::to_vec(&me2_le_save_game)?;
+            let me2_le_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+            assert_eq!(*me2_le_save_game.base_level_name(), spot.base_level_name);
+            assert_eq!(me2_le_save_game.location().xyz(), spot.location);
+            assert_eq!(me2_le_save_game.levels().len(), spot.levels.len());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn relocate_me3() -> Result<()> {
+        let location_db = location_db("databases/me3_location_db.ron")?;
+        assert!(!location_db.locations().is_empty());
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let mut me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // The locations are copied from saves, moving the save to its own spot changes nothing
+        // (the checksum isn't serialized)
+        let spot = &location_db.locations()[0];
+        me3_save_game.relocate(spot);
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+        assert!(input.starts_with(&output));
+
+        for spot in location_db.locations() {
+            me3_save_game.relocate(spot);
+            let output = unreal::Serializer::to_vec(&me3_save_game)?;
+            let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&output)?;
+            assert_eq!(*me3_save_game.base_level_name(), spot.base_level_name);
+            assert_eq!(me3_save_game.location().xyz(), spot.location);
+            assert_eq!(me3_save_game.streaming_states().len(), spot.streaming_states.len());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn capture_me3() -> Result<()> {
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let mut me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Moving a save to where it already is changes nothing
+        let mut spot = me3_save_game.current_spot();
+        me3_save_game.relocate(&spot);
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+        assert!(input.starts_with(&output));
+
+        // A spot without a display name override resets it
+        spot.base_level_name_display_override = None;
+        me3_save_game.relocate(&spot);
+        let spot = me3_save_game.current_spot();
+        assert_eq!(spot.base_level_name_display_override.as_deref(), Some("None"));
+
+        Ok(())
+    }
+}
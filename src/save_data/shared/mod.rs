@@ -1,6 +1,12 @@
 pub mod appearance;
+pub mod crew_db;
+pub mod galaxy_db;
+pub mod journal_db;
+pub mod location_db;
+pub mod morality_db;
 pub mod player;
 pub mod plot;
+pub mod romance_db;
 
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -58,6 +64,16 @@ pub struct Vector {
     z: f32,
 }
 
+impl Vector {
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Vector { x: x.into(), y: y.into(), z: z.into() }
+    }
+
+    pub fn xyz(&self) -> (f32, f32, f32) {
+        (self.x.get(), self.y.get(), self.z.get())
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
@@ -74,6 +90,12 @@ pub struct Rotator {
     roll: i32,
 }
 
+impl Rotator {
+    pub fn new(pitch: i32, yaw: i32, roll: i32) -> Self {
+        Rotator { pitch: pitch.into(), yaw: yaw.into(), roll: roll.into() }
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", name)]
@@ -83,6 +105,16 @@ pub struct Level {
     should_be_visible: bool,
 }
 
+impl Level {
+    pub fn new(name: String, should_be_loaded: bool, should_be_visible: bool) -> Self {
+        Level {
+            name: name.into(),
+            should_be_loaded: should_be_loaded.into(),
+            should_be_visible: should_be_visible.into(),
+        }
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", name)]
@@ -91,19 +123,77 @@ pub struct StreamingState {
     is_active: bool,
 }
 
+impl StreamingState {
+    pub fn new(name: String, is_active: bool) -> Self {
+        StreamingState { name: name.into(), is_active: is_active.into() }
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
 pub struct Kismet {
-    guid: Guid,
-    value: bool,
+    pub guid: Guid,
+    pub value: bool,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
 pub struct Door {
-    guid: Guid,
-    current_state: u8,
-    old_state: u8,
+    pub guid: Guid,
+    pub current_state: u8,
+    pub old_state: u8,
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use crate::{
+        save_data::{
+            mass_effect_2::Me2LeSaveGame,
+            mass_effect_3::{Me3SaveGame, Placeable, PlaceableState},
+            RcRef,
+        },
+        unreal,
+    };
+
+    #[test]
+    fn reset_records() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Removing a record gives the object back its state from the level
+        let len = me2_save_game.doors().len();
+        let door = RcRef::clone(&me2_save_game.doors()[0]);
+        me2_save_game.doors.borrow_mut().retain(|other| *other != door);
+
+        let output = unreal::Serializer::to_vec(&me2_save_game)?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+        assert_eq!(me2_save_game.doors().len(), len - 1);
+        let guid = door.borrow().guid().hyphenated();
+        assert!(me2_save_game
+            .doors()
+            .iter()
+            .all(|other| other.borrow().guid().hyphenated() != guid));
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let placeable = RcRef::new(Placeable::default());
+        *placeable.borrow().is_destroyed.borrow_mut() = PlaceableState::Yes;
+        me3_save_game.placeables.borrow_mut().push(RcRef::clone(&placeable));
+        assert!(placeable.borrow().is_disabled());
+        placeable.borrow_mut().reenable();
+
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let placeables = me3_save_game.placeables();
+        assert_eq!(placeables.len(), 1);
+        assert!(!placeables[0].borrow().is_disabled());
+
+        Ok(())
+    }
 }
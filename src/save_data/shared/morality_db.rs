@@ -0,0 +1,158 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+pub enum MoralityKind {
+    Paragon,
+    Renegade,
+    Reputation,
+    Charm,
+    Intimidate,
+}
+
+#[derive(Deserialize)]
+pub struct MoralityCheck {
+    pub name: String,
+    pub kind: MoralityKind,
+    /// Points, reputation or skill rank needed to pass.
+    #[serde(default)]
+    pub value: f32,
+    /// Plot integer holding the points needed, when the game computes them.
+    #[serde(default)]
+    pub plot: Option<usize>,
+}
+
+impl MoralityCheck {
+    pub fn needed(&self, integer: impl Fn(usize) -> Option<i32>) -> f32 {
+        self.plot.and_then(integer).map_or(self.value, |value| value as f32)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MoralityDb {
+    pub paragon: usize,
+    pub renegade: usize,
+    // ME3 only
+    #[serde(default)]
+    pub reputation: Option<usize>,
+    #[serde(default)]
+    pub reputation_points: Option<usize>,
+    #[serde(default)]
+    pub persuade_multiplier: Option<usize>,
+    /// Points filling a morality bar, if known.
+    #[serde(default)]
+    pub bar_points: Option<i32>,
+    /// Plot integers of the points filling each bar, when the game keeps them (ME2).
+    #[serde(default)]
+    pub paragon_potential: Option<usize>,
+    #[serde(default)]
+    pub renegade_potential: Option<usize>,
+    checks: Vec<MoralityCheck>,
+}
+
+impl MoralityDb {
+    pub fn checks(&self) -> &[MoralityCheck] {
+        &self.checks
+    }
+
+    /// Points filling the paragon or renegade bar, if the game keeps them in the plot.
+    pub fn potential(
+        &self, kind: MoralityKind, integer: impl Fn(usize) -> Option<i32>,
+    ) -> Option<i32> {
+        let potential = match kind {
+            MoralityKind::Paragon => self.paragon_potential,
+            MoralityKind::Renegade => self.renegade_potential,
+            _ => None,
+        };
+        potential.and_then(integer)
+    }
+}
+
+/// Part of the bar filled by `points`, in percent.
+pub fn bar_percentage(points: i32, bar_points: i32) -> f32 {
+    if bar_points <= 0 {
+        return 0.0;
+    }
+    (points as f32 * 100.0 / bar_points as f32).clamp(0.0, 100.0)
+}
+
+/// Fewest points filling at least `percentage` of the bar.
+pub fn points_for_percentage(percentage: f32, bar_points: i32) -> i32 {
+    let percentage = percentage.clamp(0.0, 100.0);
+    let points = (percentage as f64 * bar_points as f64 / 100.0).ceil() as i32;
+    // Rounding of the bar percentage can still fall short by a point
+    if bar_percentage(points, bar_points) < percentage {
+        points + 1
+    } else {
+        points
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{mass_effect_2::Me2LeSaveGame, RcCell},
+        unreal,
+    };
+
+    #[test]
+    fn deserialize_morality_db() -> Result<()> {
+        for file in [
+            "databases/me1_morality_db.ron",
+            "databases/me2_morality_db.ron",
+            "databases/me3_morality_db.ron",
+        ] {
+            let input = fs::read_to_string(file)?;
+            let morality_db: MoralityDb = ron::from_str(&input)?;
+            assert_ne!(morality_db.paragon, morality_db.renegade);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn me2_checks() -> Result<()> {
+        let input = fs::read_to_string("databases/me2_morality_db.ron")?;
+        let morality_db: MoralityDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let plot = me2_save_game.plot();
+        let integer = |idx| plot.integers().get(idx).map(|value: &RcCell<i32>| value.get());
+
+        // The hard checks need a full bar
+        assert_eq!(morality_db.potential(MoralityKind::Paragon, integer), Some(879));
+        assert_eq!(morality_db.potential(MoralityKind::Renegade, integer), Some(865));
+        let needed: Vec<_> = morality_db
+            .checks()
+            .iter()
+            .filter(|check| check.kind == MoralityKind::Paragon)
+            .map(|check| check.needed(integer))
+            .collect();
+        assert_eq!(needed, [175.0, 439.0, 879.0, 1352.0, 1674.0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn target_percentage() {
+        for bar_points in [1, 7, 340, 1234] {
+            for percentage in 0..=100 {
+                let percentage = percentage as f32;
+                let points = points_for_percentage(percentage, bar_points);
+                assert!(bar_percentage(points, bar_points) >= percentage);
+                if points > 0 {
+                    assert!(bar_percentage(points - 1, bar_points) < percentage);
+                }
+            }
+        }
+
+        assert_eq!(points_for_percentage(75.0, 340), 255);
+        assert_eq!(bar_percentage(500, 340), 100.0);
+        assert_eq!(bar_percentage(-5, 340), 0.0);
+    }
+}
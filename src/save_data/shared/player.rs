@@ -19,10 +19,10 @@ pub enum Notoriety {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, RawUi)]
 pub struct WeaponLoadout {
-    assault_rifle: String,
-    shotgun: String,
-    sniper_rifle: String,
-    submachine_gun: String,
-    pistol: String,
-    heavy_weapon: String,
+    pub assault_rifle: String,
+    pub shotgun: String,
+    pub sniper_rifle: String,
+    pub submachine_gun: String,
+    pub pistol: String,
+    pub heavy_weapon: String,
 }
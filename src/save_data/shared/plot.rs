@@ -3,6 +3,8 @@ use bitvec::prelude::*;
 use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::save_data::RcRef;
+
 #[derive(Deref, DerefMut, Clone)]
 pub struct BitVec(bitvec::vec::BitVec<u32, Lsb0>);
 
@@ -38,40 +40,86 @@ pub struct PlotTable {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
 pub struct Journal {
-    quest_progress_counter: i32,
-    quest_progress: Vec<PlotQuest>,
-    quest_ids: Vec<i32>,
+    pub quest_progress_counter: i32,
+    pub quest_progress: Vec<PlotQuest>,
+    pub quest_ids: Vec<i32>,
+}
+
+impl Journal {
+    /// Progress of a quest by its id, `None` if it hasn't started.
+    pub fn quest(&self, id: i32) -> Option<RcRef<PlotQuest>> {
+        let idx = self.quest_ids().iter().position(|other| other.get() == id)?;
+        self.quest_progress().get(idx).cloned()
+    }
+
+    /// Adds a goal to the history of a quest, starting it if needed.
+    /// The quest is flagged as updated and moved to the top of the journal like the game does.
+    pub fn add_goal(&mut self, id: i32, goal: i32) {
+        let counter = self.quest_progress_counter();
+        let quest = match self.quest(id) {
+            Some(quest) => quest,
+            None => {
+                let quest = RcRef::new(PlotQuest::default());
+                self.quest_ids_mut().push(id.into());
+                self.quest_progress_mut().push(RcRef::clone(&quest));
+                quest
+            }
+        };
+
+        let quest = quest.borrow();
+        if !quest.history().iter().any(|other| other.get() == goal) {
+            quest.history.borrow_mut().push(goal.into());
+        }
+        quest.quest_counter.set(counter);
+        quest.quest_updated.set(true);
+        self.quest_progress_counter.set(counter + 1);
+    }
+
+    pub fn remove_goal(&mut self, id: i32, goal: i32) {
+        if let Some(quest) = self.quest(id) {
+            quest.borrow_mut().history_mut().retain(|other| other.get() != goal);
+        }
+    }
+
+    /// Clears the history of a quest, its marker goes back to the start of the quest.
+    pub fn reset_quest(&mut self, id: i32) {
+        if let Some(quest) = self.quest(id) {
+            let quest = quest.borrow();
+            quest.history.borrow_mut().clear();
+            quest.quest_updated.set(false);
+        }
+    }
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
 pub struct Codex {
-    codex_entries: Vec<PlotCodex>,
-    codex_ids: Vec<i32>,
+    pub codex_entries: Vec<PlotCodex>,
+    pub codex_ids: Vec<i32>,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", quest_counter)]
 pub struct PlotQuest {
-    quest_counter: i32,
-    quest_updated: bool,
-    history: Vec<i32>,
+    pub quest_counter: i32,
+    pub quest_updated: bool,
+    pub history: Vec<i32>,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
 pub struct PlotCodex {
-    pages: Vec<PlotCodexPage>,
+    pub pages: Vec<PlotCodexPage>,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", page)]
 pub struct PlotCodexPage {
-    page: i32,
-    is_new: bool,
+    pub page: i32,
+    pub is_new: bool,
 }
 
 #[derive(Deserialize, Clone, PartialEq, Eq)]
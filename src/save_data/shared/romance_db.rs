@@ -0,0 +1,351 @@
+use std::iter;
+
+use serde::Deserialize;
+
+use super::plot::BitVec;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum RomanceStage {
+    None,
+    Flirted,
+    LockedIn,
+    Completed,
+    BreakUp,
+}
+
+impl RomanceStage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RomanceStage::None => "None",
+            RomanceStage::Flirted => "Flirted",
+            RomanceStage::LockedIn => "Locked in",
+            RomanceStage::Completed => "Completed",
+            RomanceStage::BreakUp => "Break up",
+        }
+    }
+
+    /// Whether reaching `target` goes through this stage, a break up follows a flirt.
+    fn leads_to(self, target: RomanceStage) -> bool {
+        match self {
+            RomanceStage::Flirted => target >= RomanceStage::Flirted,
+            RomanceStage::LockedIn => {
+                matches!(target, RomanceStage::LockedIn | RomanceStage::Completed)
+            }
+            _ => self == target,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RomanceFlags {
+    /// Booleans set to `true`.
+    #[serde(default)]
+    pub booleans: Vec<usize>,
+    #[serde(default)]
+    pub integers: Vec<(usize, i32)>,
+}
+
+impl RomanceFlags {
+    fn is_set(&self, booleans: &BitVec, integer: &impl Fn(usize) -> Option<i32>) -> bool {
+        let booleans_set = self.booleans.iter().all(|&idx| booleans.get(idx).map_or(false, |b| *b));
+        let integers_set = self.integers.iter().all(|&(idx, value)| integer(idx) == Some(value));
+        booleans_set && integers_set
+    }
+
+    fn is_empty(&self) -> bool {
+        self.booleans.is_empty() && self.integers.is_empty()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Romance {
+    pub name: String,
+    /// Integers written when no stage sets them, like the friendship value of a progress.
+    #[serde(default)]
+    pub none: Vec<(usize, i32)>,
+    /// Flags of each stage, stages without known flags are left out.
+    pub stages: Vec<(RomanceStage, RomanceFlags)>,
+}
+
+impl Romance {
+    /// The furthest stage whose flags are all set, a break up wins over the others.
+    pub fn stage(&self, booleans: &BitVec, integer: impl Fn(usize) -> Option<i32>) -> RomanceStage {
+        self.stages
+            .iter()
+            .filter(|(_, flags)| !flags.is_empty() && flags.is_set(booleans, &integer))
+            .map(|(stage, _)| *stage)
+            .max()
+            .unwrap_or(RomanceStage::None)
+    }
+
+    pub fn stages(&self) -> impl Iterator<Item = RomanceStage> + '_ {
+        iter::once(RomanceStage::None).chain(self.stages.iter().map(|(stage, _)| *stage))
+    }
+
+    /// Writes the flags of `target` and the stages leading to it, clears the flags of the other
+    /// stages the romance reached. A flag shared with other romances is only cleared by the one
+    /// that reached it.
+    pub fn set_stage(
+        &self, target: RomanceStage, booleans: &mut BitVec, integer: impl Fn(usize) -> Option<i32>,
+        mut set_integer: impl FnMut(usize, i32),
+    ) {
+        let (written, others): (Vec<_>, Vec<_>) =
+            self.stages.iter().partition(|(stage, _)| stage.leads_to(target));
+        let cleared: Vec<_> =
+            others.into_iter().filter(|(_, flags)| flags.is_set(booleans, &integer)).collect();
+        let is_written = |idx| {
+            written.iter().any(|(_, flags)| flags.integers.iter().any(|&(other, _)| other == idx))
+        };
+
+        for (_, flags) in cleared {
+            for &idx in &flags.booleans {
+                if let Some(mut flag) = booleans.get_mut(idx) {
+                    *flag = false;
+                }
+            }
+            for &(idx, _) in flags.integers.iter().filter(|(idx, _)| !is_written(*idx)) {
+                if let Some(&(_, value)) = self.none.iter().find(|(other, _)| *other == idx) {
+                    set_integer(idx, value);
+                }
+            }
+        }
+
+        for (_, flags) in written {
+            for &idx in &flags.booleans {
+                if idx >= booleans.len() {
+                    booleans.resize(idx + 1, false);
+                }
+                booleans.set(idx, true);
+            }
+            for &(idx, value) in &flags.integers {
+                set_integer(idx, value);
+            }
+        }
+    }
+}
+
+/// A romance flag a previous game left in the plot of the save.
+#[derive(Deserialize)]
+pub struct ImportedRomance {
+    pub game: String,
+    pub name: String,
+    pub boolean: usize,
+}
+
+#[derive(Deserialize)]
+pub struct RomanceDb {
+    romances: Vec<Romance>,
+    #[serde(default)]
+    imported: Vec<ImportedRomance>,
+    /// Set on import when there was no romance in the previous game.
+    #[serde(default)]
+    imported_none: Vec<(String, usize)>,
+}
+
+impl RomanceDb {
+    pub fn romances(&self) -> &[Romance] {
+        &self.romances
+    }
+
+    pub fn imported(&self) -> &[ImportedRomance] {
+        &self.imported
+    }
+
+    /// States the game can't be in, like two locked in romances.
+    pub fn contradictions(
+        &self, booleans: &BitVec, integer: impl Fn(usize) -> Option<i32>,
+    ) -> Vec<String> {
+        let mut contradictions = Vec::new();
+
+        let locked_in: Vec<_> = self
+            .romances
+            .iter()
+            .filter(|romance| {
+                matches!(
+                    romance.stage(booleans, &integer),
+                    RomanceStage::LockedIn | RomanceStage::Completed
+                )
+            })
+            .map(|romance| romance.name.as_str())
+            .collect();
+        if locked_in.len() > 1 {
+            contradictions.push(format!("Several locked in romances: {}", locked_in.join(", ")));
+        }
+
+        let is_set = |idx: usize| booleans.get(idx).map_or(false, |b| *b);
+        let mut games: Vec<_> =
+            self.imported.iter().map(|imported| imported.game.as_str()).collect();
+        games.sort_unstable();
+        games.dedup();
+        for game in games {
+            let imported: Vec<_> = self
+                .imported
+                .iter()
+                .filter(|imported| imported.game == game && is_set(imported.boolean))
+                .map(|imported| imported.name.as_str())
+                .collect();
+            if imported.len() > 1 {
+                contradictions.push(format!(
+                    "Several imported {} romances: {}",
+                    game,
+                    imported.join(", ")
+                ));
+            }
+
+            let none = self.imported_none.iter().any(|(other, idx)| other == game && is_set(*idx));
+            if none && !imported.is_empty() {
+                contradictions
+                    .push(format!("Imported {} romance flagged as both none and set", game));
+            }
+        }
+
+        contradictions
+    }
+
+    /// Sets the imported romance of `game`, `None` sets the no romance flag.
+    pub fn set_imported(&self, game: &str, romance: Option<&str>, booleans: &mut BitVec) {
+        let flags = self
+            .imported
+            .iter()
+            .filter(|imported| imported.game == game)
+            .map(|imported| (imported.boolean, Some(imported.name.as_str()) == romance))
+            .chain(
+                self.imported_none
+                    .iter()
+                    .filter(|(other, _)| other == game)
+                    .map(|(_, idx)| (*idx, romance.is_none())),
+            );
+        for (idx, value) in flags {
+            if idx >= booleans.len() {
+                booleans.resize(idx + 1, false);
+            }
+            booleans.set(idx, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            mass_effect_1_le::Me1LeSaveGame, mass_effect_2::Me2LeSaveGame,
+            mass_effect_3::Me3SaveGame,
+        },
+        unreal,
+    };
+
+    fn romance_db(file: &str) -> Result<RomanceDb> {
+        let input = fs::read_to_string(file)?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn deserialize_romance_db() -> Result<()> {
+        for file in [
+            "databases/me1_romance_db.ron",
+            "databases/me2_romance_db.ron",
+            "databases/me3_romance_db.ron",
+        ] {
+            let romance_db = romance_db(file)?;
+            for romance in romance_db.romances() {
+                // Stages are detected from the furthest one
+                assert!(romance.stages.windows(2).all(|w| w[0].0 < w[1].0), "{}", romance.name);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_romance_stage() -> Result<()> {
+        let romance_db = romance_db("databases/me2_romance_db.ron")?;
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        {
+            let plot = me2_save_game.plot();
+            let mut booleans = plot.booleans.borrow_mut();
+            let integers = &plot.integers;
+            let integer = |idx: usize| integers.borrow().get(idx).map(|v| v.get());
+            let set_integer = |idx: usize, value: i32| {
+                if let Some(v) = integers.borrow().get(idx) {
+                    v.set(value)
+                }
+            };
+
+            for romance in romance_db.romances() {
+                for stage in romance.stages().collect::<Vec<_>>() {
+                    romance.set_stage(stage, &mut booleans, integer, set_integer);
+                    assert_eq!(romance.stage(&booleans, integer), stage, "{}", romance.name);
+                }
+            }
+
+            let garrus = &romance_db.romances()[0];
+            let thane =
+                romance_db.romances().iter().find(|romance| romance.name == "Thane").unwrap();
+            garrus.set_stage(RomanceStage::LockedIn, &mut booleans, integer, set_integer);
+            thane.set_stage(RomanceStage::LockedIn, &mut booleans, integer, set_integer);
+            assert_eq!(romance_db.contradictions(&booleans, integer).len(), 1);
+            thane.set_stage(RomanceStage::None, &mut booleans, integer, set_integer);
+            assert!(romance_db.contradictions(&booleans, integer).is_empty());
+        }
+
+        let output = unreal::Serializer::to_vec(&me2_save_game)?;
+        let _: Me2LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn me1_completed_romance() -> Result<()> {
+        let romance_db = romance_db("databases/me1_romance_db.ron")?;
+        let input = fs::read("test/ME1LeExport.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let save_data = me1_save_game.save_data.borrow();
+        let plot = save_data.plot.borrow();
+        let mut booleans = plot.booleans.borrow_mut();
+        let integer = |_| None;
+        let romance = |name| romance_db.romances().iter().find(|romance| romance.name == name);
+        let (ashley, liara) = (romance("Ashley").unwrap(), romance("Liara").unwrap());
+        assert_eq!(liara.stage(&booleans, integer), RomanceStage::Completed);
+        assert_eq!(ashley.stage(&booleans, integer), RomanceStage::BreakUp);
+
+        // The shared completion flag is Liara's, Ashley doesn't clear it
+        ashley.set_stage(RomanceStage::None, &mut booleans, integer, |_, _| ());
+        assert_eq!(ashley.stage(&booleans, integer), RomanceStage::None);
+        assert_eq!(liara.stage(&booleans, integer), RomanceStage::Completed);
+        assert!(romance_db.contradictions(&booleans, integer).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_imported_romance() -> Result<()> {
+        let romance_db = romance_db("databases/me3_romance_db.ron")?;
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let plot = me3_save_game.plot();
+        let mut booleans = plot.booleans.borrow_mut();
+        let integers = &plot.integers;
+        let integer = |idx: usize| integers.borrow().get(&(idx as i32)).map(|v| v.get());
+
+        romance_db.set_imported("Mass Effect 1", Some("Liara"), &mut booleans);
+        assert!(romance_db.contradictions(&booleans, integer).is_empty());
+        romance_db.set_imported("Mass Effect 1", None, &mut booleans);
+        assert!(romance_db.contradictions(&booleans, integer).is_empty());
+        assert!(romance_db
+            .imported()
+            .iter()
+            .filter(|imported| imported.game == "Mass Effect 1")
+            .all(|imported| !booleans[imported.boolean]));
+
+        Ok(())
+    }
+}
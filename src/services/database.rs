@@ -5,138 +5,138 @@ use yew::{prelude::*, ContextProvider};
 
 use crate::{
     save_data::{
-        mass_effect_1::plot_db::Me1PlotDb, mass_effect_1_le::item_db::Me1ItemDb,
-        mass_effect_1_le::player_class_db::Me1LePlayerClassDb, mass_effect_2::plot_db::Me2PlotDb,
-        mass_effect_3::plot_db::Me3PlotDb, shared::plot::RawPlotDb,
+        mass_effect_1::plot_db::Me1PlotDb, mass_effect_1_le::galaxy_db::Me1LeGalaxyDb,
+        mass_effect_1_le::henchman_db::Me1LeHenchmanDb, mass_effect_1_le::item_db::Me1ItemDb,
+        mass_effect_1_le::player_class_db::Me1LePlayerClassDb,
+        mass_effect_1_le::progression_db::Me1LeProgressionDb,
+        mass_effect_2::import_bonus_db::Me2ImportBonusDb,
+        mass_effect_2::player_class_db::Me2PlayerClassDb, mass_effect_2::plot_db::Me2PlotDb,
+        mass_effect_2::research_db::Me2ResearchDb, mass_effect_2::squad_db::Me2SquadDb,
+        mass_effect_2::weapon_db::Me2WeaponDb, mass_effect_3::player_class_db::Me3PlayerClassDb,
+        mass_effect_3::plot_db::Me3PlotDb, mass_effect_3::power_db::Me3PowerDb,
+        mass_effect_3::squad_db::Me3SquadDb, mass_effect_3::war_assets_db::Me3WarAssetsDb,
+        mass_effect_3::weapon_db::Me3WeaponDb, shared::crew_db::CrewDb,
+        shared::galaxy_db::GalaxyDb, shared::journal_db::JournalDb,
+        shared::location_db::LocationDb, shared::morality_db::MoralityDb, shared::plot::RawPlotDb,
+        shared::romance_db::RomanceDb,
     },
     services::rpc,
 };
 
-pub enum Type {
-    Me1LePlayerClasses,
-    Me1Plot,
-    Me1RawPlot,
-    Me1Items,
-    Me2Plot,
-    Me2RawPlot,
-    Me3Plot,
-    Me3RawPlot,
-}
-
-pub enum Database {
-    Me1LePlayerClasses(Me1LePlayerClassDb),
-    Me1Plot(Me1PlotDb),
-    Me1RawPlot(RawPlotDb),
-    Me1Items(Me1ItemDb),
-    Me2Plot(Me2PlotDb),
-    Me2RawPlot(RawPlotDb),
-    Me3Plot(Me3PlotDb),
-    Me3RawPlot(RawPlotDb),
-}
-
-pub enum Msg {
-    LoadDatabase(Type),
-    DatabaseLoaded(Box<Database>),
-    Error(Error),
-}
-
-#[derive(Clone, Default)]
-pub struct Databases {
-    me1_le_player_classes: Option<Rc<Me1LePlayerClassDb>>,
-    me1_plot: Option<Rc<Me1PlotDb>>,
-    me1_raw_plot: Option<Rc<RawPlotDb>>,
-    me1_item_db: Option<Rc<Me1ItemDb>>,
-    me2_plot: Option<Rc<Me2PlotDb>>,
-    me2_raw_plot: Option<Rc<RawPlotDb>>,
-    me3_plot: Option<Rc<Me3PlotDb>>,
-    me3_raw_plot: Option<Rc<RawPlotDb>>,
-    load_callback: Callback<Type>,
-}
-
-impl Databases {
-    pub fn get_me1_le_player_classes(self) -> Option<Rc<Me1LePlayerClassDb>> {
-        if self.me1_le_player_classes.is_none() {
-            self.load_database(Type::Me1LePlayerClasses);
+/// Declares every database with its `Type` and `Database` variants, its field in `Databases`,
+/// its getter and its file.
+macro_rules! databases {
+    ($($variant:ident($field:ident, $getter:ident): $db:ty = $path:literal,)*) => {
+        #[derive(Clone, Copy)]
+        pub enum Type {
+            $($variant,)*
         }
-        self.me1_le_player_classes
-    }
 
-    pub fn get_me1_plot(self) -> Option<Rc<Me1PlotDb>> {
-        if self.me1_plot.is_none() {
-            self.load_database(Type::Me1Plot);
-        }
-        self.me1_plot
-    }
+        impl Type {
+            fn path(self) -> &'static str {
+                match self {
+                    $(Type::$variant => $path,)*
+                }
+            }
 
-    pub fn get_me1_raw_plot(self) -> Option<Rc<RawPlotDb>> {
-        if self.me1_raw_plot.is_none() {
-            self.load_database(Type::Me1RawPlot);
+            fn deserialize(self, file: &str) -> Result<Database> {
+                let db = match self {
+                    $(Type::$variant => Database::$variant(ron::from_str(file)?),)*
+                };
+                Ok(db)
+            }
         }
-        self.me1_raw_plot
-    }
 
-    pub fn get_me1_item_db(self) -> Option<Rc<Me1ItemDb>> {
-        if self.me1_item_db.is_none() {
-            self.load_database(Type::Me1Items);
+        pub enum Database {
+            $($variant($db),)*
         }
-        self.me1_item_db
-    }
 
-    pub fn get_me2_plot(self) -> Option<Rc<Me2PlotDb>> {
-        if self.me2_plot.is_none() {
-            self.load_database(Type::Me2Plot);
+        #[derive(Clone, Default)]
+        pub struct Databases {
+            $($field: Option<Rc<$db>>,)*
+            load_callback: Callback<Type>,
         }
-        self.me2_plot
-    }
 
-    pub fn get_me2_raw_plot(self) -> Option<Rc<RawPlotDb>> {
-        if self.me2_raw_plot.is_none() {
-            self.load_database(Type::Me2RawPlot);
-        }
-        self.me2_raw_plot
-    }
+        impl Databases {
+            $(
+                pub fn $getter(self) -> Option<Rc<$db>> {
+                    if self.$field.is_none() {
+                        self.load_database(Type::$variant);
+                    }
+                    self.$field
+                }
+            )*
 
-    pub fn get_me3_plot(self) -> Option<Rc<Me3PlotDb>> {
-        if self.me3_plot.is_none() {
-            self.load_database(Type::Me3Plot);
+            fn set(&mut self, db: Database) {
+                match db {
+                    $(Database::$variant(db) => self.$field = Some(db.into()),)*
+                }
+            }
+
+            fn load_database(&self, db_type: Type) {
+                self.load_callback.emit(db_type);
+            }
         }
-        self.me3_plot
-    }
 
-    pub fn get_me3_raw_plot(self) -> Option<Rc<RawPlotDb>> {
-        if self.me3_raw_plot.is_none() {
-            self.load_database(Type::Me3RawPlot);
+        impl PartialEq for Databases {
+            fn eq(&self, other: &Self) -> bool {
+                true $(&& self.$field.is_some() == other.$field.is_some())*
+            }
         }
-        self.me3_raw_plot
-    }
+    };
+}
 
-    fn load_database(&self, db_type: Type) {
-        self.load_callback.emit(db_type);
-    }
+databases! {
+    Me1LePlayerClasses(me1_le_player_classes, get_me1_le_player_classes):
+        Me1LePlayerClassDb = "databases/me1_le_player_class_db.ron",
+    Me1Plot(me1_plot, get_me1_plot): Me1PlotDb = "databases/me1_plot_db.ron",
+    Me1RawPlot(me1_raw_plot, get_me1_raw_plot): RawPlotDb = "databases/me1_raw_plot_db.ron",
+    Me1Items(me1_item_db, get_me1_item_db): Me1ItemDb = "databases/me1_item_db.ron",
+    Me2Plot(me2_plot, get_me2_plot): Me2PlotDb = "databases/me2_plot_db.ron",
+    Me2RawPlot(me2_raw_plot, get_me2_raw_plot): RawPlotDb = "databases/me2_raw_plot_db.ron",
+    Me3Plot(me3_plot, get_me3_plot): Me3PlotDb = "databases/me3_plot_db.ron",
+    Me3RawPlot(me3_raw_plot, get_me3_raw_plot): RawPlotDb = "databases/me3_raw_plot_db.ron",
+    Me1LeProgression(me1_le_progression, get_me1_le_progression):
+        Me1LeProgressionDb = "databases/me1_le_progression_db.ron",
+    Me1LeHenchmen(me1_le_henchmen, get_me1_le_henchmen):
+        Me1LeHenchmanDb = "databases/me1_le_henchman_class_db.ron",
+    Me1LeGalaxy(me1_le_galaxy, get_me1_le_galaxy): Me1LeGalaxyDb = "databases/me1_le_galaxy_db.ron",
+    Me2Research(me2_research, get_me2_research): Me2ResearchDb = "databases/me2_research_db.ron",
+    Me2Galaxy(me2_galaxy, get_me2_galaxy): GalaxyDb = "databases/me2_galaxy_db.ron",
+    Me3Galaxy(me3_galaxy, get_me3_galaxy): GalaxyDb = "databases/me3_galaxy_db.ron",
+    Me3WarAssets(me3_war_assets, get_me3_war_assets):
+        Me3WarAssetsDb = "databases/me3_war_assets_db.ron",
+    Me3Power(me3_power, get_me3_power): Me3PowerDb = "databases/me3_power_db.ron",
+    Me3Weapon(me3_weapon, get_me3_weapon): Me3WeaponDb = "databases/me3_weapon_db.ron",
+    Me3Squad(me3_squad, get_me3_squad): Me3SquadDb = "databases/me3_squad_db.ron",
+    Me2Squad(me2_squad, get_me2_squad): Me2SquadDb = "databases/me2_squad_db.ron",
+    Me2Weapon(me2_weapon, get_me2_weapon): Me2WeaponDb = "databases/me2_weapon_db.ron",
+    Me2PlayerClasses(me2_player_classes, get_me2_player_classes):
+        Me2PlayerClassDb = "databases/me2_player_class_db.ron",
+    Me3PlayerClasses(me3_player_classes, get_me3_player_classes):
+        Me3PlayerClassDb = "databases/me3_player_class_db.ron",
+    Me1Journal(me1_journal, get_me1_journal): JournalDb = "databases/me1_journal_db.ron",
+    Me2Journal(me2_journal, get_me2_journal): JournalDb = "databases/me2_journal_db.ron",
+    Me3Journal(me3_journal, get_me3_journal): JournalDb = "databases/me3_journal_db.ron",
+    Me2Location(me2_location, get_me2_location): LocationDb = "databases/me2_location_db.ron",
+    Me3Location(me3_location, get_me3_location): LocationDb = "databases/me3_location_db.ron",
+    Me1Morality(me1_morality, get_me1_morality): MoralityDb = "databases/me1_morality_db.ron",
+    Me2Morality(me2_morality, get_me2_morality): MoralityDb = "databases/me2_morality_db.ron",
+    Me3Morality(me3_morality, get_me3_morality): MoralityDb = "databases/me3_morality_db.ron",
+    Me1Romance(me1_romance, get_me1_romance): RomanceDb = "databases/me1_romance_db.ron",
+    Me2Romance(me2_romance, get_me2_romance): RomanceDb = "databases/me2_romance_db.ron",
+    Me3Romance(me3_romance, get_me3_romance): RomanceDb = "databases/me3_romance_db.ron",
+    Me1Crew(me1_crew, get_me1_crew): CrewDb = "databases/me1_crew_db.ron",
+    Me2Crew(me2_crew, get_me2_crew): CrewDb = "databases/me2_crew_db.ron",
+    Me3Crew(me3_crew, get_me3_crew): CrewDb = "databases/me3_crew_db.ron",
+    Me2ImportBonus(me2_import_bonus, get_me2_import_bonus):
+        Me2ImportBonusDb = "databases/me2_import_bonus_db.ron",
 }
 
-impl PartialEq for Databases {
-    fn eq(&self, other: &Self) -> bool {
-        let Databases {
-            me1_le_player_classes,
-            me1_plot,
-            me1_raw_plot,
-            me1_item_db,
-            me2_plot,
-            me2_raw_plot,
-            me3_plot,
-            me3_raw_plot,
-            load_callback: _,
-        } = self;
-        me1_le_player_classes.is_some() == other.me1_le_player_classes.is_some()
-            && me1_plot.is_some() == other.me1_plot.is_some()
-            && me1_raw_plot.is_some() == other.me1_raw_plot.is_some()
-            && me1_item_db.is_some() == other.me1_item_db.is_some()
-            && me2_plot.is_some() == other.me2_plot.is_some()
-            && me2_raw_plot.is_some() == other.me2_raw_plot.is_some()
-            && me3_plot.is_some() == other.me3_plot.is_some()
-            && me3_raw_plot.is_some() == other.me3_raw_plot.is_some()
-    }
+pub enum Msg {
+    LoadDatabase(Type),
+    DatabaseLoaded(Box<Database>),
+    Error(Error),
 }
 
 #[derive(Properties, PartialEq)]
@@ -162,77 +162,11 @@ impl Component for DatabaseProvider {
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::LoadDatabase(db_type) => {
-                match db_type {
-                    Type::Me1LePlayerClasses => {
-                        Self::load_db(ctx, "databases/me1_le_player_class_db.ron", |response| {
-                            let db = ron::from_str(&response)?;
-                            Ok(Database::Me1LePlayerClasses(db))
-                        })
-                    }
-                    Type::Me1Plot => Self::load_db(ctx, "databases/me1_plot_db.ron", |response| {
-                        let db = ron::from_str(&response)?;
-                        Ok(Database::Me1Plot(db))
-                    }),
-                    Type::Me1RawPlot => {
-                        Self::load_db(ctx, "databases/me1_raw_plot_db.ron", |response| {
-                            let db = ron::from_str(&response)?;
-                            Ok(Database::Me1RawPlot(db))
-                        })
-                    }
-                    Type::Me1Items => Self::load_db(ctx, "databases/me1_item_db.ron", |response| {
-                        let db = ron::from_str(&response)?;
-                        Ok(Database::Me1Items(db))
-                    }),
-                    Type::Me2Plot => Self::load_db(ctx, "databases/me2_plot_db.ron", |response| {
-                        let db = ron::from_str(&response)?;
-                        Ok(Database::Me2Plot(db))
-                    }),
-                    Type::Me2RawPlot => {
-                        Self::load_db(ctx, "databases/me2_raw_plot_db.ron", |response| {
-                            let db = ron::from_str(&response)?;
-                            Ok(Database::Me2RawPlot(db))
-                        })
-                    }
-                    Type::Me3Plot => Self::load_db(ctx, "databases/me3_plot_db.ron", |response| {
-                        let db = ron::from_str(&response)?;
-                        Ok(Database::Me3Plot(db))
-                    }),
-                    Type::Me3RawPlot => {
-                        Self::load_db(ctx, "databases/me3_raw_plot_db.ron", |response| {
-                            let db = ron::from_str(&response)?;
-                            Ok(Database::Me3RawPlot(db))
-                        })
-                    }
-                }
+                Self::load_db(ctx, db_type);
                 false
             }
             Msg::DatabaseLoaded(db) => {
-                match *db {
-                    Database::Me1LePlayerClasses(db) => {
-                        self.dbs.me1_le_player_classes = Some(db.into());
-                    }
-                    Database::Me1Plot(db) => {
-                        self.dbs.me1_plot = Some(db.into());
-                    }
-                    Database::Me1RawPlot(db) => {
-                        self.dbs.me1_raw_plot = Some(db.into());
-                    }
-                    Database::Me1Items(db) => {
-                        self.dbs.me1_item_db = Some(db.into());
-                    }
-                    Database::Me2Plot(db) => {
-                        self.dbs.me2_plot = Some(db.into());
-                    }
-                    Database::Me2RawPlot(db) => {
-                        self.dbs.me2_raw_plot = Some(db.into());
-                    }
-                    Database::Me3Plot(db) => {
-                        self.dbs.me3_plot = Some(db.into());
-                    }
-                    Database::Me3RawPlot(db) => {
-                        self.dbs.me3_raw_plot = Some(db.into());
-                    }
-                }
+                self.dbs.set(*db);
                 true
             }
             Msg::Error(err) => {
@@ -252,15 +186,13 @@ impl Component for DatabaseProvider {
 }
 
 impl DatabaseProvider {
-    fn load_db<F>(ctx: &Context<Self>, path: &'static str, deserialize: F)
-    where
-        F: Fn(String) -> Result<Database> + 'static,
-    {
+    fn load_db(ctx: &Context<Self>, db_type: Type) {
+        let path = db_type.path();
         ctx.link().send_future(async move {
             let handle_db = async {
                 let rpc_file = rpc::load_database(path).await?;
                 let file = String::from_utf8(rpc_file.file.decode()?)?;
-                deserialize(file)
+                db_type.deserialize(&file)
             };
             match handle_db.await.context(format!("Failed to parse `/{}`", path)) {
                 Ok(db) => Msg::DatabaseLoaded(Box::new(db)),
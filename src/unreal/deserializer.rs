@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::mem;
 
 use encoding_rs::{UTF_16BE, UTF_16LE, WINDOWS_1252};
@@ -9,22 +10,51 @@ use serde::Deserialize;
 
 use super::Result;
 
+/// Name of the newtype struct read as an object of unknown size, see
+/// [`Deserializer::from_bytes_with_opaque`].
+pub const OPAQUE_OBJECT: &str = "OpaqueObject";
+
 pub struct Deserializer<'de> {
     input: &'de [u8],
     is_le: bool,
+    input_len: usize,
+    opaque_lengths: VecDeque<usize>,
 }
 
 impl<'de> Deserializer<'de> {
+    fn new(input: &'de [u8], is_le: bool, opaque_lengths: &[usize]) -> Self {
+        let opaque_lengths = opaque_lengths.iter().copied().collect();
+        Deserializer { input, is_le, input_len: input.len(), opaque_lengths }
+    }
+
     pub fn from_bytes<T: Deserialize<'de>>(input: &'de [u8]) -> Result<T> {
-        let mut deserializer = Deserializer { input, is_le: true };
+        let mut deserializer = Deserializer::new(input, true, &[]);
         T::deserialize(&mut deserializer)
     }
 
     pub fn from_be_bytes<T: Deserialize<'de>>(input: &'de [u8]) -> Result<T> {
-        let mut deserializer = Deserializer { input, is_le: false };
+        let mut deserializer = Deserializer::new(input, false, &[]);
         T::deserialize(&mut deserializer)
     }
 
+    /// Reads the whole `input`, the [`OPAQUE_OBJECT`]s found are `opaque_lengths` bytes long in
+    /// order. One past those is an [`Error::UnknownObject`](super::Error::UnknownObject) with
+    /// its position.
+    pub fn from_bytes_with_opaque<T: Deserialize<'de>>(
+        input: &'de [u8], opaque_lengths: &[usize],
+    ) -> Result<T> {
+        let mut deserializer = Deserializer::new(input, true, opaque_lengths);
+        let value = T::deserialize(&mut deserializer)?;
+        if !deserializer.input.is_empty() {
+            return Err(Error::custom("unexpected data at the end"));
+        }
+        Ok(value)
+    }
+
+    fn position(&self) -> usize {
+        self.input_len - self.input.len()
+    }
+
     fn read(&mut self, len: usize) -> Result<&[u8]> {
         if len > self.input.len() {
             return Err(super::Error::Eof);
@@ -139,7 +169,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
 
         let mut string = if len < 0 {
             // Unicode
-            let string_len = (len.abs() * 2) as usize;
+            let string_len = len.unsigned_abs() as usize * 2;
             let bytes = self.read(string_len)?.to_owned();
 
             let (decoded, _, had_errors) =
@@ -207,10 +237,17 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         unimplemented!()
     }
 
-    fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value>
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
+        if name == OPAQUE_OBJECT {
+            let len = match self.opaque_lengths.pop_front() {
+                Some(len) => len,
+                None => return Err(super::Error::UnknownObject(self.position())),
+            };
+            return visitor.visit_byte_buf(self.read(len)?.to_owned());
+        }
         visitor.visit_newtype_struct(self)
     }
 
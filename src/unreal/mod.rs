@@ -13,6 +13,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     Message(String),
     Eof,
+    /// An object of unknown size starts at this position.
+    UnknownObject(usize),
 }
 
 impl ser::Error for Error {
@@ -34,6 +36,9 @@ impl Display for Error {
             Error::Eof => formatter.write_str(
                 "Unexpected end of file, some data in your save are unexpected or your save is corrupted ?\n\
                 Save again and retry. If this error persists, please report a bug with your save attached"),
+            Error::UnknownObject(position) => {
+                write!(formatter, "The end of an unknown object at {} couldn't be found", position)
+            }
         }
     }
 }